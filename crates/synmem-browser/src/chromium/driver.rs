@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use chromiumoxide::{Browser, BrowserConfig, Page};
 use futures::StreamExt;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, instrument};
@@ -200,7 +201,7 @@ impl BrowserDriverPort for ChromiumDriver {
         Ok(())
     }
 
-    #[instrument(skip(self))]
+    #[instrument(skip(self, text))]
     async fn type_text(&self, selector: &str, text: &str) -> Result<(), Self::Error> {
         info!(selector = %selector, "Typing text into element");
         let page = self.get_or_create_page().await?;
@@ -250,6 +251,27 @@ impl BrowserDriverPort for ChromiumDriver {
         Ok(())
     }
 
+    #[instrument(skip(self))]
+    async fn upload_files(&self, selector: &str, paths: &[PathBuf]) -> Result<(), Self::Error> {
+        info!(selector = %selector, count = %paths.len(), "Uploading files");
+        let page = self.get_or_create_page().await?;
+        let element = page
+            .find_element(selector)
+            .await
+            .map_err(|_| ChromiumError::ElementNotFound {
+                selector: selector.to_string(),
+            })?;
+
+        let mut params = chromiumoxide::cdp::browser_protocol::dom::SetFileInputFilesParams::new(
+            paths.iter().map(|path| path.display().to_string()).collect(),
+        );
+        params.backend_node_id = Some(element.backend_node_id);
+        page.execute(params)
+            .await
+            .map_err(|e| ChromiumError::InteractionError(e.to_string()))?;
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     async fn wait_for_element(&self, selector: &str, timeout_ms: u64) -> Result<(), Self::Error> {
         debug!(selector = %selector, timeout_ms = %timeout_ms, "Waiting for element");
@@ -333,7 +355,7 @@ impl BrowserDriverPort for ChromiumDriver {
         Ok(cookies.iter().map(Self::convert_cookie).collect())
     }
 
-    #[instrument(skip(self))]
+    #[instrument(skip(self, cookies))]
     async fn set_cookies(&self, cookies: &[SimpleCookie]) -> Result<(), Self::Error> {
         debug!(count = %cookies.len(), "Setting cookies");
         let page = self.get_or_create_page().await?;
@@ -347,7 +369,7 @@ impl BrowserDriverPort for ChromiumDriver {
                 .secure(cookie.secure)
                 .http_only(cookie.http_only)
                 .build()
-                .map_err(|e| ChromiumError::SessionError(e))?;
+                .map_err(ChromiumError::SessionError)?;
 
             page.set_cookie(cdp_cookie)
                 .await
@@ -5,6 +5,7 @@ mod session_manager;
 mod dom_extractor;
 mod error;
 
+pub use dom_extractor::{DomExtractor, ExtractedContent, ExtractedLink};
 pub use driver::ChromiumDriver;
 pub use error::ChromiumError;
 pub use session_manager::BrowserStateManager;
@@ -68,7 +68,7 @@ impl BatchScraper {
     /// For production use, consider using a proper HTML parser like `scraper`.
     fn extract_text(html: &str) -> String {
         // Split by < and > to separate tags from content
-        let parts: Vec<&str> = html.split(|c| c == '<' || c == '>').collect();
+        let parts: Vec<&str> = html.split(['<', '>']).collect();
         
         // Known HTML tag names to filter out
         const HTML_TAGS: &[&str] = &[
@@ -19,7 +19,7 @@ impl PageProcessor {
     pub fn extract_all_text(documents: &[String]) -> Vec<String> {
         Self::process_documents(documents, |html| {
             // Simple text extraction (in production, use proper HTML parser)
-            html.split(|c| c == '<' || c == '>')
+            html.split(['<', '>'])
                 .filter(|s| !s.trim().is_empty() && !s.starts_with('/'))
                 .filter(|s| !s.chars().all(|c| c.is_whitespace() || c == '\n'))
                 .collect::<Vec<_>>()
@@ -1,6 +1,7 @@
 //! Automation service for browser interactions
 
 use crate::ports::outbound::BrowserDriverPort;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// Service for automating browser interactions
@@ -29,6 +30,11 @@ impl<D: BrowserDriverPort> AutomationService<D> {
         self.driver.select(selector, value).await
     }
 
+    /// Set the files of a file input to local paths
+    pub async fn upload_files(&self, selector: &str, paths: &[PathBuf]) -> Result<(), D::Error> {
+        self.driver.upload_files(selector, paths).await
+    }
+
     /// Wait for an element to be present
     pub async fn wait_for_element(&self, selector: &str, timeout_ms: u64) -> Result<(), D::Error> {
         self.driver.wait_for_element(selector, timeout_ms).await
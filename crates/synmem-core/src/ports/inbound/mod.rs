@@ -2,11 +2,13 @@
 
 pub mod session_control;
 
+mod automation;
 mod browser_control;
 mod scraper;
 mod memory_query;
 
 pub use session_control::SessionControlPort;
+pub use automation::*;
 pub use browser_control::*;
 pub use scraper::*;
 pub use memory_query::*;
@@ -6,6 +6,7 @@
 use crate::domain::entities::{BrowserState, SimpleCookie};
 use async_trait::async_trait;
 use std::error::Error;
+use std::path::PathBuf;
 
 /// Port for browser driver implementations
 ///
@@ -44,6 +45,9 @@ pub trait BrowserDriverPort: Send + Sync {
     /// Select an option from a dropdown by value
     async fn select(&self, selector: &str, value: &str) -> Result<(), Self::Error>;
 
+    /// Set the files of a file input to local paths
+    async fn upload_files(&self, selector: &str, paths: &[PathBuf]) -> Result<(), Self::Error>;
+
     /// Wait for an element to be present
     async fn wait_for_element(&self, selector: &str, timeout_ms: u64) -> Result<(), Self::Error>;
 
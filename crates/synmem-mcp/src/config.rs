@@ -0,0 +1,263 @@
+//! Server configuration
+//!
+//! Loaded from a JSON file passed to the binary with `--config`.
+//!
+//! ```json
+//! {
+//!   "session_storage_path": ".synmem/sessions",
+//!   "storage_path": ".synmem/data",
+//!   "rate_limit_path": ".synmem/rate_limits",
+//!   "media_path": ".synmem/media",
+//!   "lock_path": ".synmem/locks",
+//!   "http": { "bind": "127.0.0.1:8931" },
+//!   "tools": {
+//!     "disabled": ["twitter_post"],
+//!     "retry": {
+//!       "twitter_get_*": { "max_attempts": 3, "deadline_seconds": 60 },
+//!       "twitter_like": { "max_attempts": 2 }
+//!     }
+//!   }
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur while loading the configuration
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// The file could not be read
+    #[error("Failed to read config file {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// The file is not valid configuration JSON
+    #[error("Invalid config file {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+}
+
+/// Top-level server configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerConfig {
+    /// Directory holding the encrypted session store
+    #[serde(default)]
+    pub session_storage_path: Option<PathBuf>,
+    /// Directory holding saved pages, macros and other server state
+    #[serde(default)]
+    pub storage_path: Option<PathBuf>,
+    /// Directory holding rate limiter budgets, shared by every server
+    /// running with the same path
+    #[serde(default)]
+    pub rate_limit_path: Option<PathBuf>,
+    /// Directory holding the media downloaded by the Twitter tools
+    #[serde(default)]
+    pub media_path: Option<PathBuf>,
+    /// Directory holding the locks of the state in `storage_path`, shared
+    /// by every server running with the same path
+    #[serde(default)]
+    pub lock_path: Option<PathBuf>,
+    /// Serve over HTTP instead of stdio
+    #[serde(default)]
+    pub http: Option<HttpConfig>,
+    /// Which tools are exposed
+    #[serde(default)]
+    pub tools: ToolsConfig,
+}
+
+impl ServerConfig {
+    /// Load the configuration from a JSON file
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_str(&content).map_err(|e| ConfigError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    }
+}
+
+/// HTTP transport settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// Address to listen on
+    pub bind: SocketAddr,
+    /// Token clients must send as `Authorization: Bearer <token>`
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+}
+
+/// Tool selection
+///
+/// Entries are tool names or prefixes ending in `*` (e.g. `"twitter_*"`).
+/// When `enabled` is set only matching tools are exposed; `disabled` is
+/// applied afterwards.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolsConfig {
+    /// Allow-list of tools (all tools when absent)
+    #[serde(default)]
+    pub enabled: Option<Vec<String>>,
+    /// Deny-list of tools
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// Retry policies by tool name or pattern
+    #[serde(default)]
+    pub retry: RetryPolicies,
+}
+
+impl ToolsConfig {
+    /// Check whether a tool is exposed under this configuration
+    pub fn is_enabled(&self, tool: &str) -> bool {
+        let allowed = self
+            .enabled
+            .as_ref()
+            .map(|patterns| patterns.iter().any(|p| matches_pattern(p, tool)))
+            .unwrap_or(true);
+
+        allowed && !self.disabled.iter().any(|p| matches_pattern(p, tool))
+    }
+}
+
+/// Retry policies keyed by tool name or `prefix*` pattern
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RetryPolicies(pub HashMap<String, RetryPolicy>);
+
+impl RetryPolicies {
+    /// Policy configured for a tool
+    ///
+    /// An entry naming the tool wins over patterns, and longer patterns
+    /// win over shorter ones.
+    pub fn policy_for(&self, tool: &str) -> Option<RetryPolicy> {
+        self.0
+            .iter()
+            .filter(|(pattern, _)| matches_pattern(pattern, tool))
+            .max_by_key(|(pattern, _)| (pattern.as_str() == tool, pattern.len()))
+            .map(|(_, policy)| *policy)
+    }
+
+    /// Policy configured for a tool by its exact name, ignoring patterns
+    ///
+    /// For tools that are unsafe to repeat, such as posting: a pattern
+    /// meant for reads must not make them post twice.
+    pub fn exact_policy_for(&self, tool: &str) -> Option<RetryPolicy> {
+        self.0.get(tool).copied()
+    }
+}
+
+/// How a failed tool call is retried
+///
+/// Only errors the tool reports as recoverable are retried. Missing fields
+/// take their default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further retry
+    pub initial_backoff_ms: u64,
+    /// Longest delay between two attempts
+    pub max_backoff_ms: u64,
+    /// Give up rather than wait past this many seconds since the first
+    /// attempt
+    pub deadline_seconds: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+            deadline_seconds: 120,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy making a single attempt, for calls that are unsafe to repeat
+    pub fn single_attempt() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+}
+
+/// Match a tool name against an exact name or a `prefix*` pattern
+fn matches_pattern(pattern: &str, tool: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => tool.starts_with(prefix),
+        None => pattern == tool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tools_enabled_by_default() {
+        let config = ToolsConfig::default();
+        assert!(config.is_enabled("twitter_post"));
+    }
+
+    #[test]
+    fn test_allow_and_deny_lists() {
+        let config = ToolsConfig {
+            enabled: Some(vec!["twitter_*".to_string()]),
+            disabled: vec!["twitter_post".to_string()],
+            ..Default::default()
+        };
+        assert!(config.is_enabled("twitter_search"));
+        assert!(!config.is_enabled("twitter_post"));
+        assert!(!config.is_enabled("browser_navigate"));
+    }
+
+    #[test]
+    fn test_load_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, r#"{"tools": {"disabled": ["twitter_post"]}}"#).unwrap();
+
+        let config = ServerConfig::load(&path).unwrap();
+        assert!(config.session_storage_path.is_none());
+        assert!(config.storage_path.is_none());
+        assert!(config.rate_limit_path.is_none());
+        assert!(config.media_path.is_none());
+        assert!(config.lock_path.is_none());
+        assert!(config.http.is_none());
+        assert!(!config.tools.is_enabled("twitter_post"));
+
+        std::fs::write(
+            &path,
+            r#"{"http": {"bind": "127.0.0.1:8931", "bearer_token": "s3cret"}}"#,
+        )
+        .unwrap();
+        let http = ServerConfig::load(&path).unwrap().http.unwrap();
+        assert_eq!(http.bind.port(), 8931);
+        assert_eq!(http.bearer_token.as_deref(), Some("s3cret"));
+
+        std::fs::write(
+            &path,
+            r#"{"tools": {"retry": {"twitter_*": {"max_attempts": 5}, "twitter_post": {}}}}"#,
+        )
+        .unwrap();
+        let tools = ServerConfig::load(&path).unwrap().tools;
+        assert_eq!(tools.retry.policy_for("twitter_search").unwrap().max_attempts, 5);
+        assert_eq!(tools.retry.policy_for("twitter_post"), Some(RetryPolicy::default()));
+        assert!(tools.retry.policy_for("browser_click").is_none());
+        assert_eq!(tools.retry.exact_policy_for("twitter_post"), Some(RetryPolicy::default()));
+        assert!(tools.retry.exact_policy_for("twitter_search").is_none());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(ServerConfig::load(&path), Err(ConfigError::Parse { .. })));
+    }
+}
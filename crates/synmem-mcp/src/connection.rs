@@ -0,0 +1,275 @@
+//! Per-connection state
+//!
+//! Each MCP client gets a [`Connection`], opened through [`Connections`] by
+//! the transport when the client connects (stdio) or initializes a session
+//! (HTTP). It carries the client information, the resources the client
+//! subscribed to, the requests still in flight (so they can be cancelled)
+//! and the channel used to push server-to-client notifications.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Weak};
+
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+use crate::protocol::{Implementation, JsonRpcNotification, RequestId};
+
+/// Number of notifications buffered per connection before old ones are dropped
+const NOTIFICATION_BUFFER: usize = 256;
+
+/// State of a single client connection
+pub struct Connection {
+    id: String,
+    notifications: broadcast::Sender<JsonRpcNotification>,
+    client_info: Mutex<Option<Implementation>>,
+    subscriptions: Mutex<HashSet<String>>,
+    in_flight: Mutex<HashMap<RequestId, CancellationToken>>,
+}
+
+impl Connection {
+    /// Create a new connection with the given ID
+    pub fn new(id: impl Into<String>) -> Self {
+        let (notifications, _) = broadcast::channel(NOTIFICATION_BUFFER);
+        Self {
+            id: id.into(),
+            notifications,
+            client_info: Mutex::new(None),
+            subscriptions: Mutex::new(HashSet::new()),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Connection ID (the `Mcp-Session-Id` for HTTP clients)
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Client information sent during `initialize`
+    pub fn client_info(&self) -> Option<Implementation> {
+        self.client_info.lock().unwrap().clone()
+    }
+
+    /// Record the client information sent during `initialize`
+    pub fn set_client_info(&self, info: Implementation) {
+        *self.client_info.lock().unwrap() = Some(info);
+    }
+
+    /// Send a notification to the client
+    ///
+    /// Notifications sent while no transport stream is listening are dropped.
+    pub fn notify(&self, method: &str, params: Option<Value>) {
+        let _ = self
+            .notifications
+            .send(JsonRpcNotification::new(method, params));
+    }
+
+    /// Subscribe to the notifications sent to this client
+    pub fn subscribe(&self) -> broadcast::Receiver<JsonRpcNotification> {
+        self.notifications.subscribe()
+    }
+
+    /// Whether a transport stream is listening for notifications
+    pub fn has_listeners(&self) -> bool {
+        self.notifications.receiver_count() > 0
+    }
+
+    /// Sender for the notifications of this client
+    pub(crate) fn notifier(&self) -> broadcast::Sender<JsonRpcNotification> {
+        self.notifications.clone()
+    }
+
+    /// Track a request until the returned guard is dropped
+    ///
+    /// The guard's token is cancelled by [`Connection::cancel_request`].
+    pub fn begin_request(&self, id: &RequestId) -> InFlightRequest<'_> {
+        let token = CancellationToken::new();
+        self.in_flight
+            .lock()
+            .unwrap()
+            .insert(id.clone(), token.clone());
+        InFlightRequest {
+            connection: self,
+            id: id.clone(),
+            token,
+        }
+    }
+
+    /// Cancel an in-flight request
+    ///
+    /// Returns `false` when the request already finished or never existed.
+    pub fn cancel_request(&self, id: &RequestId) -> bool {
+        match self.in_flight.lock().unwrap().get(id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Start sending `notifications/resources/updated` for `uri`
+    pub fn subscribe_resource(&self, uri: &str) {
+        self.subscriptions.lock().unwrap().insert(uri.to_string());
+    }
+
+    /// Stop sending updates for `uri`
+    pub fn unsubscribe_resource(&self, uri: &str) {
+        self.subscriptions.lock().unwrap().remove(uri);
+    }
+
+    /// Whether the client subscribed to `uri`
+    pub fn is_subscribed(&self, uri: &str) -> bool {
+        self.subscriptions.lock().unwrap().contains(uri)
+    }
+}
+
+/// A request tracked by [`Connection::begin_request`]
+pub struct InFlightRequest<'a> {
+    connection: &'a Connection,
+    id: RequestId,
+    token: CancellationToken,
+}
+
+impl InFlightRequest<'_> {
+    /// Token cancelled when the client cancels the request
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for InFlightRequest<'_> {
+    fn drop(&mut self) {
+        self.connection.in_flight.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// The open client connections
+///
+/// Holds weak references only; a connection lives as long as its transport
+/// keeps it.
+#[derive(Default)]
+pub struct Connections {
+    connections: Mutex<HashMap<String, Weak<Connection>>>,
+}
+
+impl Connections {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a connection with the given ID
+    pub fn open(&self, id: impl Into<String>) -> Arc<Connection> {
+        let connection = Arc::new(Connection::new(id));
+        let mut connections = self.connections.lock().unwrap();
+        connections.retain(|_, c| c.strong_count() > 0);
+        connections.insert(connection.id().to_string(), Arc::downgrade(&connection));
+        connection
+    }
+
+    /// Forget the connection with the given ID
+    pub fn close(&self, id: &str) {
+        self.connections.lock().unwrap().remove(id);
+    }
+
+    /// Connections that are still open
+    pub fn all(&self) -> Vec<Arc<Connection>> {
+        self.connections
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
+    /// Notify subscribers that the resource at `uri` changed
+    pub fn resource_updated(&self, uri: &str) {
+        for connection in self.all().iter().filter(|c| c.is_subscribed(uri)) {
+            connection.notify(
+                "notifications/resources/updated",
+                Some(serde_json::json!({ "uri": uri })),
+            );
+        }
+    }
+
+    /// Notify every client that the list of resources changed
+    pub fn resource_list_changed(&self) {
+        for connection in self.all() {
+            connection.notify("notifications/resources/list_changed", None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_notify_reaches_subscribers() {
+        let connection = Connection::new("conn-1");
+        let mut receiver = connection.subscribe();
+
+        connection.notify("notifications/message", Some(json!({"level": "info"})));
+
+        let notification = receiver.recv().await.unwrap();
+        assert_eq!(notification.method, "notifications/message");
+        assert_eq!(connection.id(), "conn-1");
+    }
+
+    #[tokio::test]
+    async fn test_resource_updates_reach_subscribers_only() {
+        let connections = Connections::new();
+        let subscribed = connections.open("a");
+        let other = connections.open("b");
+        let mut subscribed_rx = subscribed.subscribe();
+        let mut other_rx = other.subscribe();
+
+        subscribed.subscribe_resource("synmem://pages/1");
+        connections.resource_updated("synmem://pages/1");
+
+        let notification = subscribed_rx.recv().await.unwrap();
+        assert_eq!(notification.method, "notifications/resources/updated");
+        assert_eq!(notification.params.unwrap()["uri"], "synmem://pages/1");
+        assert!(other_rx.try_recv().is_err());
+
+        subscribed.unsubscribe_resource("synmem://pages/1");
+        connections.resource_updated("synmem://pages/1");
+        assert!(subscribed_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_closed_connections_are_forgotten() {
+        let connections = Connections::new();
+        let kept = connections.open("kept");
+        drop(connections.open("dropped"));
+        let closed = connections.open("closed");
+        connections.close(closed.id());
+
+        let open: Vec<String> = connections.all().iter().map(|c| c.id().to_string()).collect();
+        assert_eq!(open, vec![kept.id().to_string()]);
+    }
+
+    #[test]
+    fn test_cancel_in_flight_request() {
+        let connection = Connection::new("conn-3");
+        let id = RequestId::Number(7);
+
+        let request = connection.begin_request(&id);
+        let token = request.token();
+        assert!(connection.cancel_request(&id));
+        assert!(token.is_cancelled());
+
+        drop(request);
+        assert!(!connection.cancel_request(&id));
+        assert!(!connection.cancel_request(&RequestId::Number(8)));
+    }
+
+    #[test]
+    fn test_notify_without_subscribers_is_dropped() {
+        let connection = Connection::new("conn-2");
+        connection.notify("notifications/message", None);
+        assert!(connection.client_info().is_none());
+    }
+}
@@ -1,8 +1,22 @@
 //! SynMem MCP Server - Browser Automation Tools
 //!
-//! This crate provides MCP (Model Context Protocol) tools for browser automation,
-//! including Twitter/X automation tools.
+//! This crate provides an MCP (Model Context Protocol) server for browser
+//! automation, including Twitter/X automation tools. The `synmem-mcp`
+//! binary serves it over stdio or HTTP.
 
+pub mod config;
+pub mod connection;
+pub mod protocol;
+pub mod resources;
+pub mod schema;
+pub mod server;
+pub mod storage;
 pub mod tools;
+pub mod transport;
 
+#[cfg(test)]
+mod testing;
+
+pub use connection::Connection;
+pub use server::McpServer;
 pub use tools::twitter;
@@ -0,0 +1,174 @@
+//! `synmem-mcp` binary
+//!
+//! Runs the SynMem MCP server over stdio, or over HTTP when the
+//! configuration has an `http` section.
+//!
+//! Usage: `synmem-mcp [--config <path>]`
+//!
+//! The HTTP bearer token may also be set with `SYNMEM_MCP_BEARER_TOKEN`.
+//!
+//! The Twitter session is loaded from an encrypted session profile when
+//! `SYNMEM_TWITTER_PROFILE` names one (e.g. "twitter-main"), decrypted
+//! with `SYNMEM_SESSION_PASSWORD`. Otherwise it is read from the
+//! environment:
+//! - `SYNMEM_TWITTER_COOKIES` - cookie header for x.com
+//! - `SYNMEM_TWITTER_CSRF_TOKEN` - value of the `ct0` cookie
+//! - `SYNMEM_TWITTER_BEARER_TOKEN` - web client bearer token
+//! - `SYNMEM_TWITTER_USER_ID` - authenticated user ID (optional)
+//!
+//! Tweets scheduled with `twitter_schedule_post` are kept in the storage
+//! directory and posted by a background task while the server runs with a
+//! Twitter session.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use synmem_browser::ChromiumDriver;
+use synmem_core::{SessionManager, SessionManagerConfig};
+use synmem_mcp::config::ServerConfig;
+use synmem_mcp::storage::FileStorage;
+use synmem_mcp::tools::{default_registry, ToolContext};
+use synmem_mcp::transport::{serve_http, serve_stdio};
+use synmem_mcp::twitter::{spawn_scheduler, MediaStore, RateLimitStore, TwitterSession};
+use synmem_mcp::McpServer;
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
+
+/// Where pages and macros are stored when the configuration does not say
+const DEFAULT_STORAGE_PATH: &str = ".synmem/data";
+
+/// Where rate limiter budgets are kept when the configuration does not say
+const DEFAULT_RATE_LIMIT_PATH: &str = ".synmem/rate_limits";
+
+/// Where downloaded media is kept when the configuration does not say
+const DEFAULT_MEDIA_PATH: &str = ".synmem/media";
+
+/// Where locks are kept when the configuration does not say
+const DEFAULT_LOCK_PATH: &str = ".synmem/locks";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // stdout carries the protocol, so logs go to stderr
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+
+    let config = match config_path_from_args()? {
+        Some(path) => ServerConfig::load(&path)?,
+        None => ServerConfig::default(),
+    };
+
+    let mut session_config = SessionManagerConfig::default();
+    if let Some(path) = &config.session_storage_path {
+        session_config.storage_path = path.clone();
+    }
+
+    let driver = ChromiumDriver::new()
+        .await
+        .context("Failed to launch the browser")?;
+    let storage_path = config
+        .storage_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_STORAGE_PATH));
+    let rate_limit_path = config
+        .rate_limit_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_RATE_LIMIT_PATH));
+    let media_path = config
+        .media_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_MEDIA_PATH));
+    let lock_path = config
+        .lock_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_LOCK_PATH));
+    let context = ToolContext::new(Arc::new(driver), SessionManager::new(session_config))
+        .with_storage(Arc::new(FileStorage::new(storage_path)))
+        .with_lock_dir(lock_path)
+        .with_rate_limit_store(RateLimitStore::new(rate_limit_path))
+        .with_media_store(MediaStore::new(media_path))
+        .with_retry_policies(config.tools.retry.clone());
+    let session = match std::env::var("SYNMEM_TWITTER_PROFILE") {
+        Ok(profile) => session_from_profile(&context, &profile).await?,
+        Err(_) => session_from_env(),
+    };
+    if session.is_authenticated() {
+        spawn_scheduler(&context, session.clone());
+    } else {
+        warn!("No Twitter session, scheduled tweets will not be published");
+    }
+    let registry = default_registry(&config.tools, session);
+    info!(tools = ?registry.names(), "Tools registered");
+
+    let server = Arc::new(McpServer::new(registry, context));
+    match &config.http {
+        Some(http) => {
+            let listener = tokio::net::TcpListener::bind(http.bind)
+                .await
+                .with_context(|| format!("Failed to bind {}", http.bind))?;
+            let bearer_token = http
+                .bearer_token
+                .clone()
+                .or_else(|| std::env::var("SYNMEM_MCP_BEARER_TOKEN").ok());
+            serve_http(server, listener, bearer_token, async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await?;
+        }
+        None => serve_stdio(server).await?,
+    }
+    Ok(())
+}
+
+/// Read the `--config <path>` argument
+fn config_path_from_args() -> anyhow::Result<Option<PathBuf>> {
+    let mut args = std::env::args().skip(1);
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => {
+                let value = args.next().context("--config requires a path")?;
+                path = Some(PathBuf::from(value));
+            }
+            other => anyhow::bail!("Unknown argument: {}", other),
+        }
+    }
+    Ok(path)
+}
+
+/// Load the Twitter session from an encrypted profile
+///
+/// Also injects the profile's cookies into the browser.
+async fn session_from_profile(
+    context: &ToolContext<ChromiumDriver>,
+    profile: &str,
+) -> anyhow::Result<TwitterSession> {
+    let password = std::env::var("SYNMEM_SESSION_PASSWORD")
+        .context("SYNMEM_SESSION_PASSWORD is required to load SYNMEM_TWITTER_PROFILE")?;
+    let mut session_manager = context.session_manager.lock().await;
+    let mut session =
+        TwitterSession::from_profile(&mut session_manager, profile, &password, &context.driver)
+            .await
+            .with_context(|| format!("Failed to load the Twitter profile {}", profile))?;
+    if let Ok(bearer_token) = std::env::var("SYNMEM_TWITTER_BEARER_TOKEN") {
+        session.bearer_token = bearer_token;
+    }
+    info!(profile, "Twitter session loaded from profile");
+    Ok(session)
+}
+
+/// Build the Twitter session from environment variables
+///
+/// Missing variables leave the corresponding field empty, in which case the
+/// Twitter tools report `NoSession`.
+fn session_from_env() -> TwitterSession {
+    let var = |name: &str| std::env::var(name).unwrap_or_default();
+    TwitterSession {
+        cookies: var("SYNMEM_TWITTER_COOKIES"),
+        csrf_token: var("SYNMEM_TWITTER_CSRF_TOKEN"),
+        bearer_token: var("SYNMEM_TWITTER_BEARER_TOKEN"),
+        user_id: std::env::var("SYNMEM_TWITTER_USER_ID").ok(),
+    }
+}
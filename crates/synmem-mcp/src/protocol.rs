@@ -0,0 +1,492 @@
+//! MCP protocol types
+//!
+//! JSON-RPC 2.0 envelopes and the subset of Model Context Protocol
+//! messages implemented by the SynMem server.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// JSON-RPC version string sent with every message
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// MCP protocol revision implemented by this server
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Request identifier (either a number or a string)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RequestId {
+    /// Numeric identifier
+    Number(i64),
+    /// String identifier
+    String(String),
+}
+
+/// Token correlating `notifications/progress` with the request that asked
+/// for them
+pub type ProgressToken = RequestId;
+
+/// An incoming JSON-RPC message
+///
+/// Requests carry an `id`; notifications do not and never receive a response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcRequest {
+    /// Must be "2.0"
+    pub jsonrpc: String,
+    /// Request ID (absent for notifications)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<RequestId>,
+    /// Method name
+    pub method: String,
+    /// Method parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl JsonRpcRequest {
+    /// Create a new request
+    pub fn new(id: RequestId, method: impl Into<String>, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: Some(id),
+            method: method.into(),
+            params,
+        }
+    }
+
+    /// Whether this message is a notification (no response expected)
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+}
+
+/// An outgoing JSON-RPC response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+    /// Always "2.0"
+    pub jsonrpc: String,
+    /// ID of the request being answered (null if it could not be determined)
+    pub id: Option<RequestId>,
+    /// Result on success
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// Error on failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+impl JsonRpcResponse {
+    /// Create a successful response
+    pub fn success(id: Option<RequestId>, result: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    /// Create an error response
+    pub fn failure(id: Option<RequestId>, error: JsonRpcError) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// An outgoing JSON-RPC notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    /// Always "2.0"
+    pub jsonrpc: String,
+    /// Notification method
+    pub method: String,
+    /// Notification parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl JsonRpcNotification {
+    /// Create a new notification
+    pub fn new(method: impl Into<String>, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+/// JSON-RPC error object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    /// Error code
+    pub code: i64,
+    /// Short description
+    pub message: String,
+    /// Additional error data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl JsonRpcError {
+    /// Invalid JSON was received
+    pub const PARSE_ERROR: i64 = -32700;
+    /// The JSON sent is not a valid request object
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// The method does not exist
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// Invalid method parameters
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// Internal server error
+    pub const INTERNAL_ERROR: i64 = -32603;
+    /// The requested resource does not exist (MCP extension)
+    pub const RESOURCE_NOT_FOUND: i64 = -32002;
+
+    /// Create a new error
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Attach additional data to the error
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Parse error
+    pub fn parse_error(message: impl Into<String>) -> Self {
+        Self::new(Self::PARSE_ERROR, message)
+    }
+
+    /// Invalid request error
+    pub fn invalid_request(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_REQUEST, message)
+    }
+
+    /// Method not found error
+    pub fn method_not_found(method: &str) -> Self {
+        Self::new(Self::METHOD_NOT_FOUND, format!("Method not found: {}", method))
+    }
+
+    /// Invalid params error
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_PARAMS, message)
+    }
+
+    /// Internal error
+    pub fn internal_error(message: impl Into<String>) -> Self {
+        Self::new(Self::INTERNAL_ERROR, message)
+    }
+
+    /// Resource not found error
+    pub fn resource_not_found(uri: &str) -> Self {
+        Self::new(Self::RESOURCE_NOT_FOUND, format!("Resource not found: {}", uri))
+            .with_data(serde_json::json!({ "uri": uri }))
+    }
+}
+
+/// Name and version of an MCP implementation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Implementation {
+    /// Implementation name
+    pub name: String,
+    /// Implementation version
+    pub version: String,
+}
+
+/// Parameters of the `initialize` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeParams {
+    /// Protocol version requested by the client
+    pub protocol_version: String,
+    /// Client capabilities
+    #[serde(default)]
+    pub capabilities: Value,
+    /// Client information
+    pub client_info: Option<Implementation>,
+}
+
+/// Result of the `initialize` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeResult {
+    /// Protocol version the server will speak
+    pub protocol_version: String,
+    /// Server capabilities
+    pub capabilities: ServerCapabilities,
+    /// Server information
+    pub server_info: Implementation,
+    /// Optional usage instructions for the client
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+}
+
+/// Capabilities advertised by the server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCapabilities {
+    /// Tool support
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<ToolsCapability>,
+    /// Resource support
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourcesCapability>,
+}
+
+/// Tool capability flags
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolsCapability {
+    /// Whether the server emits `notifications/tools/list_changed`
+    pub list_changed: bool,
+}
+
+/// Resource capability flags
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourcesCapability {
+    /// Whether clients may subscribe to resource updates
+    pub subscribe: bool,
+    /// Whether the server emits `notifications/resources/list_changed`
+    pub list_changed: bool,
+}
+
+/// A tool advertised through `tools/list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolDefinition {
+    /// Unique tool name
+    pub name: String,
+    /// Human-readable description
+    pub description: String,
+    /// JSON Schema for the tool arguments
+    pub input_schema: Value,
+}
+
+/// Result of the `tools/list` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListToolsResult {
+    /// Available tools
+    pub tools: Vec<ToolDefinition>,
+}
+
+/// Parameters of the `tools/call` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallToolParams {
+    /// Name of the tool to call
+    pub name: String,
+    /// Tool arguments
+    #[serde(default)]
+    pub arguments: Option<Value>,
+    /// Request metadata
+    #[serde(default, rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RequestMeta>,
+}
+
+/// Metadata sent by the client in `params._meta`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestMeta {
+    /// Token to attach to progress notifications for this request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress_token: Option<ProgressToken>,
+}
+
+/// Parameters of `notifications/progress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressParams {
+    /// Token from the originating request
+    pub progress_token: ProgressToken,
+    /// Work done so far
+    pub progress: u64,
+    /// Total amount of work, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
+/// Parameters of `notifications/cancelled`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelledParams {
+    /// ID of the request to cancel
+    pub request_id: RequestId,
+    /// Why the client cancelled it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Result of the `tools/call` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallToolResult {
+    /// Content returned by the tool
+    pub content: Vec<Content>,
+    /// Whether the tool reported an error
+    #[serde(default)]
+    pub is_error: bool,
+}
+
+impl CallToolResult {
+    /// Successful result with a single text item
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            content: vec![Content::text(text)],
+            is_error: false,
+        }
+    }
+
+    /// Error result with a single text item
+    pub fn error(text: impl Into<String>) -> Self {
+        Self {
+            content: vec![Content::text(text)],
+            is_error: true,
+        }
+    }
+
+    /// Successful result holding `value` as pretty-printed JSON text
+    pub fn json<T: Serialize>(value: &T) -> Self {
+        match serde_json::to_string_pretty(value) {
+            Ok(text) => Self::text(text),
+            Err(e) => Self::error(format!("Failed to serialize result: {}", e)),
+        }
+    }
+}
+
+/// A resource advertised through `resources/list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    /// Resource URI
+    pub uri: String,
+    /// Human-readable name
+    pub name: String,
+    /// Description of the resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// MIME type of the contents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Result of the `resources/list` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListResourcesResult {
+    /// Available resources
+    pub resources: Vec<Resource>,
+}
+
+/// A parameterized resource advertised through `resources/templates/list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplate {
+    /// RFC 6570 URI template
+    pub uri_template: String,
+    /// Human-readable name
+    pub name: String,
+    /// Description of the resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// MIME type of the contents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Result of the `resources/templates/list` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResourceTemplatesResult {
+    /// Available templates
+    pub resource_templates: Vec<ResourceTemplate>,
+}
+
+/// Parameters of `resources/read`, `resources/subscribe` and
+/// `resources/unsubscribe`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceParams {
+    /// Resource URI
+    pub uri: String,
+}
+
+/// Result of the `resources/read` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadResourceResult {
+    /// Contents of the resource
+    pub contents: Vec<ResourceContents>,
+}
+
+/// Text contents of a resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    /// Resource URI
+    pub uri: String,
+    /// MIME type of the text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// The contents
+    pub text: String,
+}
+
+/// Content item returned by a tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Content {
+    /// Plain text
+    Text {
+        /// The text
+        text: String,
+    },
+    /// Base64-encoded image
+    Image {
+        /// Base64 image data
+        data: String,
+        /// MIME type of the image
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+}
+
+impl Content {
+    /// Create a text content item
+    pub fn text(text: impl Into<String>) -> Self {
+        Content::Text { text: text.into() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_without_id_is_notification() {
+        let request: JsonRpcRequest =
+            serde_json::from_value(json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+                .unwrap();
+        assert!(request.is_notification());
+
+        let request: JsonRpcRequest =
+            serde_json::from_value(json!({"jsonrpc": "2.0", "id": "abc", "method": "ping"}))
+                .unwrap();
+        assert_eq!(request.id, Some(RequestId::String("abc".to_string())));
+    }
+
+    #[test]
+    fn test_call_tool_result_serialization() {
+        let result = CallToolResult::error("boom");
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["isError"], json!(true));
+        assert_eq!(value["content"][0]["type"], json!("text"));
+        assert_eq!(value["content"][0]["text"], json!("boom"));
+    }
+}
@@ -0,0 +1,466 @@
+//! MCP resources
+//!
+//! Exposes server state as read-only resources:
+//! - `synmem://sessions/{name}`: metadata of an encrypted session profile
+//!   (never its cookies)
+//! - `synmem://macros/{id}`: a recorded macro
+//! - `synmem://pages/{id}`: a saved page snapshot
+//!
+//! Macros and pages live in the server [`Storage`](crate::storage::Storage)
+//! under `macros/{id}` and `pages/{id}`. Saving or deleting one through this
+//! module notifies subscribed connections.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use synmem_core::domain::entities::{ScrapedPage, SessionProfile};
+use synmem_core::ports::inbound::Macro;
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::SessionError;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::protocol::{
+    JsonRpcError, ReadResourceResult, Resource, ResourceContents, ResourceTemplate,
+};
+use crate::storage::StorageError;
+use crate::tools::ToolContext;
+
+/// URI scheme of every resource
+pub const SCHEME: &str = "synmem";
+
+const JSON_MIME_TYPE: &str = "application/json";
+
+/// A parsed resource URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    /// `synmem://sessions/{name}`
+    Session(String),
+    /// `synmem://macros/{id}`
+    Macro(String),
+    /// `synmem://pages/{id}`
+    Page(String),
+}
+
+impl ResourceUri {
+    /// Parse a `synmem://` URI
+    pub fn parse(uri: &str) -> Option<Self> {
+        let path = uri.strip_prefix(SCHEME)?.strip_prefix("://")?;
+        let (kind, name) = path.split_once('/')?;
+        if name.is_empty() || name.contains('/') {
+            return None;
+        }
+
+        match kind {
+            "sessions" => Some(Self::Session(name.to_string())),
+            "macros" => Some(Self::Macro(name.to_string())),
+            "pages" => Some(Self::Page(name.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Storage key of a stored resource (`None` for sessions)
+    fn storage_key(&self) -> Option<String> {
+        match self {
+            Self::Session(_) => None,
+            Self::Macro(id) => Some(format!("macros/{}", id)),
+            Self::Page(id) => Some(format!("pages/{}", id)),
+        }
+    }
+}
+
+impl fmt::Display for ResourceUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Session(name) => write!(f, "{}://sessions/{}", SCHEME, name),
+            Self::Macro(id) => write!(f, "{}://macros/{}", SCHEME, id),
+            Self::Page(id) => write!(f, "{}://pages/{}", SCHEME, id),
+        }
+    }
+}
+
+/// Public view of a session profile
+///
+/// Deliberately omits the encrypted cookies, nonce and salt.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMetadata {
+    /// Profile name
+    pub profile: String,
+    /// Profile identifier
+    pub id: Uuid,
+    /// When the session was created
+    pub created_at: DateTime<Utc>,
+    /// When the session expires
+    pub expires_at: DateTime<Utc>,
+    /// Last time the session was refreshed
+    pub last_refreshed: Option<DateTime<Utc>>,
+    /// Whether the session has expired
+    pub expired: bool,
+    /// Whether the session should be refreshed soon
+    pub needs_refresh: bool,
+}
+
+impl From<&SessionProfile> for SessionMetadata {
+    fn from(profile: &SessionProfile) -> Self {
+        Self {
+            profile: profile.profile.clone(),
+            id: profile.id,
+            created_at: profile.created_at,
+            expires_at: profile.expires_at,
+            last_refreshed: profile.last_refreshed,
+            expired: profile.is_expired(),
+            needs_refresh: profile.needs_refresh(),
+        }
+    }
+}
+
+/// Templates for `resources/templates/list`
+pub fn templates() -> Vec<ResourceTemplate> {
+    vec![
+        ResourceTemplate {
+            uri_template: format!("{}://sessions/{{name}}", SCHEME),
+            name: "Session".to_string(),
+            description: Some("Metadata of a stored browser session".to_string()),
+            mime_type: Some(JSON_MIME_TYPE.to_string()),
+        },
+        ResourceTemplate {
+            uri_template: format!("{}://macros/{{id}}", SCHEME),
+            name: "Macro".to_string(),
+            description: Some("A recorded automation macro".to_string()),
+            mime_type: Some(JSON_MIME_TYPE.to_string()),
+        },
+        ResourceTemplate {
+            uri_template: format!("{}://pages/{{id}}", SCHEME),
+            name: "Page".to_string(),
+            description: Some("A saved page snapshot".to_string()),
+            mime_type: Some(JSON_MIME_TYPE.to_string()),
+        },
+    ]
+}
+
+/// List every available resource
+pub async fn list<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+) -> Result<Vec<Resource>, JsonRpcError> {
+    let mut resources = Vec::new();
+
+    let mut profiles = context
+        .session_manager
+        .lock()
+        .await
+        .list_profiles()
+        .map_err(|e| JsonRpcError::internal_error(e.to_string()))?;
+    profiles.sort();
+    for name in profiles {
+        resources.push(Resource {
+            uri: ResourceUri::Session(name.clone()).to_string(),
+            name: format!("Session {}", name),
+            description: None,
+            mime_type: Some(JSON_MIME_TYPE.to_string()),
+        });
+    }
+
+    for key in list_keys(context, "macros/").await? {
+        if let Some(stored) = retrieve::<D, Macro>(context, &key).await? {
+            resources.push(Resource {
+                uri: ResourceUri::Macro(stored.id.to_string()).to_string(),
+                name: stored.name,
+                description: stored.description,
+                mime_type: Some(JSON_MIME_TYPE.to_string()),
+            });
+        }
+    }
+
+    for key in list_keys(context, "pages/").await? {
+        if let Some(page) = retrieve::<D, ScrapedPage>(context, &key).await? {
+            let id = key.trim_start_matches("pages/").to_string();
+            resources.push(Resource {
+                uri: ResourceUri::Page(id).to_string(),
+                name: page
+                    .title
+                    .filter(|t| !t.is_empty())
+                    .unwrap_or_else(|| page.url.clone()),
+                description: Some(page.url),
+                mime_type: Some(JSON_MIME_TYPE.to_string()),
+            });
+        }
+    }
+
+    Ok(resources)
+}
+
+/// Read the resource at `uri`
+pub async fn read<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    uri: &str,
+) -> Result<ReadResourceResult, JsonRpcError> {
+    let parsed = ResourceUri::parse(uri).ok_or_else(|| JsonRpcError::resource_not_found(uri))?;
+
+    let text = match &parsed {
+        ResourceUri::Session(name) => {
+            let profile = match context.session_manager.lock().await.get_profile_info(name) {
+                Ok(profile) => profile,
+                Err(SessionError::NotFound(_)) => {
+                    return Err(JsonRpcError::resource_not_found(uri))
+                }
+                Err(SessionError::IoError(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(JsonRpcError::resource_not_found(uri))
+                }
+                Err(e) => return Err(JsonRpcError::internal_error(e.to_string())),
+            };
+            to_json(&SessionMetadata::from(&profile))?
+        }
+        ResourceUri::Macro(_) | ResourceUri::Page(_) => {
+            let key = parsed.storage_key().unwrap_or_default();
+            context
+                .storage
+                .retrieve(&key)
+                .await
+                .map_err(storage_error)?
+                .ok_or_else(|| JsonRpcError::resource_not_found(uri))?
+        }
+    };
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents {
+            uri: parsed.to_string(),
+            mime_type: Some(JSON_MIME_TYPE.to_string()),
+            text,
+        }],
+    })
+}
+
+/// Whether `uri` names a resource kind this server exposes
+pub fn is_known(uri: &str) -> bool {
+    ResourceUri::parse(uri).is_some()
+}
+
+/// Save a page snapshot, returning its URI
+pub async fn save_page<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    page: &ScrapedPage,
+) -> Result<ResourceUri, StorageError> {
+    let uri = ResourceUri::Page(Uuid::new_v4().to_string());
+    store(context, &uri, page).await?;
+    Ok(uri)
+}
+
+/// Save (or replace) a macro, returning its URI
+pub async fn save_macro<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    recorded: &Macro,
+) -> Result<ResourceUri, StorageError> {
+    let uri = ResourceUri::Macro(recorded.id.to_string());
+    store(context, &uri, recorded).await?;
+    Ok(uri)
+}
+
+/// Delete a stored macro or page
+pub async fn delete<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    uri: &ResourceUri,
+) -> Result<(), StorageError> {
+    let Some(key) = uri.storage_key() else {
+        return Ok(());
+    };
+    context.storage.delete(&key).await?;
+    context.connections.resource_updated(&uri.to_string());
+    context.connections.resource_list_changed();
+    Ok(())
+}
+
+async fn store<D: BrowserDriverPort, T: Serialize>(
+    context: &ToolContext<D>,
+    uri: &ResourceUri,
+    value: &T,
+) -> Result<(), StorageError> {
+    let key = uri.storage_key().unwrap_or_default();
+    let existed = context.storage.retrieve(&key).await?.is_some();
+    let json = serde_json::to_string_pretty(value).map_err(|e| StorageError::Io {
+        key: key.clone(),
+        source: e.into(),
+    })?;
+    context.storage.store(&key, &json).await?;
+
+    debug!(uri = %uri, "Resource saved");
+    context.connections.resource_updated(&uri.to_string());
+    if !existed {
+        context.connections.resource_list_changed();
+    }
+    Ok(())
+}
+
+async fn list_keys<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    prefix: &str,
+) -> Result<Vec<String>, JsonRpcError> {
+    context
+        .storage
+        .list_keys(Some(prefix))
+        .await
+        .map_err(storage_error)
+}
+
+async fn retrieve<D: BrowserDriverPort, T: serde::de::DeserializeOwned>(
+    context: &ToolContext<D>,
+    key: &str,
+) -> Result<Option<T>, JsonRpcError> {
+    let Some(json) = context.storage.retrieve(key).await.map_err(storage_error)? else {
+        return Ok(None);
+    };
+    match serde_json::from_str(&json) {
+        Ok(value) => Ok(Some(value)),
+        Err(e) => {
+            debug!(key = %key, error = %e, "Skipping unreadable resource");
+            Ok(None)
+        }
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, JsonRpcError> {
+    serde_json::to_string_pretty(value).map_err(|e| JsonRpcError::internal_error(e.to_string()))
+}
+
+fn storage_error(error: StorageError) -> JsonRpcError {
+    JsonRpcError::internal_error(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use synmem_core::{Cookie, SessionManager, SessionManagerConfig};
+
+    use super::*;
+    use crate::connection::Connection;
+    use crate::testing::{test_context, MockDriver};
+
+    fn sample_page() -> ScrapedPage {
+        ScrapedPage::new("https://example.com")
+            .with_title("Example Domain")
+            .with_html("<h1>Example</h1>")
+    }
+
+    #[test]
+    fn test_parse_uris() {
+        assert_eq!(
+            ResourceUri::parse("synmem://sessions/twitter-main"),
+            Some(ResourceUri::Session("twitter-main".to_string()))
+        );
+        assert_eq!(
+            ResourceUri::parse("synmem://pages/abc")
+                .unwrap()
+                .to_string(),
+            "synmem://pages/abc"
+        );
+        for uri in [
+            "synmem://pages/",
+            "synmem://pages/a/b",
+            "synmem://other/a",
+            "https://pages/a",
+            "synmem:/pages/a",
+        ] {
+            assert!(ResourceUri::parse(uri).is_none(), "{} was accepted", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saved_page_is_listed_and_readable() {
+        let context = test_context();
+        let uri = save_page(&context, &sample_page()).await.unwrap();
+
+        let resources = list(&context).await.unwrap();
+        let listed = resources.iter().find(|r| r.uri == uri.to_string()).unwrap();
+        assert_eq!(listed.name, "Example Domain");
+
+        let result = read(&context, &uri.to_string()).await.unwrap();
+        let page: ScrapedPage = serde_json::from_str(&result.contents[0].text).unwrap();
+        assert_eq!(page.url, "https://example.com");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_resources_are_not_found() {
+        let context = test_context();
+        for uri in [
+            "synmem://pages/missing",
+            "synmem://nope/x",
+            "synmem://sessions/ghost",
+        ] {
+            let error = read(&context, uri).await.unwrap_err();
+            assert_eq!(error.code, JsonRpcError::RESOURCE_NOT_FOUND);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_metadata_never_contains_cookies() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut manager = SessionManager::new(SessionManagerConfig {
+            storage_path: dir.path().to_path_buf(),
+            ..Default::default()
+        });
+        let cookie = Cookie {
+            name: "auth_token".to_string(),
+            value: "super-secret".to_string(),
+            domain: ".x.com".to_string(),
+            path: "/".to_string(),
+            expires: None,
+            http_only: true,
+            secure: true,
+            same_site: None,
+        };
+        let profile = manager
+            .create_session("twitter-main", vec![cookie], "password")
+            .unwrap();
+        manager.save_session(&profile).unwrap();
+
+        let context = ToolContext::new(std::sync::Arc::new(MockDriver::new()), manager);
+        let resources = list(&context).await.unwrap();
+        assert!(resources
+            .iter()
+            .any(|r| r.uri == "synmem://sessions/twitter-main"));
+
+        let text = read(&context, "synmem://sessions/twitter-main")
+            .await
+            .unwrap()
+            .contents
+            .remove(0)
+            .text;
+        let metadata: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(metadata["profile"], "twitter-main");
+        assert_eq!(metadata["expired"], false);
+        for secret in [&profile.encrypted_cookies, &profile.nonce, &profile.salt] {
+            assert!(!text.contains(secret.as_str()));
+        }
+        assert!(!text.contains("super-secret"));
+        assert!(metadata.get("encrypted_cookies").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_changes_notify_subscribers() {
+        let context = test_context();
+        let connection: std::sync::Arc<Connection> = context.connections.open("client");
+        let mut notifications = connection.subscribe();
+
+        let recorded = Macro {
+            id: Uuid::new_v4(),
+            name: "Login".to_string(),
+            description: None,
+            actions: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let uri = save_macro(&context, &recorded).await.unwrap();
+        let first = notifications.try_recv().unwrap();
+        assert_eq!(first.method, "notifications/resources/list_changed");
+
+        connection.subscribe_resource(&uri.to_string());
+        save_macro(&context, &recorded).await.unwrap();
+        let updated = notifications.try_recv().unwrap();
+        assert_eq!(updated.method, "notifications/resources/updated");
+        assert_eq!(updated.params.unwrap()["uri"], uri.to_string());
+        assert!(notifications.try_recv().is_err());
+
+        delete(&context, &uri).await.unwrap();
+        assert!(read(&context, &uri.to_string()).await.is_err());
+    }
+}
@@ -0,0 +1,371 @@
+//! JSON Schema support for tool inputs
+//!
+//! Tool input types implement [`InputSchema`] to describe themselves to MCP
+//! clients. The same schema is used by [`validate`] to check incoming
+//! arguments before they are dispatched to a tool.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// A type that can describe itself as a JSON Schema
+pub trait InputSchema {
+    /// JSON Schema (draft 2020-12 subset) for this type
+    fn input_schema() -> Value;
+}
+
+/// Builder for JSON Schema documents
+#[derive(Debug, Clone)]
+pub struct Schema(Map<String, Value>);
+
+impl Schema {
+    fn typed(type_name: &str) -> Self {
+        let mut map = Map::new();
+        map.insert("type".to_string(), json!(type_name));
+        Self(map)
+    }
+
+    /// A string schema
+    pub fn string() -> Self {
+        Self::typed("string")
+    }
+
+    /// An integer schema
+    pub fn integer() -> Self {
+        Self::typed("integer")
+    }
+
+    /// A boolean schema
+    pub fn boolean() -> Self {
+        Self::typed("boolean")
+    }
+
+    /// An array schema with the given item schema
+    pub fn array(items: Schema) -> Self {
+        Self::typed("array").with("items", items.into_value())
+    }
+
+    /// An object schema that rejects unknown properties
+    pub fn object() -> Self {
+        Self::typed("object")
+            .with("properties", json!({}))
+            .with("additionalProperties", json!(false))
+    }
+
+    /// A string schema restricted to the serde representation of `variants`
+    pub fn enumeration<T: Serialize>(variants: &[T]) -> Self {
+        let values: Vec<Value> = variants
+            .iter()
+            .filter_map(|v| serde_json::to_value(v).ok())
+            .collect();
+        Self::string().with("enum", Value::Array(values))
+    }
+
+    /// A schema matched by exactly one of `variants`
+    pub fn one_of(variants: Vec<Schema>) -> Self {
+        let variants: Vec<Value> = variants.into_iter().map(Schema::into_value).collect();
+        Self(Map::new()).with("oneOf", Value::Array(variants))
+    }
+
+    /// Allow `null` in addition to the current type (for `Option` fields)
+    pub fn nullable(mut self) -> Self {
+        if let Some(Value::String(t)) = self.0.get("type").cloned() {
+            self.0.insert("type".to_string(), json!([t, "null"]));
+        }
+        self
+    }
+
+    /// Set the description
+    pub fn description(self, description: &str) -> Self {
+        self.with("description", json!(description))
+    }
+
+    /// Set the default value
+    pub fn default_value(self, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+        self.with("default", value)
+    }
+
+    /// Minimum string length in characters
+    pub fn min_length(self, min: usize) -> Self {
+        self.with("minLength", json!(min))
+    }
+
+    /// Maximum string length in characters
+    pub fn max_length(self, max: usize) -> Self {
+        self.with("maxLength", json!(max))
+    }
+
+    /// Inclusive numeric minimum
+    pub fn minimum(self, min: i64) -> Self {
+        self.with("minimum", json!(min))
+    }
+
+    /// Inclusive numeric maximum
+    pub fn maximum(self, max: i64) -> Self {
+        self.with("maximum", json!(max))
+    }
+
+    /// Maximum number of array items
+    pub fn max_items(self, max: usize) -> Self {
+        self.with("maxItems", json!(max))
+    }
+
+    /// Add an optional object property
+    pub fn property(mut self, name: &str, schema: Schema) -> Self {
+        if let Some(Value::Object(properties)) = self.0.get_mut("properties") {
+            properties.insert(name.to_string(), schema.into_value());
+        }
+        self
+    }
+
+    /// Add a required object property
+    pub fn required_property(self, name: &str, schema: Schema) -> Self {
+        let mut this = self.property(name, schema);
+        match this.0.get_mut("required") {
+            Some(Value::Array(required)) => required.push(json!(name)),
+            _ => {
+                this.0.insert("required".to_string(), json!([name]));
+            }
+        }
+        this
+    }
+
+    /// Set an arbitrary keyword
+    pub fn with(mut self, keyword: &str, value: Value) -> Self {
+        self.0.insert(keyword.to_string(), value);
+        self
+    }
+
+    /// Finish building
+    pub fn into_value(self) -> Value {
+        Value::Object(self.0)
+    }
+}
+
+/// A single validation failure
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value
+    pub path: String,
+    /// What was wrong with it
+    pub message: String,
+}
+
+/// Validate `instance` against `schema`
+///
+/// Supports the keywords emitted by [`Schema`]: `type`, `enum`, `oneOf`,
+/// `properties`, `required`, `additionalProperties`, `items`, `minLength`,
+/// `maxLength`, `minimum`, `maximum` and `maxItems`. Unknown keywords are
+/// ignored.
+pub fn validate(schema: &Value, instance: &Value) -> Result<(), Vec<SchemaViolation>> {
+    let mut violations = Vec::new();
+    validate_at(schema, instance, "", &mut violations);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn validate_at(schema: &Value, instance: &Value, path: &str, out: &mut Vec<SchemaViolation>) {
+    let mut fail = |message: String| {
+        out.push(SchemaViolation {
+            path: if path.is_empty() { "/".to_string() } else { path.to_string() },
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|t| matches_type(t, instance)) {
+            fail(format!("expected {}, got {}", types.join(" or "), type_name(instance)));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(instance) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            fail(format!("must be one of {}", allowed.join(", ")));
+        }
+    }
+
+    if let Some(Value::Array(variants)) = schema.get("oneOf") {
+        let matched = variants
+            .iter()
+            .filter(|variant| validate(variant, instance).is_ok())
+            .count();
+        if matched != 1 {
+            fail(format!(
+                "must match exactly one of {} schemas (matched {})",
+                variants.len(),
+                matched
+            ));
+        }
+    }
+
+    match instance {
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    fail(format!("must be at least {} characters (got {})", min, len));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    fail(format!("must be at most {} characters (got {})", max, len));
+                }
+            }
+        }
+        Value::Number(n) => {
+            if let Some(n) = n.as_f64() {
+                if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                    if n < min {
+                        fail(format!("must be >= {}", min));
+                    }
+                }
+                if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                    if n > max {
+                        fail(format!("must be <= {}", max));
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if items.len() as u64 > max {
+                    fail(format!("must have at most {} items (got {})", max, items.len()));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(item_schema, item, &format!("{}/{}", path, i), out);
+                }
+            }
+        }
+        Value::Object(fields) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !fields.contains_key(name) {
+                        fail(format!("missing required property '{}'", name));
+                    }
+                }
+            }
+
+            let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+            for (name, value) in fields {
+                let child = format!("{}/{}", path, name);
+                match properties.and_then(|p| p.get(name)) {
+                    Some(property_schema) => validate_at(property_schema, value, &child, out),
+                    None if closed => out.push(SchemaViolation {
+                        path: child,
+                        message: format!("unknown property '{}'", name),
+                    }),
+                    None => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn matches_type(expected: &str, instance: &Value) -> bool {
+    match expected {
+        "string" => instance.is_string(),
+        "integer" => instance.is_i64() || instance.is_u64(),
+        "number" => instance.is_number(),
+        "boolean" => instance.is_boolean(),
+        "array" => instance.is_array(),
+        "object" => instance.is_object(),
+        "null" => instance.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_schema() -> Value {
+        Schema::object()
+            .required_property("name", Schema::string().min_length(1).max_length(5))
+            .property("count", Schema::integer().minimum(1).maximum(10))
+            .property("tags", Schema::array(Schema::string()).max_items(2))
+            .property("note", Schema::string().nullable())
+            .into_value()
+    }
+
+    #[test]
+    fn test_valid_instance() {
+        let instance = json!({"name": "abc", "count": 3, "tags": ["x"], "note": null});
+        assert!(validate(&sample_schema(), &instance).is_ok());
+    }
+
+    #[test]
+    fn test_collects_violations_with_paths() {
+        let instance = json!({"count": 11, "tags": ["x", 1], "extra": true});
+        let violations = validate(&sample_schema(), &instance).unwrap_err();
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+
+        assert!(paths.contains(&"/"));
+        assert!(paths.contains(&"/count"));
+        assert!(paths.contains(&"/tags/1"));
+        assert!(paths.contains(&"/extra"));
+    }
+
+    #[test]
+    fn test_max_length_counts_characters() {
+        let schema = Schema::string().max_length(2).into_value();
+        assert!(validate(&schema, &json!("éé")).is_ok());
+        assert!(validate(&schema, &json!("ééé")).is_err());
+    }
+
+    #[test]
+    fn test_enumeration_uses_serde_names() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Kind {
+            Alpha,
+            Beta,
+        }
+
+        let schema = Schema::enumeration(&[Kind::Alpha, Kind::Beta]).into_value();
+        assert_eq!(schema["enum"], json!(["alpha", "beta"]));
+        assert!(validate(&schema, &json!("beta")).is_ok());
+        assert!(validate(&schema, &json!("Beta")).is_err());
+    }
+
+    #[test]
+    fn test_one_of_requires_a_single_match() {
+        let schema = Schema::one_of(vec![
+            Schema::string().max_length(3),
+            Schema::object().required_property("id", Schema::string()),
+        ])
+        .into_value();
+
+        assert!(validate(&schema, &json!("abc")).is_ok());
+        assert!(validate(&schema, &json!({"id": "1"})).is_ok());
+        assert!(validate(&schema, &json!("abcd")).is_err());
+        assert!(validate(&schema, &json!({"other": "1"})).is_err());
+        assert!(validate(&schema, &json!(1)).is_err());
+    }
+}
@@ -0,0 +1,680 @@
+//! MCP server
+//!
+//! Transport-independent request handling: decodes JSON-RPC messages,
+//! answers the MCP lifecycle methods, routes `tools/call` through the
+//! [`ToolRegistry`] and serves `resources/*` from [`crate::resources`].
+
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tracing::debug;
+
+use crate::connection::Connection;
+use crate::protocol::{
+    CallToolParams, CancelledParams, Implementation, InitializeParams, InitializeResult,
+    JsonRpcError, JsonRpcRequest, JsonRpcResponse, ListResourceTemplatesResult,
+    ListResourcesResult, ListToolsResult, RequestId, ResourceParams, ResourcesCapability,
+    ServerCapabilities, ToolsCapability, JSONRPC_VERSION, PROTOCOL_VERSION,
+};
+use crate::resources;
+use crate::tools::{Progress, RequestContext, ToolContext, ToolRegistry};
+
+/// Server name reported during initialization
+pub const SERVER_NAME: &str = "synmem-mcp";
+
+/// MCP server exposing the registered tools
+pub struct McpServer<D: BrowserDriverPort> {
+    registry: ToolRegistry<D>,
+    context: ToolContext<D>,
+}
+
+impl<D: BrowserDriverPort> McpServer<D> {
+    /// Create a new server
+    pub fn new(registry: ToolRegistry<D>, context: ToolContext<D>) -> Self {
+        Self { registry, context }
+    }
+
+    /// The registered tools
+    pub fn registry(&self) -> &ToolRegistry<D> {
+        &self.registry
+    }
+
+    /// The context shared by all tools
+    pub fn context(&self) -> &ToolContext<D> {
+        &self.context
+    }
+
+    /// Register a client connection
+    ///
+    /// Resource notifications are delivered to the returned connection until
+    /// it is dropped or [`McpServer::disconnect`] is called.
+    pub fn connect(&self, id: impl Into<String>) -> Arc<Connection> {
+        self.context.connections.open(id)
+    }
+
+    /// Forget a client connection
+    pub fn disconnect(&self, id: &str) {
+        self.context.connections.close(id);
+    }
+
+    /// Handle a raw JSON message
+    ///
+    /// Returns the serialized response, or `None` for notifications.
+    pub async fn handle_message(&self, message: &str, connection: &Connection) -> Option<String> {
+        let response = match serde_json::from_str::<Value>(message) {
+            Err(e) => Some(JsonRpcResponse::failure(
+                None,
+                JsonRpcError::parse_error(e.to_string()),
+            )),
+            Ok(value) => match serde_json::from_value::<JsonRpcRequest>(value) {
+                Ok(request) => self.handle_request(request, connection).await,
+                Err(e) => Some(JsonRpcResponse::failure(
+                    None,
+                    JsonRpcError::invalid_request(e.to_string()),
+                )),
+            },
+        };
+
+        response.map(|r| {
+            serde_json::to_string(&r).unwrap_or_else(|e| {
+                serde_json::to_string(&JsonRpcResponse::failure(
+                    r.id.clone(),
+                    JsonRpcError::internal_error(e.to_string()),
+                ))
+                .unwrap_or_default()
+            })
+        })
+    }
+
+    /// Handle a decoded JSON-RPC request
+    ///
+    /// Returns `None` for notifications and for requests cancelled by the
+    /// client, which must not be answered.
+    pub async fn handle_request(
+        &self,
+        request: JsonRpcRequest,
+        connection: &Connection,
+    ) -> Option<JsonRpcResponse> {
+        if request.jsonrpc != JSONRPC_VERSION {
+            return Some(JsonRpcResponse::failure(
+                request.id,
+                JsonRpcError::invalid_request("jsonrpc must be \"2.0\""),
+            ));
+        }
+
+        let Some(id) = request.id.clone() else {
+            debug!(method = %request.method, "Received notification");
+            if request.method == "notifications/cancelled" {
+                self.cancel(request.params, connection);
+            }
+            return None;
+        };
+
+        debug!(method = %request.method, "Handling request");
+        let result = match request.method.as_str() {
+            "initialize" => self.initialize(request.params, connection),
+            "ping" => Ok(json!({})),
+            "tools/list" => to_value(self.list_tools()),
+            "tools/call" => match parse_params::<CallToolParams>(request.params) {
+                Ok(params) => self.call_tool(&id, params, connection).await?,
+                Err(e) => Err(e),
+            },
+            "resources/list" => match resources::list(&self.context).await {
+                Ok(resources) => to_value(ListResourcesResult { resources }),
+                Err(e) => Err(e),
+            },
+            "resources/templates/list" => to_value(ListResourceTemplatesResult {
+                resource_templates: resources::templates(),
+            }),
+            "resources/read" => match parse_params::<ResourceParams>(request.params) {
+                Ok(params) => resources::read(&self.context, &params.uri)
+                    .await
+                    .and_then(to_value),
+                Err(e) => Err(e),
+            },
+            "resources/subscribe" => parse_params::<ResourceParams>(request.params)
+                .and_then(|params| self.subscribe(params, connection)),
+            "resources/unsubscribe" => {
+                parse_params::<ResourceParams>(request.params).map(|params| {
+                    connection.unsubscribe_resource(&params.uri);
+                    json!({})
+                })
+            }
+            method => Err(JsonRpcError::method_not_found(method)),
+        };
+
+        Some(match result {
+            Ok(value) => JsonRpcResponse::success(request.id, value),
+            Err(error) => JsonRpcResponse::failure(request.id, error),
+        })
+    }
+
+    /// Answer the `initialize` handshake
+    fn initialize(
+        &self,
+        params: Option<Value>,
+        connection: &Connection,
+    ) -> Result<Value, JsonRpcError> {
+        let params: InitializeParams = parse_params(params)?;
+        if let Some(client) = params.client_info {
+            debug!(
+                connection = %connection.id(),
+                client = %client.name,
+                version = %client.version,
+                "Client connected"
+            );
+            connection.set_client_info(client);
+        }
+
+        to_value(InitializeResult {
+            protocol_version: PROTOCOL_VERSION.to_string(),
+            capabilities: ServerCapabilities {
+                tools: Some(ToolsCapability {
+                    list_changed: false,
+                }),
+                resources: Some(ResourcesCapability {
+                    subscribe: true,
+                    list_changed: true,
+                }),
+            },
+            server_info: Implementation {
+                name: SERVER_NAME.to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            instructions: None,
+        })
+    }
+
+    /// Run a tool, tracking the request so the client can cancel it
+    ///
+    /// Returns `None` when the request was cancelled.
+    async fn call_tool(
+        &self,
+        id: &RequestId,
+        params: CallToolParams,
+        connection: &Connection,
+    ) -> Option<Result<Value, JsonRpcError>> {
+        let in_flight = connection.begin_request(id);
+        let progress = match params.meta.and_then(|meta| meta.progress_token) {
+            Some(token) => Progress::new(token, connection.notifier()),
+            None => Progress::none(),
+        };
+        let request = RequestContext::new(progress, in_flight.token());
+
+        let result = self
+            .registry
+            .call(
+                &params.name,
+                params.arguments.unwrap_or_else(|| json!({})),
+                &self.context,
+                &request,
+            )
+            .await;
+
+        if request.is_cancelled() {
+            debug!(connection = %connection.id(), tool = %params.name, "Request cancelled");
+            return None;
+        }
+        Some(result.and_then(to_value))
+    }
+
+    /// Handle `notifications/cancelled`
+    fn cancel(&self, params: Option<Value>, connection: &Connection) {
+        match parse_params::<CancelledParams>(params) {
+            Ok(params) => {
+                let cancelled = connection.cancel_request(&params.request_id);
+                debug!(
+                    connection = %connection.id(),
+                    request = ?params.request_id,
+                    reason = ?params.reason,
+                    cancelled,
+                    "Cancellation requested"
+                );
+            }
+            Err(e) => debug!(error = %e.message, "Ignoring malformed cancellation"),
+        }
+    }
+
+    /// Subscribe `connection` to updates of a resource
+    fn subscribe(
+        &self,
+        params: ResourceParams,
+        connection: &Connection,
+    ) -> Result<Value, JsonRpcError> {
+        if !resources::is_known(&params.uri) {
+            return Err(JsonRpcError::resource_not_found(&params.uri));
+        }
+        debug!(connection = %connection.id(), uri = %params.uri, "Resource subscribed");
+        connection.subscribe_resource(&params.uri);
+        Ok(json!({}))
+    }
+
+    /// List the available tools
+    pub fn list_tools(&self) -> ListToolsResult {
+        ListToolsResult {
+            tools: self.registry.definitions(),
+        }
+    }
+}
+
+/// Decode request parameters, treating missing params as an empty object
+fn parse_params<T: DeserializeOwned>(params: Option<Value>) -> Result<T, JsonRpcError> {
+    serde_json::from_value(params.unwrap_or_else(|| json!({})))
+        .map_err(|e| JsonRpcError::invalid_params(e.to_string()))
+}
+
+/// Serialize a result value
+fn to_value<T: Serialize>(value: T) -> Result<Value, JsonRpcError> {
+    serde_json::to_value(value).map_err(|e| JsonRpcError::internal_error(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ToolsConfig;
+    use crate::protocol::CallToolResult;
+    use crate::testing::{test_context, MockDriver};
+    use crate::tools::Tool;
+    use crate::tools::default_registry;
+    use crate::twitter::TwitterSession;
+
+    fn test_server() -> McpServer<MockDriver> {
+        test_server_with(&ToolsConfig::default())
+    }
+
+    fn test_server_with(config: &ToolsConfig) -> McpServer<MockDriver> {
+        let session = TwitterSession {
+            cookies: String::new(),
+            csrf_token: String::new(),
+            bearer_token: String::new(),
+            user_id: None,
+        };
+        McpServer::new(default_registry(config, session), test_context())
+    }
+
+    async fn call(server: &McpServer<MockDriver>, message: Value) -> Value {
+        let connection = Connection::new("test");
+        let response = server
+            .handle_message(&message.to_string(), &connection)
+            .await
+            .unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize() {
+        let server = test_server();
+        let response = call(
+            &server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {"name": "test", "version": "1.0"}
+                }
+            }),
+        )
+        .await;
+
+        assert_eq!(response["id"], json!(1));
+        assert_eq!(response["result"]["protocolVersion"], json!(PROTOCOL_VERSION));
+        assert_eq!(response["result"]["serverInfo"]["name"], json!(SERVER_NAME));
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+        assert_eq!(
+            response["result"]["capabilities"]["resources"]["subscribe"],
+            json!(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notification_has_no_response() {
+        let server = test_server();
+        let message = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        let connection = Connection::new("test");
+        assert!(server
+            .handle_message(&message.to_string(), &connection)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tools_list() {
+        let server = test_server();
+        let response = call(&server, json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"})).await;
+
+        let names: Vec<&str> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"twitter_post"));
+        assert!(names.contains(&"twitter_read_thread"));
+        assert!(names.contains(&"twitter_search"));
+        assert!(names.contains(&"twitter_get_timeline"));
+        assert!(names.contains(&"twitter_post_thread"));
+        assert!(names.contains(&"twitter_get_profile"));
+        assert!(names.contains(&"twitter_get_notifications"));
+        assert!(names.contains(&"twitter_list_conversations"));
+        assert!(names.contains(&"twitter_read_dm"));
+        assert!(names.contains(&"twitter_send_dm"));
+        for name in [
+            "twitter_like",
+            "twitter_unlike",
+            "twitter_retweet",
+            "twitter_bookmark",
+            "twitter_follow",
+            "twitter_unfollow",
+            "twitter_schedule_post",
+            "twitter_list_scheduled",
+            "twitter_reschedule_post",
+            "twitter_cancel_scheduled",
+        ] {
+            assert!(names.contains(&name));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_reports_tool_error() {
+        let server = test_server();
+        let response = call(
+            &server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "tools/call",
+                "params": {"name": "twitter_search", "arguments": {"query": "rust"}}
+            }),
+        )
+        .await;
+
+        assert_eq!(response["result"]["isError"], json!(true));
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("session"));
+    }
+
+    #[tokio::test]
+    async fn test_tools_list_includes_schemas() {
+        let server = test_server();
+        let tools = server.list_tools().tools;
+        let post = tools.iter().find(|t| t.name == "twitter_post").unwrap();
+        assert_eq!(post.input_schema["required"], json!(["text"]));
+        assert_eq!(post.input_schema["properties"]["text"]["maxLength"], json!(280));
+
+        let search = tools.iter().find(|t| t.name == "twitter_search").unwrap();
+        assert_eq!(search.input_schema["properties"]["filter"]["default"], json!("top"));
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_rejects_invalid_arguments() {
+        let server = test_server();
+        let response = call(
+            &server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 6,
+                "method": "tools/call",
+                "params": {
+                    "name": "twitter_search",
+                    "arguments": {"query": "rust", "count": 500, "filter": "oldest"}
+                }
+            }),
+        )
+        .await;
+
+        assert_eq!(response["error"]["code"], json!(JsonRpcError::INVALID_PARAMS));
+        let violations = response["error"]["data"]["violations"].as_array().unwrap();
+        let paths: Vec<&str> = violations.iter().map(|v| v["path"].as_str().unwrap()).collect();
+        assert_eq!(paths, vec!["/count", "/filter"]);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_tools_are_hidden_and_uncallable() {
+        let server = test_server_with(&ToolsConfig {
+            enabled: None,
+            disabled: vec!["twitter_post".to_string()],
+            ..Default::default()
+        });
+        assert!(!server.registry().names().contains(&"twitter_post"));
+
+        let response = call(
+            &server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "method": "tools/call",
+                "params": {"name": "twitter_post", "arguments": {"text": "hi"}}
+            }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], json!(JsonRpcError::INVALID_PARAMS));
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_unknown_tool() {
+        let server = test_server();
+        let response = call(
+            &server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 4,
+                "method": "tools/call",
+                "params": {"name": "does_not_exist"}
+            }),
+        )
+        .await;
+
+        assert_eq!(response["error"]["code"], json!(JsonRpcError::INVALID_PARAMS));
+    }
+
+    #[tokio::test]
+    async fn test_resources_list_read_and_subscribe() {
+        let server = test_server();
+        let page = synmem_core::domain::entities::ScrapedPage::new("https://example.com");
+        let uri = resources::save_page(server.context(), &page)
+            .await
+            .unwrap()
+            .to_string();
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 8, "method": "resources/list"}),
+        )
+        .await;
+        let listed = response["result"]["resources"].as_array().unwrap();
+        assert!(listed.iter().any(|r| r["uri"] == json!(uri)));
+
+        let response = call(
+            &server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 9,
+                "method": "resources/read",
+                "params": {"uri": uri}
+            }),
+        )
+        .await;
+        let contents = &response["result"]["contents"][0];
+        assert_eq!(contents["mimeType"], json!("application/json"));
+        assert!(contents["text"].as_str().unwrap().contains("https://example.com"));
+
+        let response = call(
+            &server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 10,
+                "method": "resources/read",
+                "params": {"uri": "synmem://pages/missing"}
+            }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], json!(JsonRpcError::RESOURCE_NOT_FOUND));
+
+        let connection = server.connect("subscriber");
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": 11,
+            "method": "resources/subscribe",
+            "params": {"uri": uri}
+        });
+        server
+            .handle_message(&message.to_string(), &connection)
+            .await
+            .unwrap();
+        assert!(connection.is_subscribed(&uri));
+    }
+
+    /// Reports one step of progress, starts loading a page, then never
+    /// finishes
+    struct WaitForCancelTool;
+
+    #[async_trait::async_trait]
+    impl Tool<MockDriver> for WaitForCancelTool {
+        fn name(&self) -> &str {
+            "wait_for_cancel"
+        }
+
+        fn description(&self) -> &str {
+            "Never finishes"
+        }
+
+        fn input_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        async fn call(
+            &self,
+            _arguments: Value,
+            context: &ToolContext<MockDriver>,
+            request: &RequestContext,
+        ) -> CallToolResult {
+            context.browser(request).goto("https://example.com").await.unwrap();
+            request.progress().report(1, Some(2));
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progress_and_cancellation() {
+        let mut registry = ToolRegistry::new();
+        registry.register(WaitForCancelTool);
+        let server = Arc::new(McpServer::new(registry, test_context()));
+        let connection = server.connect("client");
+        let mut notifications = connection.subscribe();
+
+        let call = {
+            let server = server.clone();
+            let connection = connection.clone();
+            tokio::spawn(async move {
+                let message = json!({
+                    "jsonrpc": "2.0",
+                    "id": 12,
+                    "method": "tools/call",
+                    "params": {
+                        "name": "wait_for_cancel",
+                        "_meta": {"progressToken": "tok"}
+                    }
+                });
+                server.handle_message(&message.to_string(), &connection).await
+            })
+        };
+
+        let progress = notifications.recv().await.unwrap();
+        assert_eq!(progress.method, "notifications/progress");
+        assert_eq!(
+            progress.params.unwrap(),
+            json!({"progressToken": "tok", "progress": 1, "total": 2})
+        );
+
+        let cancel = json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/cancelled",
+            "params": {"requestId": 12, "reason": "user aborted"}
+        });
+        assert!(server
+            .handle_message(&cancel.to_string(), &connection)
+            .await
+            .is_none());
+
+        assert!(call.await.unwrap().is_none());
+        assert!(!connection.cancel_request(&RequestId::Number(12)));
+        assert_eq!(
+            server.context().driver.actions(),
+            vec!["goto https://example.com", "js window.stop()"]
+        );
+    }
+
+    /// Opens the page named in its arguments, then clicks on it
+    struct OpenAndClickTool;
+
+    #[async_trait::async_trait]
+    impl Tool<MockDriver> for OpenAndClickTool {
+        fn name(&self) -> &str {
+            "open_and_click"
+        }
+
+        fn description(&self) -> &str {
+            "Open a page and click on it"
+        }
+
+        fn input_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        async fn call(
+            &self,
+            arguments: Value,
+            context: &ToolContext<MockDriver>,
+            request: &RequestContext,
+        ) -> CallToolResult {
+            let page = arguments["page"].as_str().unwrap_or_default();
+            let browser = context.browser(request);
+            browser.goto(page).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            browser.click(page).await.unwrap();
+            CallToolResult::text(page)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_tool_calls_do_not_interleave() {
+        let mut registry = ToolRegistry::new();
+        registry.register(OpenAndClickTool);
+        let server = McpServer::new(registry, test_context());
+        let message = |id: u64, page: &str| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tools/call",
+                "params": {"name": "open_and_click", "arguments": {"page": page}}
+            })
+        };
+
+        tokio::join!(call(&server, message(1, "a")), call(&server, message(2, "b")));
+
+        let actions = server.context().driver.actions();
+        assert_eq!(actions.len(), 4);
+        for pair in actions.chunks(2) {
+            assert_eq!(pair[0].replace("goto", "click"), pair[1]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_method_and_parse_error() {
+        let server = test_server();
+        let response = call(&server, json!({"jsonrpc": "2.0", "id": 5, "method": "nope"})).await;
+        assert_eq!(response["error"]["code"], json!(JsonRpcError::METHOD_NOT_FOUND));
+
+        let connection = Connection::new("test");
+        let response = server.handle_message("{not json", &connection).await.unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"]["code"], json!(JsonRpcError::PARSE_ERROR));
+        assert_eq!(response["id"], Value::Null);
+    }
+}
@@ -0,0 +1,261 @@
+//! Key-value storage adapters
+//!
+//! Implementations of the core [`StoragePort`] used by the server to keep
+//! pages, macros and other state. Keys are `/`-separated paths such as
+//! `pages/3f2a...`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use synmem_core::ports::outbound::StoragePort;
+use thiserror::Error;
+
+/// Storage shared by the server components
+pub type Storage = dyn StoragePort<Error = StorageError>;
+
+/// Errors that can occur while accessing storage
+#[derive(Debug, Error)]
+pub enum StorageError {
+    /// The key is empty or escapes the storage root
+    #[error("Invalid storage key: {key}")]
+    InvalidKey { key: String },
+
+    /// Reading or writing the backing file failed
+    #[error("Storage I/O error for {key}: {source}")]
+    Io {
+        key: String,
+        source: std::io::Error,
+    },
+}
+
+/// Storage keeping every value in memory
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    values: RwLock<BTreeMap<String, String>>,
+}
+
+impl MemoryStorage {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StoragePort for MemoryStorage {
+    type Error = StorageError;
+
+    async fn store(&self, key: &str, value: &str) -> Result<(), Self::Error> {
+        validate_key(key)?;
+        self.values
+            .write()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    async fn retrieve(&self, key: &str) -> Result<Option<String>, Self::Error> {
+        Ok(self.values.read().unwrap().get(key).cloned())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Self::Error> {
+        self.values.write().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn list_keys(&self, prefix: Option<&str>) -> Result<Vec<String>, Self::Error> {
+        let prefix = prefix.unwrap_or("");
+        Ok(self
+            .values
+            .read()
+            .unwrap()
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect())
+    }
+}
+
+/// Storage keeping one file per key under a root directory
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    /// Create a store rooted at `root` (created on first write)
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl StoragePort for FileStorage {
+    type Error = StorageError;
+
+    async fn store(&self, key: &str, value: &str) -> Result<(), Self::Error> {
+        let path = self.path_for(key)?;
+        let io_error = |source| StorageError::Io {
+            key: key.to_string(),
+            source,
+        };
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+        // Write to a sibling file first so readers never see a partial value
+        let temp = path.with_extension("tmp");
+        tokio::fs::write(&temp, value).await.map_err(io_error)?;
+        tokio::fs::rename(&temp, &path).await.map_err(io_error)
+    }
+
+    async fn retrieve(&self, key: &str) -> Result<Option<String>, Self::Error> {
+        match tokio::fs::read_to_string(self.path_for(key)?).await {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(source) => Err(StorageError::Io {
+                key: key.to_string(),
+                source,
+            }),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Self::Error> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(StorageError::Io {
+                key: key.to_string(),
+                source: e,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    async fn list_keys(&self, prefix: Option<&str>) -> Result<Vec<String>, Self::Error> {
+        let prefix = prefix.unwrap_or("");
+        let mut keys = Vec::new();
+        let mut pending = vec![self.root.clone()];
+
+        while let Some(dir) = pending.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(source) => {
+                    return Err(StorageError::Io {
+                        key: prefix.to_string(),
+                        source,
+                    })
+                }
+            };
+
+            while let Some(entry) = entries.next_entry().await.map_err(|source| StorageError::Io {
+                key: prefix.to_string(),
+                source,
+            })? {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "tmp") {
+                    continue;
+                } else if let Some(key) = relative_key(&self.root, &path) {
+                    if key.starts_with(prefix) {
+                        keys.push(key);
+                    }
+                }
+            }
+        }
+
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// Keys must be relative paths without `.` or `..` segments
+fn validate_key(key: &str) -> Result<(), StorageError> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && !key.contains('\\')
+        && key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey {
+            key: key.to_string(),
+        })
+    }
+}
+
+/// Key of the file at `path` relative to `root`
+fn relative_key(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let segments: Option<Vec<&str>> = relative.iter().map(|s| s.to_str()).collect();
+    Some(segments?.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_storage_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path());
+
+        storage.store("pages/a", "first").await.unwrap();
+        storage.store("pages/b", "second").await.unwrap();
+        storage.store("macros/c", "third").await.unwrap();
+
+        assert_eq!(
+            storage.retrieve("pages/a").await.unwrap().as_deref(),
+            Some("first")
+        );
+        assert_eq!(
+            storage.list_keys(Some("pages/")).await.unwrap(),
+            vec!["pages/a", "pages/b"]
+        );
+
+        storage.delete("pages/a").await.unwrap();
+        storage.delete("pages/a").await.unwrap();
+        assert!(storage.retrieve("pages/a").await.unwrap().is_none());
+        assert_eq!(storage.list_keys(None).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_keys_cannot_escape_root() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path());
+
+        for key in ["", "/etc/passwd", "../outside", "pages/../../x", "a//b"] {
+            assert!(
+                matches!(
+                    storage.store(key, "x").await,
+                    Err(StorageError::InvalidKey { .. })
+                ),
+                "{} was accepted",
+                key
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_prefix_listing() {
+        let storage = MemoryStorage::new();
+        storage.store("pages/a", "1").await.unwrap();
+        storage.store("macros/b", "2").await.unwrap();
+
+        assert_eq!(
+            storage.list_keys(Some("macros/")).await.unwrap(),
+            vec!["macros/b"]
+        );
+        assert_eq!(storage.retrieve("missing").await.unwrap(), None);
+    }
+}
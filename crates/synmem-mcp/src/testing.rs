@@ -0,0 +1,207 @@
+//! Test doubles shared by the unit tests
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use synmem_core::domain::entities::{BrowserState, SimpleCookie};
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::SessionManager;
+use thiserror::Error;
+
+use crate::tools::ToolContext;
+
+/// Error returned by [`MockDriver`]
+#[derive(Debug, Error)]
+#[error("mock driver error: {0}")]
+pub struct MockDriverError(pub String);
+
+/// In-memory browser driver serving canned HTML per URL
+#[derive(Default)]
+pub struct MockDriver {
+    pages: Mutex<HashMap<String, Vec<String>>>,
+    reads: Mutex<HashMap<String, usize>>,
+    redirects: Mutex<HashMap<String, String>>,
+    missing: Mutex<Vec<String>>,
+    current_url: Mutex<String>,
+    js_result: Mutex<String>,
+    actions: Mutex<Vec<String>>,
+}
+
+impl MockDriver {
+    /// Create a driver with no pages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `html` for `url`
+    pub fn with_page(self, url: &str, html: &str) -> Self {
+        self.with_snapshots(url, &[html])
+    }
+
+    /// Serve successive snapshots of `url`, one per `get_html` call
+    ///
+    /// The last snapshot is repeated once the others have been served,
+    /// mimicking a page that loads more content as it is scrolled.
+    pub fn with_snapshots(self, url: &str, snapshots: &[&str]) -> Self {
+        self.pages.lock().unwrap().insert(
+            url.to_string(),
+            snapshots.iter().map(|html| html.to_string()).collect(),
+        );
+        self
+    }
+
+    /// Land on `to` when `from` is opened, like a server-side redirect
+    pub fn with_redirect(self, from: &str, to: &str) -> Self {
+        self.redirects
+            .lock()
+            .unwrap()
+            .insert(from.to_string(), to.to_string());
+        self
+    }
+
+    /// Return `result` from every `evaluate_js` call
+    pub fn with_js_result(self, result: &str) -> Self {
+        *self.js_result.lock().unwrap() = result.to_string();
+        self
+    }
+
+    /// Make waiting for `selector` time out, as on a page lacking it
+    pub fn with_missing(self, selector: &str) -> Self {
+        self.missing.lock().unwrap().push(selector.to_string());
+        self
+    }
+
+    /// Actions performed so far, e.g. `"click #submit"`
+    pub fn actions(&self) -> Vec<String> {
+        self.actions.lock().unwrap().clone()
+    }
+
+    fn record(&self, action: String) {
+        self.actions.lock().unwrap().push(action);
+    }
+}
+
+#[async_trait]
+impl BrowserDriverPort for MockDriver {
+    type Error = MockDriverError;
+
+    async fn goto(&self, url: &str) -> Result<(), Self::Error> {
+        self.record(format!("goto {}", url));
+        let landed = self.redirects.lock().unwrap().get(url).cloned();
+        *self.current_url.lock().unwrap() = landed.unwrap_or_else(|| url.to_string());
+        Ok(())
+    }
+
+    async fn back(&self) -> Result<(), Self::Error> {
+        self.record("back".to_string());
+        Ok(())
+    }
+
+    async fn forward(&self) -> Result<(), Self::Error> {
+        self.record("forward".to_string());
+        Ok(())
+    }
+
+    async fn refresh(&self) -> Result<(), Self::Error> {
+        self.record("refresh".to_string());
+        Ok(())
+    }
+
+    async fn current_url(&self) -> Result<String, Self::Error> {
+        Ok(self.current_url.lock().unwrap().clone())
+    }
+
+    async fn click(&self, selector: &str) -> Result<(), Self::Error> {
+        self.record(format!("click {}", selector));
+        Ok(())
+    }
+
+    async fn type_text(&self, selector: &str, text: &str) -> Result<(), Self::Error> {
+        self.record(format!("type {} {}", selector, text));
+        Ok(())
+    }
+
+    async fn select(&self, selector: &str, value: &str) -> Result<(), Self::Error> {
+        self.record(format!("select {} {}", selector, value));
+        Ok(())
+    }
+
+    async fn upload_files(&self, selector: &str, paths: &[PathBuf]) -> Result<(), Self::Error> {
+        let paths: Vec<_> = paths.iter().map(|path| path.display().to_string()).collect();
+        self.record(format!("upload {} {}", selector, paths.join(" ")));
+        Ok(())
+    }
+
+    async fn wait_for_element(&self, selector: &str, _timeout_ms: u64) -> Result<(), Self::Error> {
+        self.record(format!("wait {}", selector));
+        if self.missing.lock().unwrap().iter().any(|missing| missing == selector) {
+            return Err(MockDriverError(format!("timed out waiting for {}", selector)));
+        }
+        Ok(())
+    }
+
+    async fn screenshot(&self) -> Result<Vec<u8>, Self::Error> {
+        Ok(vec![0x89, b'P', b'N', b'G'])
+    }
+
+    async fn get_html(&self) -> Result<String, Self::Error> {
+        let url = self.current_url.lock().unwrap().clone();
+        let pages = self.pages.lock().unwrap();
+        let Some(snapshots) = pages.get(&url) else {
+            return Ok(String::new());
+        };
+        let mut reads = self.reads.lock().unwrap();
+        let read = reads.entry(url).or_insert(0);
+        let html = snapshots[(*read).min(snapshots.len() - 1)].clone();
+        *read += 1;
+        Ok(html)
+    }
+
+    async fn evaluate_js(&self, script: &str) -> Result<String, Self::Error> {
+        self.record(format!("js {}", script));
+        Ok(self.js_result.lock().unwrap().clone())
+    }
+
+    async fn get_cookies(&self) -> Result<Vec<SimpleCookie>, Self::Error> {
+        Ok(vec![])
+    }
+
+    async fn set_cookies(&self, cookies: &[SimpleCookie]) -> Result<(), Self::Error> {
+        self.record(format!("set_cookies {}", cookies.len()));
+        Ok(())
+    }
+
+    async fn save_session(&self) -> Result<BrowserState, Self::Error> {
+        Ok(BrowserState::new("mock"))
+    }
+
+    async fn load_session(&self, _state: &BrowserState) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn clear_session(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Contents of a file under `tests/fixtures`
+pub fn fixture(path: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), path);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e))
+}
+
+/// Tool context backed by an empty [`MockDriver`]
+pub fn test_context() -> ToolContext<MockDriver> {
+    test_context_with(MockDriver::new())
+}
+
+/// Tool context backed by `driver`
+pub fn test_context_with(driver: MockDriver) -> ToolContext<MockDriver> {
+    ToolContext::new(Arc::new(driver), SessionManager::default())
+}
@@ -0,0 +1,33 @@
+//! Browser tool error types
+
+use thiserror::Error;
+
+/// Errors that can occur while driving the browser
+#[derive(Debug, Error)]
+pub enum BrowserToolError {
+    /// The browser driver reported a failure
+    #[error("Browser {action} failed: {message}")]
+    Driver { action: String, message: String },
+
+    /// No element matches the selector
+    #[error("No element matches selector: {selector}")]
+    ElementNotFound { selector: String },
+
+    /// Invalid input
+    #[error("Invalid input: {message}")]
+    InvalidInput { message: String },
+
+    /// Persisting a result failed
+    #[error("Storage error: {message}")]
+    Storage { message: String },
+}
+
+impl BrowserToolError {
+    /// Wrap a driver error for the named action
+    pub fn driver(action: &str, error: impl std::error::Error) -> Self {
+        BrowserToolError::Driver {
+            action: action.to_string(),
+            message: error.to_string(),
+        }
+    }
+}
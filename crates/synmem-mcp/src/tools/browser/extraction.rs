@@ -0,0 +1,129 @@
+//! Content tools backed by [`ExtractionService`]
+
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::ExtractionService;
+use tracing::instrument;
+
+use super::{BrowserContentInput, BrowserEvaluateInput, BrowserSavedPage, BrowserToolError};
+use crate::resources;
+use crate::tools::{RequestContext, ToolContext};
+
+/// Capture the page as a PNG
+#[instrument(skip(driver))]
+pub async fn browser_screenshot<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+) -> Result<Vec<u8>, BrowserToolError> {
+    ExtractionService::new(driver.clone())
+        .screenshot()
+        .await
+        .map_err(|e| BrowserToolError::driver("screenshot", e))
+}
+
+/// Read the rendered text of the page or of one element
+#[instrument(skip(driver))]
+pub async fn browser_get_text<D: BrowserDriverPort>(
+    input: BrowserContentInput,
+    driver: &Arc<D>,
+) -> Result<String, BrowserToolError> {
+    let expression = match &input.selector {
+        Some(selector) => element_property(selector, "innerText"),
+        None => "document.body ? document.body.innerText : null".to_string(),
+    };
+    evaluate_json(driver, &expression)
+        .await?
+        .ok_or_else(|| not_found(input.selector))
+}
+
+/// Read the HTML of the page or of one element
+#[instrument(skip(driver))]
+pub async fn browser_get_html<D: BrowserDriverPort>(
+    input: BrowserContentInput,
+    driver: &Arc<D>,
+) -> Result<String, BrowserToolError> {
+    match &input.selector {
+        Some(selector) => evaluate_json(driver, &element_property(selector, "outerHTML"))
+            .await?
+            .ok_or_else(|| not_found(input.selector)),
+        None => ExtractionService::new(driver.clone())
+            .get_html()
+            .await
+            .map_err(|e| BrowserToolError::driver("get_html", e)),
+    }
+}
+
+/// Snapshot the current page into storage as a `synmem://pages/{id}` resource
+///
+/// The page is read during `request`'s turn at the browser.
+#[instrument(skip(context, request))]
+pub async fn browser_save_page<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    request: &RequestContext,
+) -> Result<BrowserSavedPage, BrowserToolError> {
+    let mut page = ExtractionService::new(context.browser(request))
+        .extract_page()
+        .await
+        .map_err(|e| BrowserToolError::driver("extract_page", e))?;
+    page.title = page.title.filter(|title| !title.is_empty());
+
+    let uri = resources::save_page(context, &page)
+        .await
+        .map_err(|e| BrowserToolError::Storage {
+            message: e.to_string(),
+        })?;
+    Ok(BrowserSavedPage {
+        uri: uri.to_string(),
+        url: page.url,
+        title: page.title,
+    })
+}
+
+/// Evaluate a JavaScript expression in the page
+#[instrument(skip(driver, input))]
+pub async fn browser_evaluate<D: BrowserDriverPort>(
+    input: BrowserEvaluateInput,
+    driver: &Arc<D>,
+) -> Result<String, BrowserToolError> {
+    ExtractionService::new(driver.clone())
+        .evaluate_js(&input.script)
+        .await
+        .map_err(|e| BrowserToolError::driver("evaluate", e))
+}
+
+/// Expression reading `property` of the first element matching `selector`,
+/// or `null` when nothing matches
+fn element_property(selector: &str, property: &str) -> String {
+    // A JSON string literal is also a valid JavaScript string literal
+    let literal = serde_json::to_string(selector).unwrap_or_default();
+    format!(
+        "(() => {{ const el = document.querySelector({}); return el ? el.{} : null; }})()",
+        literal, property
+    )
+}
+
+/// Evaluate an expression yielding a string or `null`
+///
+/// The value is JSON-encoded in the page so that `null` can be told apart
+/// from an empty string.
+async fn evaluate_json<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    expression: &str,
+) -> Result<Option<String>, BrowserToolError> {
+    let raw = ExtractionService::new(driver.clone())
+        .evaluate_js(&format!("JSON.stringify({})", expression))
+        .await
+        .map_err(|e| BrowserToolError::driver("extraction", e))?;
+
+    serde_json::from_str(&raw).map_err(|e| BrowserToolError::Driver {
+        action: "extraction".to_string(),
+        message: format!("Unexpected script result: {}", e),
+    })
+}
+
+/// Error for a selector (or the page body) that matched nothing
+fn not_found(selector: Option<String>) -> BrowserToolError {
+    BrowserToolError::ElementNotFound {
+        selector: selector.unwrap_or_else(|| "body".to_string()),
+    }
+}
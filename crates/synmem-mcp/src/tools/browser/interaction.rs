@@ -0,0 +1,67 @@
+//! Interaction tools backed by [`AutomationService`]
+
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::AutomationService;
+use tracing::instrument;
+
+use super::navigation::page_info;
+use super::{
+    BrowserClickInput, BrowserPageInfo, BrowserSelectInput, BrowserToolError, BrowserTypeInput,
+    BrowserWaitForInput,
+};
+
+/// Click an element
+#[instrument(skip(driver), fields(selector = %input.selector))]
+pub async fn browser_click<D: BrowserDriverPort>(
+    input: BrowserClickInput,
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    AutomationService::new(driver.clone())
+        .click(&input.selector)
+        .await
+        .map_err(|e| BrowserToolError::driver("click", e))?;
+    page_info(driver).await
+}
+
+/// Type text into an element
+///
+/// The text is kept out of the tracing span since it may be a credential.
+#[instrument(skip(driver, input), fields(selector = %input.selector))]
+pub async fn browser_type<D: BrowserDriverPort>(
+    input: BrowserTypeInput,
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    AutomationService::new(driver.clone())
+        .type_text(&input.selector, &input.text)
+        .await
+        .map_err(|e| BrowserToolError::driver("type", e))?;
+    page_info(driver).await
+}
+
+/// Choose an option of a dropdown
+#[instrument(skip(driver), fields(selector = %input.selector))]
+pub async fn browser_select<D: BrowserDriverPort>(
+    input: BrowserSelectInput,
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    AutomationService::new(driver.clone())
+        .select(&input.selector, &input.value)
+        .await
+        .map_err(|e| BrowserToolError::driver("select", e))?;
+    page_info(driver).await
+}
+
+/// Wait until an element is present
+#[instrument(skip(driver), fields(selector = %input.selector))]
+pub async fn browser_wait_for<D: BrowserDriverPort>(
+    input: BrowserWaitForInput,
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    AutomationService::new(driver.clone())
+        .wait_for_element(&input.selector, input.timeout_ms)
+        .await
+        .map_err(|e| BrowserToolError::driver("wait", e))?;
+    page_info(driver).await
+}
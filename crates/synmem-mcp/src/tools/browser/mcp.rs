@@ -0,0 +1,452 @@
+//! MCP adapters for the browser tools
+//!
+//! Wraps the tool functions in [`Tool`] implementations and registers them
+//! into a [`ToolRegistry`].
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::Serialize;
+use serde_json::Value;
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tracing::warn;
+
+use super::{
+    browser_back, browser_click, browser_evaluate, browser_forward, browser_get_html,
+    browser_get_text, browser_navigate, browser_refresh, browser_save_page, browser_screenshot,
+    browser_select, browser_type, browser_wait_for, BrowserClickInput, BrowserContentInput,
+    BrowserEmptyInput, BrowserEvaluateInput, BrowserNavigateInput, BrowserSelectInput,
+    BrowserToolError, BrowserTypeInput, BrowserWaitForInput,
+};
+use crate::protocol::{CallToolResult, Content};
+use crate::schema::InputSchema;
+use crate::tools::{decode_arguments, RequestContext, Tool, ToolContext, ToolRegistry};
+
+/// Register all browser tools
+pub fn register_tools<D: BrowserDriverPort>(registry: &mut ToolRegistry<D>) {
+    registry.register(BrowserNavigateTool);
+    registry.register(BrowserBackTool);
+    registry.register(BrowserForwardTool);
+    registry.register(BrowserRefreshTool);
+    registry.register(BrowserClickTool);
+    registry.register(BrowserTypeTool);
+    registry.register(BrowserSelectTool);
+    registry.register(BrowserWaitForTool);
+    registry.register(BrowserScreenshotTool);
+    registry.register(BrowserGetTextTool);
+    registry.register(BrowserGetHtmlTool);
+    registry.register(BrowserEvaluateTool);
+    registry.register(BrowserSavePageTool);
+}
+
+/// Convert a tool result into JSON content
+fn tool_result<T: Serialize>(result: Result<T, BrowserToolError>) -> CallToolResult {
+    match result {
+        Ok(value) => CallToolResult::json(&value),
+        Err(e) => error_result(e),
+    }
+}
+
+/// Convert a tool result into text content
+fn text_result(result: Result<String, BrowserToolError>) -> CallToolResult {
+    match result {
+        Ok(text) => CallToolResult::text(text),
+        Err(e) => error_result(e),
+    }
+}
+
+fn error_result(error: BrowserToolError) -> CallToolResult {
+    warn!(error = %error, "Browser tool failed");
+    CallToolResult::error(error.to_string())
+}
+
+/// `browser_navigate` tool
+struct BrowserNavigateTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserNavigateTool {
+    fn name(&self) -> &str {
+        "browser_navigate"
+    }
+
+    fn description(&self) -> &str {
+        "Open a URL in the browser, optionally waiting for an element"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserNavigateInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: BrowserNavigateInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(browser_navigate(input, &context.browser(request)).await)
+    }
+}
+
+/// `browser_back` tool
+struct BrowserBackTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserBackTool {
+    fn name(&self) -> &str {
+        "browser_back"
+    }
+
+    fn description(&self) -> &str {
+        "Go back in the browser history"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserEmptyInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        _arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        tool_result(browser_back(&context.browser(request)).await)
+    }
+}
+
+/// `browser_forward` tool
+struct BrowserForwardTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserForwardTool {
+    fn name(&self) -> &str {
+        "browser_forward"
+    }
+
+    fn description(&self) -> &str {
+        "Go forward in the browser history"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserEmptyInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        _arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        tool_result(browser_forward(&context.browser(request)).await)
+    }
+}
+
+/// `browser_refresh` tool
+struct BrowserRefreshTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserRefreshTool {
+    fn name(&self) -> &str {
+        "browser_refresh"
+    }
+
+    fn description(&self) -> &str {
+        "Reload the current page"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserEmptyInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        _arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        tool_result(browser_refresh(&context.browser(request)).await)
+    }
+}
+
+/// `browser_click` tool
+struct BrowserClickTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserClickTool {
+    fn name(&self) -> &str {
+        "browser_click"
+    }
+
+    fn description(&self) -> &str {
+        "Click the element matching a CSS selector"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserClickInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: BrowserClickInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(browser_click(input, &context.browser(request)).await)
+    }
+}
+
+/// `browser_type` tool
+struct BrowserTypeTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserTypeTool {
+    fn name(&self) -> &str {
+        "browser_type"
+    }
+
+    fn description(&self) -> &str {
+        "Type text into the element matching a CSS selector"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserTypeInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: BrowserTypeInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(browser_type(input, &context.browser(request)).await)
+    }
+}
+
+/// `browser_select` tool
+struct BrowserSelectTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserSelectTool {
+    fn name(&self) -> &str {
+        "browser_select"
+    }
+
+    fn description(&self) -> &str {
+        "Choose an option of a dropdown by value"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserSelectInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: BrowserSelectInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(browser_select(input, &context.browser(request)).await)
+    }
+}
+
+/// `browser_wait_for` tool
+struct BrowserWaitForTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserWaitForTool {
+    fn name(&self) -> &str {
+        "browser_wait_for"
+    }
+
+    fn description(&self) -> &str {
+        "Wait until an element matching a CSS selector is present"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserWaitForInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: BrowserWaitForInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(browser_wait_for(input, &context.browser(request)).await)
+    }
+}
+
+/// `browser_screenshot` tool
+struct BrowserScreenshotTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserScreenshotTool {
+    fn name(&self) -> &str {
+        "browser_screenshot"
+    }
+
+    fn description(&self) -> &str {
+        "Take a full-page PNG screenshot"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserEmptyInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        _arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        match browser_screenshot(&context.browser(request)).await {
+            Ok(png) => CallToolResult {
+                content: vec![Content::Image {
+                    data: BASE64.encode(png),
+                    mime_type: "image/png".to_string(),
+                }],
+                is_error: false,
+            },
+            Err(e) => error_result(e),
+        }
+    }
+}
+
+/// `browser_get_text` tool
+struct BrowserGetTextTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserGetTextTool {
+    fn name(&self) -> &str {
+        "browser_get_text"
+    }
+
+    fn description(&self) -> &str {
+        "Get the visible text of the page or of one element"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserContentInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: BrowserContentInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        text_result(browser_get_text(input, &context.browser(request)).await)
+    }
+}
+
+/// `browser_get_html` tool
+struct BrowserGetHtmlTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserGetHtmlTool {
+    fn name(&self) -> &str {
+        "browser_get_html"
+    }
+
+    fn description(&self) -> &str {
+        "Get the HTML of the page or of one element"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserContentInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: BrowserContentInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        text_result(browser_get_html(input, &context.browser(request)).await)
+    }
+}
+
+/// `browser_evaluate` tool
+struct BrowserEvaluateTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserEvaluateTool {
+    fn name(&self) -> &str {
+        "browser_evaluate"
+    }
+
+    fn description(&self) -> &str {
+        "Evaluate a JavaScript expression in the page and return the result"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserEvaluateInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: BrowserEvaluateInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        text_result(browser_evaluate(input, &context.browser(request)).await)
+    }
+}
+
+/// `browser_save_page` tool
+struct BrowserSavePageTool;
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for BrowserSavePageTool {
+    fn name(&self) -> &str {
+        "browser_save_page"
+    }
+
+    fn description(&self) -> &str {
+        "Save a snapshot of the current page as a synmem://pages resource"
+    }
+
+    fn input_schema(&self) -> Value {
+        BrowserEmptyInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        _arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        tool_result(browser_save_page(context, request).await)
+    }
+}
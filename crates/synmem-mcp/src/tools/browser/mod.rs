@@ -0,0 +1,32 @@
+//! Generic browser control tools
+//!
+//! This module exposes the browser driver to MCP clients:
+//! - Navigation (open a URL, back, forward, refresh)
+//! - Interaction (click, type, select, wait for an element)
+//! - Extraction (screenshot, text, HTML, JavaScript evaluation, saving the
+//!   page as a `synmem://pages/{id}` resource)
+//!
+//! Each tool goes through the matching core service
+//! ([`NavigationService`](synmem_core::NavigationService),
+//! [`AutomationService`](synmem_core::AutomationService) or
+//! [`ExtractionService`](synmem_core::ExtractionService)).
+
+mod error;
+mod extraction;
+mod interaction;
+mod mcp;
+mod navigation;
+mod schema;
+mod types;
+
+pub use error::BrowserToolError;
+pub use extraction::{
+    browser_evaluate, browser_get_html, browser_get_text, browser_save_page, browser_screenshot,
+};
+pub use interaction::{browser_click, browser_select, browser_type, browser_wait_for};
+pub use mcp::register_tools;
+pub use navigation::{browser_back, browser_forward, browser_navigate, browser_refresh};
+pub use types::*;
+
+#[cfg(test)]
+mod tests;
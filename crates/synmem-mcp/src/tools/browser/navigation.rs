@@ -0,0 +1,94 @@
+//! Navigation tools backed by [`NavigationService`]
+
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::{AutomationService, ExtractionService, NavigationService};
+use tracing::{debug, instrument};
+
+use super::{BrowserNavigateInput, BrowserPageInfo, BrowserToolError};
+
+/// Open a URL, optionally waiting for an element to appear
+#[instrument(skip(driver), fields(url = %input.url))]
+pub async fn browser_navigate<D: BrowserDriverPort>(
+    input: BrowserNavigateInput,
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    validate_url(&input.url)?;
+
+    NavigationService::new(driver.clone())
+        .goto(&input.url)
+        .await
+        .map_err(|e| BrowserToolError::driver("navigation", e))?;
+
+    if let Some(selector) = &input.wait_for {
+        debug!(selector = %selector, "Waiting for element");
+        AutomationService::new(driver.clone())
+            .wait_for_element(selector, input.timeout_ms)
+            .await
+            .map_err(|e| BrowserToolError::driver("wait", e))?;
+    }
+
+    page_info(driver).await
+}
+
+/// Go back in the browser history
+pub async fn browser_back<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    NavigationService::new(driver.clone())
+        .back()
+        .await
+        .map_err(|e| BrowserToolError::driver("back", e))?;
+    page_info(driver).await
+}
+
+/// Go forward in the browser history
+pub async fn browser_forward<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    NavigationService::new(driver.clone())
+        .forward()
+        .await
+        .map_err(|e| BrowserToolError::driver("forward", e))?;
+    page_info(driver).await
+}
+
+/// Reload the current page
+pub async fn browser_refresh<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    NavigationService::new(driver.clone())
+        .refresh()
+        .await
+        .map_err(|e| BrowserToolError::driver("refresh", e))?;
+    page_info(driver).await
+}
+
+/// Describe the page the browser is currently on
+pub(super) async fn page_info<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+) -> Result<BrowserPageInfo, BrowserToolError> {
+    let url = NavigationService::new(driver.clone())
+        .current_url()
+        .await
+        .map_err(|e| BrowserToolError::driver("navigation", e))?;
+    let title = ExtractionService::new(driver.clone())
+        .evaluate_js("document.title")
+        .await
+        .ok()
+        .filter(|title| !title.is_empty());
+
+    Ok(BrowserPageInfo { url, title })
+}
+
+/// Only web pages may be opened through the tools
+fn validate_url(url: &str) -> Result<(), BrowserToolError> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(BrowserToolError::InvalidInput {
+            message: format!("URL must start with http:// or https:// (got {})", url),
+        })
+    }
+}
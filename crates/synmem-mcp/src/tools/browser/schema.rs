@@ -0,0 +1,120 @@
+//! JSON Schemas for the browser tool inputs
+
+use serde_json::Value;
+
+use super::types::default_wait_timeout;
+use super::{
+    BrowserClickInput, BrowserContentInput, BrowserEmptyInput, BrowserEvaluateInput,
+    BrowserNavigateInput, BrowserSelectInput, BrowserTypeInput, BrowserWaitForInput,
+    MAX_WAIT_TIMEOUT_MS,
+};
+use crate::schema::{InputSchema, Schema};
+
+/// Schema for a CSS selector argument
+fn selector(description: &str) -> Schema {
+    Schema::string().min_length(1).description(description)
+}
+
+/// Schema for a wait timeout argument
+fn timeout(description: &str) -> Schema {
+    Schema::integer()
+        .minimum(0)
+        .maximum(MAX_WAIT_TIMEOUT_MS as i64)
+        .default_value(default_wait_timeout())
+        .description(description)
+}
+
+impl InputSchema for BrowserNavigateInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property(
+                "url",
+                Schema::string()
+                    .min_length(1)
+                    .description("The http(s) URL to open"),
+            )
+            .property(
+                "wait_for",
+                selector("CSS selector to wait for once the page has loaded").nullable(),
+            )
+            .property(
+                "timeout_ms",
+                timeout("Maximum time to wait for `wait_for`, in milliseconds"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for BrowserEmptyInput {
+    fn input_schema() -> Value {
+        Schema::object().into_value()
+    }
+}
+
+impl InputSchema for BrowserClickInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property("selector", selector("CSS selector of the element to click"))
+            .into_value()
+    }
+}
+
+impl InputSchema for BrowserTypeInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property("selector", selector("CSS selector of the input element"))
+            .required_property("text", Schema::string().description("Text to type"))
+            .into_value()
+    }
+}
+
+impl InputSchema for BrowserSelectInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property("selector", selector("CSS selector of the <select> element"))
+            .required_property(
+                "value",
+                Schema::string().description("Value of the option to select"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for BrowserWaitForInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property(
+                "selector",
+                selector("CSS selector of the element to wait for"),
+            )
+            .property(
+                "timeout_ms",
+                timeout("Maximum time to wait, in milliseconds"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for BrowserContentInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .property(
+                "selector",
+                selector("CSS selector of the element to read (whole page when absent)").nullable(),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for BrowserEvaluateInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property(
+                "script",
+                Schema::string()
+                    .min_length(1)
+                    .description("JavaScript expression to evaluate in the page"),
+            )
+            .into_value()
+    }
+}
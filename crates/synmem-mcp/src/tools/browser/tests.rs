@@ -0,0 +1,257 @@
+//! Tests for the browser tools
+
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::*;
+use crate::protocol::Content;
+use crate::testing::{test_context_with, MockDriver};
+use crate::tools::{RequestContext, ToolRegistry};
+
+mod navigation_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_navigate_waits_and_reports_page() {
+        let driver = Arc::new(MockDriver::new().with_js_result("Example Domain"));
+        let input = BrowserNavigateInput {
+            url: "https://example.com".to_string(),
+            wait_for: Some("h1".to_string()),
+            timeout_ms: DEFAULT_WAIT_TIMEOUT_MS,
+        };
+
+        let page = browser_navigate(input, &driver).await.unwrap();
+        assert_eq!(page.url, "https://example.com");
+        assert_eq!(page.title.as_deref(), Some("Example Domain"));
+
+        let actions = driver.actions();
+        assert_eq!(actions[0], "goto https://example.com");
+        assert_eq!(actions[1], "wait h1");
+    }
+
+    #[tokio::test]
+    async fn test_navigate_rejects_non_web_urls() {
+        let driver = Arc::new(MockDriver::new());
+        let input = BrowserNavigateInput {
+            url: "file:///etc/passwd".to_string(),
+            wait_for: None,
+            timeout_ms: DEFAULT_WAIT_TIMEOUT_MS,
+        };
+
+        let result = browser_navigate(input, &driver).await;
+        assert!(matches!(result, Err(BrowserToolError::InvalidInput { .. })));
+        assert!(driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_history_navigation() {
+        let driver = Arc::new(MockDriver::new());
+        browser_back(&driver).await.unwrap();
+        browser_forward(&driver).await.unwrap();
+        let page = browser_refresh(&driver).await.unwrap();
+        assert!(page.title.is_none());
+
+        let actions = driver.actions();
+        assert!(actions.contains(&"back".to_string()));
+        assert!(actions.contains(&"forward".to_string()));
+        assert!(actions.contains(&"refresh".to_string()));
+    }
+}
+
+mod interaction_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_click_type_select() {
+        let driver = Arc::new(MockDriver::new());
+        browser_click(
+            BrowserClickInput {
+                selector: "#submit".to_string(),
+            },
+            &driver,
+        )
+        .await
+        .unwrap();
+        browser_type(
+            BrowserTypeInput {
+                selector: "input[name=q]".to_string(),
+                text: "rust".to_string(),
+            },
+            &driver,
+        )
+        .await
+        .unwrap();
+        browser_select(
+            BrowserSelectInput {
+                selector: "#lang".to_string(),
+                value: "en".to_string(),
+            },
+            &driver,
+        )
+        .await
+        .unwrap();
+
+        let actions = driver.actions();
+        assert!(actions.contains(&"click #submit".to_string()));
+        assert!(actions.contains(&"type input[name=q] rust".to_string()));
+        assert!(actions.contains(&"select #lang en".to_string()));
+    }
+}
+
+mod extraction_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_text_of_element() {
+        let driver = Arc::new(MockDriver::new().with_js_result("\"Hello, world\""));
+        let input = BrowserContentInput {
+            selector: Some("p[data-id=\"1\"]".to_string()),
+        };
+
+        let text = browser_get_text(input, &driver).await.unwrap();
+        assert_eq!(text, "Hello, world");
+
+        let script = driver.actions().pop().unwrap();
+        assert!(script.contains(r#"document.querySelector("p[data-id=\"1\"]")"#));
+    }
+
+    #[tokio::test]
+    async fn test_get_text_of_missing_element() {
+        let driver = Arc::new(MockDriver::new().with_js_result("null"));
+        let input = BrowserContentInput {
+            selector: Some(".missing".to_string()),
+        };
+
+        let result = browser_get_text(input, &driver).await;
+        assert!(matches!(
+            result,
+            Err(BrowserToolError::ElementNotFound { selector }) if selector == ".missing"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_html_of_page() {
+        let driver =
+            Arc::new(MockDriver::new().with_page("https://example.com", "<html><p>Hi</p></html>"));
+        browser_navigate(
+            BrowserNavigateInput {
+                url: "https://example.com".to_string(),
+                wait_for: None,
+                timeout_ms: DEFAULT_WAIT_TIMEOUT_MS,
+            },
+            &driver,
+        )
+        .await
+        .unwrap();
+
+        let html = browser_get_html(BrowserContentInput::default(), &driver)
+            .await
+            .unwrap();
+        assert_eq!(html, "<html><p>Hi</p></html>");
+    }
+
+    #[tokio::test]
+    async fn test_save_page_stores_resource() {
+        let driver = MockDriver::new()
+            .with_page("https://example.com", "<h1>Example</h1>")
+            .with_js_result("Example Domain");
+        let context = test_context_with(driver);
+        browser_navigate(
+            BrowserNavigateInput {
+                url: "https://example.com".to_string(),
+                wait_for: None,
+                timeout_ms: DEFAULT_WAIT_TIMEOUT_MS,
+            },
+            &context.driver,
+        )
+        .await
+        .unwrap();
+
+        let saved = browser_save_page(&context, &RequestContext::detached()).await.unwrap();
+        assert!(saved.uri.starts_with("synmem://pages/"));
+        assert_eq!(saved.title.as_deref(), Some("Example Domain"));
+
+        let contents = crate::resources::read(&context, &saved.uri).await.unwrap();
+        assert!(contents.contents[0].text.contains("<h1>Example</h1>"));
+    }
+}
+
+mod mcp_tests {
+    use super::*;
+
+    fn registry() -> ToolRegistry<MockDriver> {
+        let mut registry = ToolRegistry::new();
+        register_tools(&mut registry);
+        registry
+    }
+
+    #[test]
+    fn test_tools_are_registered() {
+        let registry = registry();
+        for name in [
+            "browser_navigate",
+            "browser_click",
+            "browser_type",
+            "browser_screenshot",
+            "browser_get_text",
+            "browser_get_html",
+            "browser_evaluate",
+        ] {
+            assert!(registry.get(name).is_some(), "{} not registered", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_is_image_content() {
+        let context = test_context_with(MockDriver::new());
+        let result = registry()
+            .call(
+                "browser_screenshot",
+                json!({}),
+                &context,
+                &RequestContext::detached(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.is_error);
+        match &result.content[0] {
+            Content::Image { data, mime_type } => {
+                assert_eq!(mime_type, "image/png");
+                assert_eq!(data, "iVBORw==");
+            }
+            other => panic!("expected image content, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_type_requires_text() {
+        let context = test_context_with(MockDriver::new());
+        let error = registry()
+            .call(
+                "browser_type",
+                json!({"selector": "#q"}),
+                &context,
+                &RequestContext::detached(),
+            )
+            .await
+            .unwrap_err();
+        assert!(error.message.contains("missing required property 'text'"));
+    }
+
+    #[tokio::test]
+    async fn test_tool_errors_are_reported_in_result() {
+        let context = test_context_with(MockDriver::new());
+        let result = registry()
+            .call(
+                "browser_navigate",
+                json!({"url": "javascript:alert(1)"}),
+                &context,
+                &RequestContext::detached(),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error);
+    }
+}
@@ -0,0 +1,100 @@
+//! Browser tool inputs and results
+
+use serde::{Deserialize, Serialize};
+
+/// Default timeout when waiting for an element
+pub const DEFAULT_WAIT_TIMEOUT_MS: u64 = 10_000;
+
+/// Longest wait a caller may request
+pub const MAX_WAIT_TIMEOUT_MS: u64 = 120_000;
+
+/// Input parameters for navigating to a URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserNavigateInput {
+    /// The http(s) URL to open
+    pub url: String,
+    /// Optional selector to wait for once the page has loaded
+    #[serde(default)]
+    pub wait_for: Option<String>,
+    /// Maximum time to wait for `wait_for`, in milliseconds
+    #[serde(default = "default_wait_timeout")]
+    pub timeout_ms: u64,
+}
+
+/// Input parameters for tools without arguments
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BrowserEmptyInput {}
+
+/// Input parameters for clicking an element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserClickInput {
+    /// CSS selector of the element to click
+    pub selector: String,
+}
+
+/// Input parameters for typing into an element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserTypeInput {
+    /// CSS selector of the input element
+    pub selector: String,
+    /// Text to type
+    pub text: String,
+}
+
+/// Input parameters for choosing a dropdown option
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserSelectInput {
+    /// CSS selector of the `<select>` element
+    pub selector: String,
+    /// Value of the option to select
+    pub value: String,
+}
+
+/// Input parameters for waiting for an element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserWaitForInput {
+    /// CSS selector of the element to wait for
+    pub selector: String,
+    /// Maximum time to wait, in milliseconds
+    #[serde(default = "default_wait_timeout")]
+    pub timeout_ms: u64,
+}
+
+/// Input parameters for reading content from the page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BrowserContentInput {
+    /// CSS selector of the element to read (whole page when absent)
+    #[serde(default)]
+    pub selector: Option<String>,
+}
+
+/// Input parameters for evaluating JavaScript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserEvaluateInput {
+    /// JavaScript expression to evaluate in the page
+    pub script: String,
+}
+
+pub(super) fn default_wait_timeout() -> u64 {
+    DEFAULT_WAIT_TIMEOUT_MS
+}
+
+/// The page the browser is on after an action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserPageInfo {
+    /// Current URL
+    pub url: String,
+    /// Document title
+    pub title: Option<String>,
+}
+
+/// A page snapshot saved as an MCP resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserSavedPage {
+    /// Resource URI of the snapshot (`synmem://pages/{id}`)
+    pub uri: String,
+    /// URL of the saved page
+    pub url: String,
+    /// Document title
+    pub title: Option<String>,
+}
@@ -0,0 +1,150 @@
+//! Turns at the shared browser page
+//!
+//! Every tool drives the same page, so calls take turns at it or their
+//! actions would interleave. A call takes its turn with its first browser
+//! action, through a [`LeasedDriver`], and keeps it until it releases it or
+//! returns. Waits that do not need the page (rate limiter delays, retry
+//! back-off, media downloads) should happen outside a turn, so that other
+//! calls can use the page meanwhile.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use synmem_core::domain::entities::{BrowserState, SimpleCookie};
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// One call's turn at the page, once taken
+///
+/// Shared by the call's [`LeasedDriver`]s and its
+/// [`RequestContext`](super::RequestContext), so the turn outlives an aborted
+/// call until the registry has stopped what it was loading.
+#[derive(Clone, Default)]
+pub struct BrowserTurn {
+    lease: Arc<Mutex<Option<OwnedMutexGuard<()>>>>,
+}
+
+impl BrowserTurn {
+    /// Take the turn from `lease`, unless it is already held
+    async fn take(&self, lease: &Arc<Mutex<()>>) {
+        let mut held = self.lease.lock().await;
+        if held.is_none() {
+            *held = Some(lease.clone().lock_owned().await);
+        }
+    }
+
+    /// Give the turn back, returning the lease if it was held
+    pub async fn release(&self) -> Option<OwnedMutexGuard<()>> {
+        self.lease.lock().await.take()
+    }
+}
+
+/// Browser driver that takes its call's turn before every action
+pub struct LeasedDriver<D> {
+    driver: Arc<D>,
+    lease: Arc<Mutex<()>>,
+    turn: BrowserTurn,
+}
+
+impl<D: BrowserDriverPort> LeasedDriver<D> {
+    /// Drive `driver` during `turn`, taken from `lease`
+    pub fn new(driver: Arc<D>, lease: Arc<Mutex<()>>, turn: BrowserTurn) -> Self {
+        Self {
+            driver,
+            lease,
+            turn,
+        }
+    }
+
+    /// Give the turn back until the next action, e.g. before a long wait
+    pub async fn release(&self) {
+        self.turn.release().await;
+    }
+
+    async fn driver(&self) -> &D {
+        self.turn.take(&self.lease).await;
+        &self.driver
+    }
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> BrowserDriverPort for LeasedDriver<D> {
+    type Error = D::Error;
+
+    async fn goto(&self, url: &str) -> Result<(), Self::Error> {
+        self.driver().await.goto(url).await
+    }
+
+    async fn back(&self) -> Result<(), Self::Error> {
+        self.driver().await.back().await
+    }
+
+    async fn forward(&self) -> Result<(), Self::Error> {
+        self.driver().await.forward().await
+    }
+
+    async fn refresh(&self) -> Result<(), Self::Error> {
+        self.driver().await.refresh().await
+    }
+
+    async fn current_url(&self) -> Result<String, Self::Error> {
+        self.driver().await.current_url().await
+    }
+
+    async fn click(&self, selector: &str) -> Result<(), Self::Error> {
+        self.driver().await.click(selector).await
+    }
+
+    async fn type_text(&self, selector: &str, text: &str) -> Result<(), Self::Error> {
+        self.driver().await.type_text(selector, text).await
+    }
+
+    async fn select(&self, selector: &str, value: &str) -> Result<(), Self::Error> {
+        self.driver().await.select(selector, value).await
+    }
+
+    async fn upload_files(&self, selector: &str, paths: &[PathBuf]) -> Result<(), Self::Error> {
+        self.driver().await.upload_files(selector, paths).await
+    }
+
+    async fn wait_for_element(&self, selector: &str, timeout_ms: u64) -> Result<(), Self::Error> {
+        self.driver().await.wait_for_element(selector, timeout_ms).await
+    }
+
+    async fn screenshot(&self) -> Result<Vec<u8>, Self::Error> {
+        self.driver().await.screenshot().await
+    }
+
+    async fn get_html(&self) -> Result<String, Self::Error> {
+        self.driver().await.get_html().await
+    }
+
+    async fn evaluate_js(&self, script: &str) -> Result<String, Self::Error> {
+        self.driver().await.evaluate_js(script).await
+    }
+
+    async fn get_cookies(&self) -> Result<Vec<SimpleCookie>, Self::Error> {
+        self.driver().await.get_cookies().await
+    }
+
+    async fn set_cookies(&self, cookies: &[SimpleCookie]) -> Result<(), Self::Error> {
+        self.driver().await.set_cookies(cookies).await
+    }
+
+    async fn save_session(&self) -> Result<BrowserState, Self::Error> {
+        self.driver().await.save_session().await
+    }
+
+    async fn load_session(&self, state: &BrowserState) -> Result<(), Self::Error> {
+        self.driver().await.load_session(state).await
+    }
+
+    async fn clear_session(&self) -> Result<(), Self::Error> {
+        self.driver().await.clear_session().await
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        self.driver().await.close().await
+    }
+}
@@ -1,3 +1,31 @@
 //! MCP Tools for browser automation
+//!
+//! Each integration registers its tools into a [`ToolRegistry`]; see
+//! [`default_registry`] for the set exposed by the server.
 
+pub mod browser;
+mod lease;
+mod registry;
+mod request;
 pub mod twitter;
+
+pub use lease::{BrowserTurn, LeasedDriver};
+pub use registry::{decode_arguments, RateLimiters, Tool, ToolContext, ToolRegistry};
+pub use request::{Progress, RequestContext};
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+
+use crate::config::ToolsConfig;
+use twitter::TwitterSession;
+
+/// Build the registry with every built-in tool, filtered by `config`
+pub fn default_registry<D: BrowserDriverPort>(
+    config: &ToolsConfig,
+    twitter_session: TwitterSession,
+) -> ToolRegistry<D> {
+    let mut registry = ToolRegistry::new();
+    browser::register_tools(&mut registry);
+    twitter::register_tools(&mut registry, twitter_session);
+    registry.apply_config(config);
+    registry
+}
@@ -0,0 +1,487 @@
+//! Tool registry
+//!
+//! Every MCP tool implements [`Tool`] and is registered into a
+//! [`ToolRegistry`]. The server only talks to the registry, so new site
+//! integrations are added by registering their tools rather than by
+//! touching the dispatcher.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::SessionManager;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use super::twitter::{MediaStore, RateLimitConfig, RateLimitStore, RateLimiter};
+use super::{LeasedDriver, RequestContext};
+use crate::config::{RetryPolicies, ToolsConfig};
+use crate::connection::Connections;
+use crate::protocol::{CallToolResult, JsonRpcError, ToolDefinition};
+use crate::schema;
+use crate::storage::{MemoryStorage, Storage};
+
+/// Script run after a cancelled call to abort pending navigation
+const STOP_LOADING_SCRIPT: &str = "window.stop()";
+
+/// State shared by all tools
+pub struct ToolContext<D: BrowserDriverPort> {
+    /// Browser driver used for automation
+    pub driver: Arc<D>,
+    /// Encrypted session store
+    pub session_manager: Arc<Mutex<SessionManager>>,
+    /// Rate limiters shared between tools
+    pub rate_limiters: RateLimiters,
+    /// Configured retry policies
+    pub retry_policies: RetryPolicies,
+    /// Where downloaded media is kept, if downloads are enabled
+    pub media_store: Option<MediaStore>,
+    /// Persistent key-value storage
+    pub storage: Arc<Storage>,
+    /// Where the locks shared with other processes are kept, if anywhere
+    pub lock_dir: Option<PathBuf>,
+    /// Open client connections, used to push notifications
+    pub connections: Arc<Connections>,
+    /// Held by the call whose turn it is at the browser page, so callers
+    /// sharing it do not interleave their actions
+    pub browser_lease: Arc<Mutex<()>>,
+}
+
+impl<D: BrowserDriverPort> ToolContext<D> {
+    /// Create a new context with in-memory storage
+    pub fn new(driver: Arc<D>, session_manager: SessionManager) -> Self {
+        Self {
+            driver,
+            session_manager: Arc::new(Mutex::new(session_manager)),
+            rate_limiters: RateLimiters::new(),
+            retry_policies: RetryPolicies::default(),
+            media_store: None,
+            storage: Arc::new(MemoryStorage::new()),
+            lock_dir: None,
+            connections: Arc::new(Connections::new()),
+            browser_lease: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Use `storage` instead of the in-memory default
+    pub fn with_storage(mut self, storage: Arc<Storage>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Share locks with the other processes keeping theirs under `root`
+    ///
+    /// Needed when other processes use the same storage.
+    pub fn with_lock_dir(mut self, root: impl Into<PathBuf>) -> Self {
+        self.lock_dir = Some(root.into());
+        self
+    }
+
+    /// Retry failed calls according to `policies`
+    pub fn with_retry_policies(mut self, policies: RetryPolicies) -> Self {
+        self.retry_policies = policies;
+        self
+    }
+
+    /// Keep downloaded media in `store`
+    pub fn with_media_store(mut self, store: MediaStore) -> Self {
+        self.media_store = Some(store);
+        self
+    }
+
+    /// Persist the rate limiters' state in `store`
+    pub fn with_rate_limit_store(mut self, store: RateLimitStore) -> Self {
+        self.rate_limiters = RateLimiters::persistent(store);
+        self
+    }
+
+    /// Driver taking `request`'s turn at the page before each action
+    ///
+    /// Tools drive the browser through it rather than through `driver`.
+    pub fn browser(&self, request: &RequestContext) -> Arc<LeasedDriver<D>> {
+        Arc::new(LeasedDriver::new(
+            self.driver.clone(),
+            self.browser_lease.clone(),
+            request.browser_turn().clone(),
+        ))
+    }
+}
+
+/// Named rate limiters
+///
+/// Tools that share a key share a budget. With a [`RateLimitStore`], the
+/// limiters persist their state under their key, so the budget also
+/// survives restarts and is shared with other processes.
+#[derive(Default)]
+pub struct RateLimiters {
+    limiters: std::sync::Mutex<HashMap<String, Arc<RateLimiter>>>,
+    store: Option<RateLimitStore>,
+}
+
+impl RateLimiters {
+    /// Create an empty set of limiters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty set of limiters persisting their state in `store`
+    pub fn persistent(store: RateLimitStore) -> Self {
+        Self {
+            limiters: Default::default(),
+            store: Some(store),
+        }
+    }
+
+    /// Get the limiter for `key`, creating it with `config` on first use
+    pub fn get_or_create(&self, key: &str, config: RateLimitConfig) -> Arc<RateLimiter> {
+        self.get_or_insert(key, || self.create(key, config))
+    }
+
+    /// Get the limiter for `key`, creating it with `config` on first use,
+    /// that also draws every request from `budget`
+    pub fn get_or_create_within(
+        &self,
+        key: &str,
+        config: RateLimitConfig,
+        budget: &Arc<RateLimiter>,
+    ) -> Arc<RateLimiter> {
+        self.get_or_insert(key, || self.create(key, config).with_shared_budget(budget.clone()))
+    }
+
+    fn get_or_insert(&self, key: &str, create: impl FnOnce() -> RateLimiter) -> Arc<RateLimiter> {
+        let mut limiters = self.limiters.lock().unwrap();
+        limiters
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(create()))
+            .clone()
+    }
+
+    fn create(&self, key: &str, config: RateLimitConfig) -> RateLimiter {
+        match &self.store {
+            Some(store) => RateLimiter::persistent(config, store.clone(), key),
+            None => RateLimiter::new(config),
+        }
+    }
+}
+
+/// An MCP tool
+#[async_trait]
+pub trait Tool<D: BrowserDriverPort>: Send + Sync {
+    /// Unique tool name
+    fn name(&self) -> &str;
+
+    /// Human-readable description
+    fn description(&self) -> &str;
+
+    /// JSON Schema for the tool arguments
+    fn input_schema(&self) -> Value;
+
+    /// Run the tool
+    ///
+    /// `arguments` has already been validated against [`Tool::input_schema`].
+    /// Failures are reported through [`CallToolResult::error`]. Long-running
+    /// tools report progress through `request`.
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult;
+}
+
+/// Registry of the tools exposed by the server
+pub struct ToolRegistry<D: BrowserDriverPort> {
+    tools: Vec<Arc<dyn Tool<D>>>,
+}
+
+impl<D: BrowserDriverPort> ToolRegistry<D> {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self { tools: Vec::new() }
+    }
+
+    /// Register a tool, replacing any tool with the same name
+    pub fn register<T: Tool<D> + 'static>(&mut self, tool: T) {
+        self.tools.retain(|t| t.name() != tool.name());
+        self.tools.push(Arc::new(tool));
+    }
+
+    /// Remove the tools not enabled by `config`
+    pub fn apply_config(&mut self, config: &ToolsConfig) {
+        self.tools.retain(|tool| {
+            let enabled = config.is_enabled(tool.name());
+            if !enabled {
+                debug!(tool = %tool.name(), "Tool disabled by configuration");
+            }
+            enabled
+        });
+    }
+
+    /// Look up a tool by name
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn Tool<D>>> {
+        self.tools.iter().find(|t| t.name() == name)
+    }
+
+    /// Names of the registered tools
+    pub fn names(&self) -> Vec<&str> {
+        self.tools.iter().map(|t| t.name()).collect()
+    }
+
+    /// Definitions for `tools/list`
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.tools
+            .iter()
+            .map(|tool| ToolDefinition {
+                name: tool.name().to_string(),
+                description: tool.description().to_string(),
+                input_schema: tool.input_schema(),
+            })
+            .collect()
+    }
+
+    /// Validate the arguments and call the named tool
+    ///
+    /// Unknown tools and schema violations are protocol errors; the latter
+    /// carry every offending path in the error data. A turn at the browser
+    /// the tool took is given back once it returns. When `request` is
+    /// cancelled the tool is aborted and, if it was using the browser, any
+    /// page load it started is stopped before the next call's turn, so the
+    /// browser stays usable.
+    pub async fn call(
+        &self,
+        name: &str,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> Result<CallToolResult, JsonRpcError> {
+        let tool = self
+            .get(name)
+            .ok_or_else(|| JsonRpcError::invalid_params(format!("Unknown tool: {}", name)))?;
+
+        if let Err(violations) = schema::validate(&tool.input_schema(), &arguments) {
+            let summary: Vec<String> = violations
+                .iter()
+                .map(|v| format!("{}: {}", v.path, v.message))
+                .collect();
+            return Err(JsonRpcError::invalid_params(format!(
+                "Invalid arguments: {}",
+                summary.join("; ")
+            ))
+            .with_data(json!({ "violations": violations })));
+        }
+
+        let result = tokio::select! {
+            result = tool.call(arguments, context, request) => result,
+            _ = request.cancelled() => {
+                debug!(tool = %name, "Tool call cancelled");
+                if let Some(_lease) = request.browser_turn().release().await {
+                    if let Err(e) = context.driver.evaluate_js(STOP_LOADING_SCRIPT).await {
+                        warn!(tool = %name, error = %e, "Failed to stop page loading");
+                    }
+                }
+                return Ok(CallToolResult::error("Request cancelled"));
+            }
+        };
+        request.browser_turn().release().await;
+        Ok(result)
+    }
+}
+
+impl<D: BrowserDriverPort> Default for ToolRegistry<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decode validated tool arguments into the tool's input type
+pub fn decode_arguments<T: DeserializeOwned>(arguments: Value) -> Result<T, CallToolResult> {
+    serde_json::from_value(arguments)
+        .map_err(|e| CallToolResult::error(format!("Invalid arguments: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Schema;
+    use crate::testing::{test_context, MockDriver};
+    use crate::tools::Progress;
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl<D: BrowserDriverPort> Tool<D> for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the message back"
+        }
+
+        fn input_schema(&self) -> Value {
+            Schema::object()
+                .required_property("message", Schema::string())
+                .into_value()
+        }
+
+        async fn call(
+            &self,
+            arguments: Value,
+            _context: &ToolContext<D>,
+            _request: &RequestContext,
+        ) -> CallToolResult {
+            CallToolResult::text(arguments["message"].as_str().unwrap_or_default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_and_call_custom_tool() {
+        let mut registry = ToolRegistry::<MockDriver>::new();
+        registry.register(EchoTool);
+        registry.register(EchoTool);
+        assert_eq!(registry.names(), vec!["echo"]);
+
+        let context = test_context();
+        let result = registry
+            .call(
+                "echo",
+                json!({"message": "hello"}),
+                &context,
+                &RequestContext::detached(),
+            )
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+
+        let error = registry
+            .call("echo", json!({}), &context, &RequestContext::detached())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, JsonRpcError::INVALID_PARAMS);
+    }
+
+    /// Opens a page if asked to, then never finishes
+    struct StuckTool;
+
+    #[async_trait]
+    impl<D: BrowserDriverPort> Tool<D> for StuckTool {
+        fn name(&self) -> &str {
+            "stuck"
+        }
+
+        fn description(&self) -> &str {
+            "Never finishes"
+        }
+
+        fn input_schema(&self) -> Value {
+            Schema::object()
+                .property("open", Schema::string())
+                .into_value()
+        }
+
+        async fn call(
+            &self,
+            arguments: Value,
+            context: &ToolContext<D>,
+            request: &RequestContext,
+        ) -> CallToolResult {
+            if let Some(url) = arguments["open"].as_str() {
+                let _ = context.browser(request).goto(url).await;
+            }
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_call_is_aborted_and_stops_loading() {
+        let mut registry = ToolRegistry::<MockDriver>::new();
+        registry.register(StuckTool);
+        let context = test_context();
+        let cancellation = tokio_util::sync::CancellationToken::new();
+        let request = RequestContext::new(Progress::none(), cancellation.clone());
+
+        let call = registry.call("stuck", json!({"open": "a"}), &context, &request);
+        tokio::pin!(call);
+        tokio::select! {
+            _ = &mut call => panic!("stuck call finished"),
+            _ = tokio::time::sleep(std::time::Duration::from_millis(10)) => {}
+        }
+        cancellation.cancel();
+        let result = call.await.unwrap();
+
+        assert!(result.is_error);
+        assert_eq!(context.driver.actions(), vec!["goto a", "js window.stop()"]);
+        // The turn was given back
+        assert!(context.browser_lease.try_lock().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_call_off_the_browser_leaves_it_alone() {
+        let mut registry = ToolRegistry::<MockDriver>::new();
+        registry.register(StuckTool);
+        let context = test_context();
+        let cancellation = tokio_util::sync::CancellationToken::new();
+        let request = RequestContext::new(Progress::none(), cancellation.clone());
+
+        // Another call is using the page
+        let other = context.browser_lease.clone().lock_owned().await;
+        let call = registry.call("stuck", json!({}), &context, &request);
+        cancellation.cancel();
+        assert!(call.await.unwrap().is_error);
+        drop(other);
+
+        assert!(context.driver.actions().is_empty());
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let mut registry = ToolRegistry::<MockDriver>::new();
+        registry.register(EchoTool);
+        registry.apply_config(&ToolsConfig {
+            enabled: Some(vec!["twitter_*".to_string()]),
+            disabled: vec![],
+            ..Default::default()
+        });
+        assert!(registry.get("echo").is_none());
+    }
+
+    #[test]
+    fn test_rate_limiters_are_shared_by_key() {
+        let limiters = RateLimiters::new();
+        let a = limiters.get_or_create("twitter.post", RateLimitConfig::for_post());
+        let b = limiters.get_or_create("twitter.post", RateLimitConfig::for_read());
+        let c = limiters.get_or_create("twitter.read", RateLimitConfig::for_read());
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiters_within_budget() {
+        let limiters = RateLimiters::new();
+        let budget = limiters.get_or_create(
+            "123/twitter.account",
+            RateLimitConfig {
+                max_requests: 1,
+                window_seconds: 60,
+                min_delay_ms: 0,
+                ..Default::default()
+            },
+        );
+        let read = limiters.get_or_create_within(
+            "123/twitter.read",
+            RateLimitConfig::for_read(),
+            &budget,
+        );
+        assert!(Arc::ptr_eq(
+            &read,
+            &limiters.get_or_create("123/twitter.read", RateLimitConfig::for_read())
+        ));
+
+        assert!(read.acquire().await.is_ok());
+        assert!(read.acquire().await.is_err());
+    }
+}
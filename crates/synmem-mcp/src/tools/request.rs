@@ -0,0 +1,126 @@
+//! Per-request state handed to tools
+//!
+//! A [`RequestContext`] lets a tool report progress to the client that
+//! called it and notice when that client cancels the request. The registry
+//! aborts cancelled calls on its own; tools only need to check for
+//! cancellation when they want to stop between steps themselves. It also
+//! holds the call's [`BrowserTurn`].
+
+use serde_json::json;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+use super::BrowserTurn;
+use crate::protocol::{JsonRpcNotification, ProgressParams, ProgressToken};
+
+/// State of one `tools/call` request
+#[derive(Clone, Default)]
+pub struct RequestContext {
+    progress: Progress,
+    cancellation: CancellationToken,
+    browser_turn: BrowserTurn,
+}
+
+impl RequestContext {
+    /// Create a context reporting to `progress` and cancelled by `cancellation`
+    pub fn new(progress: Progress, cancellation: CancellationToken) -> Self {
+        Self {
+            progress,
+            cancellation,
+            browser_turn: BrowserTurn::default(),
+        }
+    }
+
+    /// A context that reports nothing and is never cancelled
+    pub fn detached() -> Self {
+        Self::default()
+    }
+
+    /// Progress reporter for this request
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Whether the client cancelled the request
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Wait until the client cancels the request
+    pub async fn cancelled(&self) {
+        self.cancellation.cancelled().await
+    }
+
+    /// This request's turn at the browser page
+    pub fn browser_turn(&self) -> &BrowserTurn {
+        &self.browser_turn
+    }
+}
+
+/// Sends `notifications/progress` for one request
+///
+/// Does nothing when the client did not ask for progress.
+#[derive(Clone, Default)]
+pub struct Progress {
+    target: Option<(ProgressToken, broadcast::Sender<JsonRpcNotification>)>,
+}
+
+impl Progress {
+    /// A reporter that drops every update
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// A reporter sending updates tagged with `token` to `notifications`
+    pub(crate) fn new(
+        token: ProgressToken,
+        notifications: broadcast::Sender<JsonRpcNotification>,
+    ) -> Self {
+        Self {
+            target: Some((token, notifications)),
+        }
+    }
+
+    /// Report `progress` units of work done out of `total`
+    pub fn report(&self, progress: u64, total: Option<u64>) {
+        let Some((token, notifications)) = &self.target else {
+            return;
+        };
+        let params = ProgressParams {
+            progress_token: token.clone(),
+            progress,
+            total,
+        };
+        let _ = notifications.send(JsonRpcNotification::new(
+            "notifications/progress",
+            Some(serde_json::to_value(params).unwrap_or_else(|_| json!({}))),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::RequestId;
+
+    #[test]
+    fn test_progress_is_tagged_with_token() {
+        let (sender, mut receiver) = broadcast::channel(4);
+        let progress = Progress::new(RequestId::String("abc".to_string()), sender);
+
+        progress.report(3, Some(10));
+        let notification = receiver.try_recv().unwrap();
+        assert_eq!(notification.method, "notifications/progress");
+        assert_eq!(
+            notification.params.unwrap(),
+            json!({"progressToken": "abc", "progress": 3, "total": 10})
+        );
+    }
+
+    #[test]
+    fn test_detached_context_reports_nothing() {
+        let request = RequestContext::detached();
+        request.progress().report(1, None);
+        assert!(!request.is_cancelled());
+    }
+}
@@ -0,0 +1,297 @@
+//! Local archive of tweet media
+//!
+//! With `download_media`, the read tools keep a copy of every photo, GIF
+//! and video of the tweets they return in the [`MediaStore`]. The best
+//! version available is downloaded: photos at their original size, and
+//! for videos, whose page only shows a poster frame, the highest bitrate
+//! MP4 listed by the embed API.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::Url;
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use super::media::{decode_data_url, download, is_data_url};
+use super::{LocalMedia, MediaStore, Tweet, TwitterError};
+
+/// Tweet details served to embedded tweets, listing the video variants
+const SYNDICATION_URL: &str = "https://cdn.syndication.twimg.com/tweet-result";
+
+/// How long a tweet details request may take
+const SYNDICATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Download the media of `tweets`, and of the tweets they quote, into `store`
+///
+/// Sets `local` on every media that could be archived and returns how many
+/// were. Failures are logged and leave `local` unset, so one broken file
+/// does not lose the tweets that were read.
+///
+/// # Example
+/// ```ignore
+/// let mut result = twitter_search(input, &session, &rate_limiter, &driver, &progress).await?;
+/// archive_media(&mut result.tweets, &MediaStore::new(".synmem/media")).await;
+/// ```
+pub async fn archive_media(tweets: &mut [Tweet], store: &MediaStore) -> usize {
+    let mut archiver = Archiver {
+        store,
+        downloaded: HashMap::new(),
+        videos: HashMap::new(),
+    };
+    let mut archived = 0;
+    for tweet in tweets {
+        archived += archiver.archive_tweet(tweet).await;
+        // Quotes are parsed one level deep
+        if let Some(quoted) = &mut tweet.quoted_tweet {
+            archived += archiver.archive_tweet(quoted).await;
+        }
+    }
+    archived
+}
+
+/// Downloads of one call, shared between its tweets
+struct Archiver<'a> {
+    store: &'a MediaStore,
+    /// Files stored so far by source URL
+    downloaded: HashMap<String, LocalMedia>,
+    /// Video variants by tweet ID, `None` when they could not be fetched
+    videos: HashMap<String, Option<Vec<String>>>,
+}
+
+impl Archiver<'_> {
+    async fn archive_tweet(&mut self, tweet: &mut Tweet) -> usize {
+        let mut archived = 0;
+        let mut video_index = 0;
+        for media in &mut tweet.media {
+            let source = match media.media_type.as_str() {
+                "video" => {
+                    let variant = self.video_url(&tweet.id, video_index).await;
+                    video_index += 1;
+                    variant.unwrap_or_else(|| media.url.clone())
+                }
+                "image" => original_image_url(&media.url),
+                _ => media.url.clone(),
+            };
+            match self.fetch(&source).await {
+                Ok(local) => {
+                    media.local = Some(local);
+                    archived += 1;
+                }
+                Err(e) => {
+                    warn!(tweet = %tweet.id, url = %source, error = %e, "Failed to archive media")
+                }
+            }
+        }
+        archived
+    }
+
+    /// Store the file at `url`, downloading it unless it already is
+    async fn fetch(&mut self, url: &str) -> Result<LocalMedia, TwitterError> {
+        if let Some(local) = self.downloaded.get(url) {
+            return Ok(local.clone());
+        }
+        let local = match self.store.lookup(url).await {
+            Some(local) => local,
+            None => {
+                let (bytes, mime) = if is_data_url(url) {
+                    decode_data_url(url)?
+                } else {
+                    download(url).await?
+                };
+                self.store.put(url, &bytes, &mime).await?
+            }
+        };
+        self.downloaded.insert(url.to_string(), local.clone());
+        Ok(local)
+    }
+
+    /// URL of the best variant of the `index`-th video of a tweet
+    async fn video_url(&mut self, tweet_id: &str, index: usize) -> Option<String> {
+        if !self.videos.contains_key(tweet_id) {
+            let variants = match video_variants(tweet_id).await {
+                Ok(variants) => Some(variants),
+                Err(e) => {
+                    warn!(tweet = %tweet_id, error = %e, "Failed to list the video variants");
+                    None
+                }
+            };
+            self.videos.insert(tweet_id.to_string(), variants);
+        }
+        self.videos.get(tweet_id)?.as_ref()?.get(index).cloned()
+    }
+}
+
+/// Tweet details from the embed API, limited to what is needed here
+#[derive(Debug, Deserialize)]
+pub(super) struct SyndicationTweet {
+    #[serde(default, rename = "mediaDetails")]
+    media_details: Vec<SyndicationMedia>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SyndicationMedia {
+    #[serde(rename = "type")]
+    media_type: String,
+    #[serde(default)]
+    video_info: Option<VideoInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VideoInfo {
+    #[serde(default)]
+    variants: Vec<VideoVariant>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VideoVariant {
+    #[serde(default)]
+    bitrate: Option<u64>,
+    #[serde(default)]
+    content_type: String,
+    url: String,
+}
+
+/// Best MP4 of each video of a tweet, in order
+async fn video_variants(tweet_id: &str) -> Result<Vec<String>, TwitterError> {
+    let failed = |e: reqwest::Error| TwitterError::NetworkError {
+        message: format!("Failed to read the details of tweet {}: {}", tweet_id, e),
+    };
+    let token = syndication_token(tweet_id).ok_or_else(|| TwitterError::InvalidInput {
+        message: format!("Invalid tweet ID: {}", tweet_id),
+    })?;
+    let client = reqwest::Client::builder()
+        .timeout(SYNDICATION_TIMEOUT)
+        .build()
+        .map_err(failed)?;
+    let details: SyndicationTweet = client
+        .get(SYNDICATION_URL)
+        .query(&[("id", tweet_id), ("token", token.as_str())])
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(failed)?
+        .json()
+        .await
+        .map_err(failed)?;
+    let variants = best_video_variants(&details);
+    debug!(tweet = %tweet_id, videos = variants.len(), "Listed the video variants");
+    Ok(variants)
+}
+
+/// Highest bitrate MP4 of each video in `details`
+pub(super) fn best_video_variants(details: &SyndicationTweet) -> Vec<String> {
+    details
+        .media_details
+        .iter()
+        .filter(|media| media.media_type == "video")
+        .filter_map(|media| {
+            media
+                .video_info
+                .as_ref()?
+                .variants
+                .iter()
+                .filter(|variant| variant.content_type == "video/mp4")
+                .max_by_key(|variant| variant.bitrate.unwrap_or(0))
+                .map(|variant| variant.url.clone())
+        })
+        .collect()
+}
+
+/// URL of a photo at its original size
+///
+/// Photos are served from `pbs.twimg.com/media/` in the size named by the
+/// `name` parameter ("small", "360x360", ...); "orig" is the upload.
+pub(super) fn original_image_url(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    if parsed.host_str() != Some("pbs.twimg.com") || !parsed.path().starts_with("/media/") {
+        return url.to_string();
+    }
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| key != "name")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    parsed
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair("name", "orig");
+    parsed.to_string()
+}
+
+/// Token the embed API expects for a tweet
+///
+/// The embed script sends `(id / 1e15 * π)` written in base 36, without
+/// its zeros and dot.
+pub(super) fn syndication_token(tweet_id: &str) -> Option<String> {
+    let id: u64 = tweet_id.parse().ok()?;
+    let value = id as f64 / 1e15 * std::f64::consts::PI;
+    Some(to_radix_string(value, 36).replace(['0', '.'], ""))
+}
+
+/// `value.toString(radix)` as JavaScript writes a positive number below 2^53
+///
+/// Writes the shortest fraction that reads back as `value`, like V8.
+fn to_radix_string(value: f64, radix: u32) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let base = radix as f64;
+    let next_up = |x: f64| f64::from_bits(x.to_bits() + 1);
+
+    let mut integer = value.floor();
+    let mut fraction = value - integer;
+    // Half the distance to the next number, below which digits are noise
+    let mut delta = (0.5 * (next_up(value) - value)).max(next_up(0.0));
+    let mut fraction_digits: Vec<u32> = Vec::new();
+    if fraction >= delta {
+        loop {
+            fraction *= base;
+            delta *= base;
+            let digit = fraction as u32;
+            fraction_digits.push(digit);
+            fraction -= digit as f64;
+            if (fraction > 0.5 || (fraction == 0.5 && digit & 1 == 1)) && fraction + delta > 1.0 {
+                // Round up, carrying into the integer part if needed
+                loop {
+                    match fraction_digits.pop() {
+                        Some(digit) if digit + 1 < radix => {
+                            fraction_digits.push(digit + 1);
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            integer += 1.0;
+                            break;
+                        }
+                    }
+                }
+                break;
+            }
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+
+    let mut integer_digits = Vec::new();
+    loop {
+        let remainder = integer % base;
+        integer_digits.push(DIGITS[remainder as usize] as char);
+        integer = (integer - remainder) / base;
+        if integer <= 0.0 {
+            break;
+        }
+    }
+    let mut text: String = integer_digits.into_iter().rev().collect();
+    if !fraction_digits.is_empty() {
+        text.push('.');
+        text.extend(
+            fraction_digits
+                .iter()
+                .map(|&digit| DIGITS[digit as usize] as char),
+        );
+    }
+    text
+}
@@ -0,0 +1,369 @@
+//! Twitter direct message tools
+//!
+//! List conversations, read one, and send messages through the messages
+//! UI.
+//!
+//! Message bodies are private: they are returned to the caller but never
+//! logged, and the DM types redact them from their Debug output.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tracing::debug;
+
+use super::page::{self, CONVERSATION_SELECTOR, MESSAGE_SELECTOR, TWITTER_BASE_URL};
+use super::parser::{parse_conversations, parse_messages, parse_typeahead};
+use super::timeline::is_valid_username;
+use super::{
+    RateLimiter, RateLimitConfig, TwitterError, TwitterListConversationsInput,
+    TwitterListConversationsResult, TwitterReadDmInput, TwitterReadDmResult, TwitterSendDmInput,
+    TwitterSendDmResult, TwitterSession, DM_MAX_LENGTH, MAX_RESULTS_PER_REQUEST,
+};
+
+/// Message composer of an open conversation
+const COMPOSER_SELECTOR: &str = "div[data-testid=\"dmComposerTextInput\"]";
+
+/// Button sending the composed message
+const SEND_BUTTON_SELECTOR: &str = "button[data-testid=\"dmComposerSendButton\"]";
+
+/// Recipient search box of the new message dialog
+const SEARCH_PEOPLE_SELECTOR: &str = "input[data-testid=\"searchPeople\"]";
+
+/// Account suggested by the recipient search
+const TYPEAHEAD_USER_SELECTOR: &str = "div[data-testid=\"TypeaheadUser\"]";
+
+/// Button moving from the recipient search to the conversation
+const NEXT_BUTTON_SELECTOR: &str = "button[data-testid=\"nextButton\"]";
+
+/// How long to wait for dialogs and suggestions
+const DIALOG_TIMEOUT_MS: u64 = 5_000;
+
+/// How many times to look for a sent message before giving up
+const SEND_CONFIRM_ATTEMPTS: usize = 10;
+
+/// Delay between looks for a sent message
+const SEND_CONFIRM_INTERVAL: Duration = Duration::from_millis(500);
+
+/// List DM conversations
+///
+/// # Arguments
+/// * `input` - The number of conversations to list
+/// * `session` - Valid Twitter session
+/// * `rate_limiter` - The DM rate limiter
+/// * `driver` - Browser driver used to open the messages UI
+///
+/// # Returns
+/// Result containing the most recent conversations
+///
+/// # Example
+/// ```ignore
+/// let input = TwitterListConversationsInput { count: 10 };
+/// let result = twitter_list_conversations(input, &session, &rate_limiter, &driver).await?;
+/// for conversation in result.conversations {
+///     println!("{}: {}", conversation.conversation_id, conversation.display_name);
+/// }
+/// ```
+pub async fn twitter_list_conversations<D: BrowserDriverPort>(
+    input: TwitterListConversationsInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterListConversationsResult, TwitterError> {
+    // Validate input
+    validate_count(input.count)?;
+
+    // Check rate limit
+    rate_limiter.acquire().await?;
+
+    // Validate session
+    validate_session(session)?;
+
+    let url = format!("{}/messages", TWITTER_BASE_URL);
+    let conversations = if page::open(driver, &url, CONVERSATION_SELECTOR).await? {
+        let mut conversations = parse_conversations(&page::html(driver).await?);
+        conversations.truncate(input.count);
+        conversations
+    } else {
+        vec![]
+    };
+
+    debug!(count = conversations.len(), "Listed conversations");
+    Ok(TwitterListConversationsResult {
+        success: true,
+        conversations,
+        error: None,
+    })
+}
+
+/// Read the most recent messages of a DM conversation
+///
+/// # Arguments
+/// * `input` - The conversation and number of messages
+/// * `session` - Valid Twitter session
+/// * `rate_limiter` - The DM rate limiter
+/// * `driver` - Browser driver used to open the conversation
+///
+/// # Returns
+/// Result containing up to `count` messages, oldest first
+pub async fn twitter_read_dm<D: BrowserDriverPort>(
+    input: TwitterReadDmInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterReadDmResult, TwitterError> {
+    // Validate input
+    validate_conversation_id(&input.conversation_id)?;
+    validate_count(input.count)?;
+
+    // Check rate limit
+    rate_limiter.acquire().await?;
+
+    // Validate session
+    validate_session(session)?;
+
+    open_conversation(driver, &input.conversation_id).await?;
+    page::wait(driver, MESSAGE_SELECTOR, DIALOG_TIMEOUT_MS).await;
+    let mut messages = parse_messages(&page::html(driver).await?);
+    let excess = messages.len().saturating_sub(input.count);
+    messages.drain(..excess);
+
+    debug!(
+        conversation_id = %input.conversation_id,
+        count = messages.len(),
+        "Read conversation"
+    );
+    Ok(TwitterReadDmResult {
+        success: true,
+        conversation_id: input.conversation_id,
+        messages,
+        error: None,
+    })
+}
+
+/// Send a direct message
+///
+/// # Arguments
+/// * `input` - The conversation or username, and the message text
+/// * `session` - Valid Twitter session
+/// * `rate_limiter` - The DM rate limiter
+/// * `driver` - Browser driver used to send the message
+///
+/// # Returns
+/// Result containing the conversation the message was sent to
+///
+/// # Example
+/// ```ignore
+/// let input = TwitterSendDmInput {
+///     conversation_id: None,
+///     username: Some("rustlang".to_string()),
+///     text: "Hello!".to_string(),
+/// };
+/// let result = twitter_send_dm(input, &session, &rate_limiter, &driver).await?;
+/// ```
+pub async fn twitter_send_dm<D: BrowserDriverPort>(
+    input: TwitterSendDmInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterSendDmResult, TwitterError> {
+    // Validate input
+    validate_send_input(&input)?;
+
+    // Check rate limit
+    rate_limiter.acquire().await?;
+
+    // Validate session
+    validate_session(session)?;
+
+    match (&input.conversation_id, &input.username) {
+        (Some(conversation_id), _) => open_conversation(driver, conversation_id).await?,
+        (None, Some(username)) => start_conversation(driver, username).await?,
+        (None, None) => unreachable!("validated above"),
+    }
+
+    let sent_before = outgoing_count(&page::html(driver).await?);
+    page::type_text(driver, COMPOSER_SELECTOR, &input.text).await?;
+    page::click(driver, SEND_BUTTON_SELECTOR).await?;
+    confirm_sent(driver, sent_before, input.text.trim()).await?;
+
+    // Starting a conversation navigates to it, revealing its ID
+    let url = page::current_url(driver).await?;
+    let conversation_id = input.conversation_id.or_else(|| conversation_id_of(&url));
+    debug!(conversation_id = ?conversation_id, "Sent direct message");
+    Ok(TwitterSendDmResult {
+        success: true,
+        conversation_id,
+        error: None,
+    })
+}
+
+/// Open an existing conversation
+async fn open_conversation<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    conversation_id: &str,
+) -> Result<(), TwitterError> {
+    let url = format!("{}/messages/{}", TWITTER_BASE_URL, conversation_id);
+    if !page::open(driver, &url, COMPOSER_SELECTOR).await? {
+        return Err(TwitterError::InvalidInput {
+            message: format!("Conversation not found: {}", conversation_id),
+        });
+    }
+    Ok(())
+}
+
+/// Open the one-to-one conversation with `username`, creating it if needed
+async fn start_conversation<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    username: &str,
+) -> Result<(), TwitterError> {
+    let username = username.trim().trim_start_matches('@');
+    let url = format!("{}/messages/compose", TWITTER_BASE_URL);
+    if !page::open(driver, &url, SEARCH_PEOPLE_SELECTOR).await? {
+        return Err(TwitterError::Unknown {
+            message: "The new message dialog did not load".to_string(),
+        });
+    }
+
+    page::type_text(driver, SEARCH_PEOPLE_SELECTOR, username).await?;
+    let not_found = || TwitterError::UserNotFound {
+        username: username.to_string(),
+    };
+    if !page::wait(driver, TYPEAHEAD_USER_SELECTOR, DIALOG_TIMEOUT_MS).await {
+        return Err(not_found());
+    }
+    // The first suggestion must be the account itself, not a lookalike
+    let suggestions = parse_typeahead(&page::html(driver).await?);
+    if !suggestions
+        .first()
+        .is_some_and(|suggestion| suggestion.eq_ignore_ascii_case(username))
+    {
+        return Err(not_found());
+    }
+
+    page::click(driver, TYPEAHEAD_USER_SELECTOR).await?;
+    page::click(driver, NEXT_BUTTON_SELECTOR).await?;
+    if !page::wait(driver, COMPOSER_SELECTOR, DIALOG_TIMEOUT_MS).await {
+        return Err(TwitterError::Unknown {
+            message: format!("Could not open a conversation with {}", username),
+        });
+    }
+    Ok(())
+}
+
+/// Wait until a new outgoing message with `text` shows up
+async fn confirm_sent<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    sent_before: usize,
+    text: &str,
+) -> Result<(), TwitterError> {
+    for attempt in 0..SEND_CONFIRM_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(SEND_CONFIRM_INTERVAL).await;
+        }
+        let messages = parse_messages(&page::html(driver).await?);
+        let outgoing: Vec<_> = messages.iter().filter(|m| m.outgoing).collect();
+        let delivered = outgoing.len() > sent_before
+            && outgoing
+                .last()
+                .is_some_and(|message| message.text.trim() == text);
+        if delivered {
+            return Ok(());
+        }
+    }
+    Err(TwitterError::Unknown {
+        message: "Twitter did not confirm the message".to_string(),
+    })
+}
+
+fn outgoing_count(html: &str) -> usize {
+    parse_messages(html)
+        .iter()
+        .filter(|message| message.outgoing)
+        .count()
+}
+
+/// Conversation ID from a `/messages/<id>` URL
+fn conversation_id_of(url: &str) -> Option<String> {
+    let (_, path) = url.split_once("/messages/")?;
+    let id = path.split(['/', '?', '#']).next()?;
+    validate_conversation_id(id).ok()?;
+    Some(id.to_string())
+}
+
+/// Validate a conversation ID ("123" for groups, "123-456" otherwise)
+fn validate_conversation_id(conversation_id: &str) -> Result<(), TwitterError> {
+    let valid = !conversation_id.is_empty()
+        && conversation_id.split('-').count() <= 2
+        && conversation_id
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    if !valid {
+        return Err(TwitterError::InvalidInput {
+            message: format!("Invalid conversation ID: {}", conversation_id),
+        });
+    }
+    Ok(())
+}
+
+fn validate_count(count: usize) -> Result<(), TwitterError> {
+    if count == 0 {
+        return Err(TwitterError::InvalidInput {
+            message: "Count must be greater than 0".to_string(),
+        });
+    }
+    if count > MAX_RESULTS_PER_REQUEST {
+        return Err(TwitterError::InvalidInput {
+            message: format!("Count cannot exceed {}", MAX_RESULTS_PER_REQUEST),
+        });
+    }
+    Ok(())
+}
+
+/// Validate the send input
+fn validate_send_input(input: &TwitterSendDmInput) -> Result<(), TwitterError> {
+    match (&input.conversation_id, &input.username) {
+        (Some(_), Some(_)) | (None, None) => {
+            return Err(TwitterError::InvalidInput {
+                message: "Provide either conversation_id or username".to_string(),
+            })
+        }
+        (Some(conversation_id), None) => validate_conversation_id(conversation_id)?,
+        (None, Some(username)) => {
+            if !is_valid_username(username.trim().trim_start_matches('@')) {
+                return Err(TwitterError::InvalidInput {
+                    message: format!("Invalid username format: {}", username),
+                });
+            }
+        }
+    }
+
+    if input.text.trim().is_empty() {
+        return Err(TwitterError::InvalidInput {
+            message: "Message must not be empty".to_string(),
+        });
+    }
+    let char_count = input.text.chars().count();
+    if char_count > DM_MAX_LENGTH {
+        return Err(TwitterError::InvalidInput {
+            message: format!(
+                "Message exceeds maximum length of {} characters (got {})",
+                DM_MAX_LENGTH, char_count
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Validate the Twitter session
+fn validate_session(session: &TwitterSession) -> Result<(), TwitterError> {
+    if session.cookies.is_empty() || session.csrf_token.is_empty() {
+        return Err(TwitterError::NoSession);
+    }
+    Ok(())
+}
+
+/// Create a rate limiter configured for direct messages
+pub fn create_dm_rate_limiter() -> RateLimiter {
+    RateLimiter::new(RateLimitConfig::for_dm())
+}
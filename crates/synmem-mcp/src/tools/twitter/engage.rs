@@ -0,0 +1,363 @@
+//! Twitter engagement tools
+//!
+//! Like, unlike, retweet and bookmark tweets, and follow or unfollow
+//! accounts, by toggling the buttons of the web client.
+//!
+//! Every action is idempotent: the button that is rendered tells whether
+//! the action is already applied (e.g. `unlike` instead of `like`), in
+//! which case nothing is clicked and the result reports `changed: false`.
+
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tracing::debug;
+
+use super::page::{
+    self, FOCAL_TWEET_SELECTOR, PROFILE_SELECTOR, TWEET_SELECTOR, TWITTER_BASE_URL,
+};
+use super::parser::{has_element, missing_account};
+use super::profile::missing_account_error;
+use super::read_thread::extract_tweet_id;
+use super::timeline::is_valid_username;
+use super::{
+    RateLimiter, TwitterActionResult, TwitterError, TwitterSession, TwitterTweetActionInput,
+    TwitterUserActionInput,
+};
+
+/// Button shown on one's own profile instead of "Follow"
+const EDIT_PROFILE_SELECTOR: &str = "a[data-testid=\"editProfileButton\"]";
+
+/// How long to wait for the page to reflect an action
+const TOGGLE_TIMEOUT_MS: u64 = 5_000;
+
+/// Action on a tweet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TweetAction {
+    /// Like the tweet
+    Like,
+    /// Remove a like
+    Unlike,
+    /// Retweet (repost) the tweet
+    Retweet,
+    /// Add the tweet to the bookmarks
+    Bookmark,
+}
+
+impl TweetAction {
+    /// All tweet actions
+    pub const ALL: [TweetAction; 4] = [
+        TweetAction::Like,
+        TweetAction::Unlike,
+        TweetAction::Retweet,
+        TweetAction::Bookmark,
+    ];
+
+    /// Name of the action, e.g. "like"
+    pub fn name(self) -> &'static str {
+        match self {
+            TweetAction::Like => "like",
+            TweetAction::Unlike => "unlike",
+            TweetAction::Retweet => "retweet",
+            TweetAction::Bookmark => "bookmark",
+        }
+    }
+
+    fn toggle(self) -> Toggle {
+        match self {
+            TweetAction::Like => Toggle::new("like", "unlike", None),
+            TweetAction::Unlike => Toggle::new("unlike", "like", None),
+            TweetAction::Retweet => Toggle::new(
+                "retweet",
+                "unretweet",
+                Some("div[data-testid=\"retweetConfirm\"]"),
+            ),
+            TweetAction::Bookmark => Toggle::new("bookmark", "removeBookmark", None),
+        }
+    }
+}
+
+/// Action on an account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserAction {
+    /// Follow the account
+    Follow,
+    /// Stop following the account
+    Unfollow,
+}
+
+impl UserAction {
+    /// All account actions
+    pub const ALL: [UserAction; 2] = [UserAction::Follow, UserAction::Unfollow];
+
+    /// Name of the action, e.g. "follow"
+    pub fn name(self) -> &'static str {
+        match self {
+            UserAction::Follow => "follow",
+            UserAction::Unfollow => "unfollow",
+        }
+    }
+
+    /// Buttons toggling the action on `username`
+    ///
+    /// Profile pages also show follow buttons for suggested accounts, so
+    /// the buttons are matched on the handle in their label, e.g.
+    /// "Follow @rustlang". Their test IDs are prefixed with the account's
+    /// user ID, e.g. "783214-follow".
+    fn toggle(self, username: &str) -> Toggle {
+        let button = |suffix: &str| {
+            format!(
+                "button[data-testid$=\"-{}\"][aria-label$=\"@{}\" i]",
+                suffix, username
+            )
+        };
+        let follow = button("follow");
+        let unfollow = button("unfollow");
+        match self {
+            UserAction::Follow => Toggle {
+                button: follow,
+                applied: unfollow,
+                confirm: None,
+            },
+            UserAction::Unfollow => Toggle {
+                button: unfollow,
+                applied: follow,
+                confirm: Some("button[data-testid=\"confirmationSheetConfirm\"]"),
+            },
+        }
+    }
+}
+
+/// Like a tweet
+///
+/// # Example
+/// ```ignore
+/// let input = TwitterTweetActionInput {
+///     tweet_url_or_id: "https://x.com/rustlang/status/123456789".to_string(),
+/// };
+/// let result = twitter_like(input, &session, &rate_limiter, &driver).await?;
+/// ```
+pub async fn twitter_like<D: BrowserDriverPort>(
+    input: TwitterTweetActionInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterActionResult, TwitterError> {
+    twitter_tweet_action(TweetAction::Like, input, session, rate_limiter, driver).await
+}
+
+/// Remove a like from a tweet
+pub async fn twitter_unlike<D: BrowserDriverPort>(
+    input: TwitterTweetActionInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterActionResult, TwitterError> {
+    twitter_tweet_action(TweetAction::Unlike, input, session, rate_limiter, driver).await
+}
+
+/// Retweet a tweet
+pub async fn twitter_retweet<D: BrowserDriverPort>(
+    input: TwitterTweetActionInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterActionResult, TwitterError> {
+    twitter_tweet_action(TweetAction::Retweet, input, session, rate_limiter, driver).await
+}
+
+/// Bookmark a tweet
+pub async fn twitter_bookmark<D: BrowserDriverPort>(
+    input: TwitterTweetActionInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterActionResult, TwitterError> {
+    twitter_tweet_action(TweetAction::Bookmark, input, session, rate_limiter, driver).await
+}
+
+/// Follow an account
+///
+/// # Example
+/// ```ignore
+/// let input = TwitterUserActionInput {
+///     username: "rustlang".to_string(),
+/// };
+/// let result = twitter_follow(input, &session, &rate_limiter, &driver).await?;
+/// ```
+pub async fn twitter_follow<D: BrowserDriverPort>(
+    input: TwitterUserActionInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterActionResult, TwitterError> {
+    twitter_user_action(UserAction::Follow, input, session, rate_limiter, driver).await
+}
+
+/// Stop following an account
+pub async fn twitter_unfollow<D: BrowserDriverPort>(
+    input: TwitterUserActionInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterActionResult, TwitterError> {
+    twitter_user_action(UserAction::Unfollow, input, session, rate_limiter, driver).await
+}
+
+/// Apply `action` to a tweet
+///
+/// # Returns
+/// Result whose `changed` is `false` when the action was already applied
+pub async fn twitter_tweet_action<D: BrowserDriverPort>(
+    action: TweetAction,
+    input: TwitterTweetActionInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterActionResult, TwitterError> {
+    // Validate input
+    let tweet_id = extract_tweet_id(input.tweet_url_or_id.trim())?;
+
+    // Check rate limit
+    rate_limiter.acquire().await?;
+
+    // Validate session
+    validate_session(session)?;
+
+    let url = format!("{}/i/status/{}", TWITTER_BASE_URL, tweet_id);
+    if !page::open(driver, &url, TWEET_SELECTOR).await? {
+        return Err(TwitterError::TweetNotFound { tweet_id });
+    }
+
+    let changed = apply(driver, action.name(), &action.toggle()).await?;
+    Ok(TwitterActionResult {
+        success: true,
+        action: action.name().to_string(),
+        target: tweet_id,
+        changed,
+        error: None,
+    })
+}
+
+/// Apply `action` to an account
+///
+/// # Returns
+/// Result whose `changed` is `false` when the action was already applied
+pub async fn twitter_user_action<D: BrowserDriverPort>(
+    action: UserAction,
+    input: TwitterUserActionInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterActionResult, TwitterError> {
+    // Validate input
+    let username = input.username.trim().trim_start_matches('@');
+    if !is_valid_username(username) {
+        return Err(TwitterError::InvalidInput {
+            message: format!("Invalid username format: {}", input.username),
+        });
+    }
+
+    // Check rate limit
+    rate_limiter.acquire().await?;
+
+    // Validate session
+    validate_session(session)?;
+
+    let not_found = || TwitterError::UserNotFound {
+        username: username.to_string(),
+    };
+    let url = format!("{}/{}", TWITTER_BASE_URL, username);
+    let loaded = page::open(driver, &url, PROFILE_SELECTOR).await?;
+    let html = page::html(driver).await?;
+    if let Some(reason) = missing_account(&html) {
+        return Err(missing_account_error(reason, username));
+    }
+    if !loaded {
+        return Err(not_found());
+    }
+    if has_element(&html, EDIT_PROFILE_SELECTOR) {
+        return Err(TwitterError::InvalidInput {
+            message: format!("Cannot {} your own account", action.name()),
+        });
+    }
+
+    let changed = apply(driver, action.name(), &action.toggle(username)).await?;
+    Ok(TwitterActionResult {
+        success: true,
+        action: action.name().to_string(),
+        target: username.to_string(),
+        changed,
+        error: None,
+    })
+}
+
+/// Buttons involved in toggling an action
+struct Toggle {
+    /// Button applying the action
+    button: String,
+    /// Button shown once the action is applied
+    applied: String,
+    /// Menu item or dialog button confirming the action, if any
+    confirm: Option<&'static str>,
+}
+
+impl Toggle {
+    /// Toggle between two action buttons of the focused tweet
+    fn new(button: &str, applied: &str, confirm: Option<&'static str>) -> Self {
+        let focal = |test_id: &str| {
+            format!(
+                "{} button[data-testid=\"{}\"]",
+                FOCAL_TWEET_SELECTOR, test_id
+            )
+        };
+        Self {
+            button: focal(button),
+            applied: focal(applied),
+            confirm,
+        }
+    }
+}
+
+/// Click through `toggle` unless it is already applied
+///
+/// Returns whether anything changed.
+async fn apply<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    action: &str,
+    toggle: &Toggle,
+) -> Result<bool, TwitterError> {
+    let html = page::html(driver).await?;
+    if has_element(&html, &toggle.applied) {
+        debug!(action, "Already applied");
+        return Ok(false);
+    }
+    if !has_element(&html, &toggle.button) {
+        return Err(TwitterError::Unknown {
+            message: format!("The {} button is not available", action),
+        });
+    }
+
+    page::click(driver, &toggle.button).await?;
+    if let Some(confirm) = toggle.confirm {
+        if !page::wait(driver, confirm, TOGGLE_TIMEOUT_MS).await {
+            return Err(TwitterError::Unknown {
+                message: format!("Twitter did not ask to confirm the {}", action),
+            });
+        }
+        page::click(driver, confirm).await?;
+    }
+    if !page::wait(driver, &toggle.applied, TOGGLE_TIMEOUT_MS).await {
+        return Err(TwitterError::Unknown {
+            message: format!("Twitter did not confirm the {}", action),
+        });
+    }
+    Ok(true)
+}
+
+/// Validate the Twitter session
+fn validate_session(session: &TwitterSession) -> Result<(), TwitterError> {
+    if session.cookies.is_empty() || session.csrf_token.is_empty() {
+        return Err(TwitterError::NoSession);
+    }
+    Ok(())
+}
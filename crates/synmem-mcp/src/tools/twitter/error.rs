@@ -17,10 +17,30 @@ pub enum TwitterError {
     #[error("Twitter session has expired. Please log in again.")]
     SessionExpired,
 
+    /// Twitter shows a login wall instead of the page
+    #[error("Twitter is asking to log in. A human needs to log in again.")]
+    LoginRequired,
+
+    /// Twitter detected unusual activity and asks for a verification challenge
+    #[error("Twitter detected unusual activity. A human needs to complete the challenge.")]
+    ChallengeRequired,
+
+    /// The content is behind an age verification gate
+    #[error("This content is age-restricted. A human needs to verify the account's age.")]
+    AgeRestricted,
+
+    /// The account is locked
+    #[error("The Twitter account is locked. A human needs to unlock it.")]
+    AccountLocked,
+
     /// Rate limit exceeded
     #[error("Rate limit exceeded. Please wait {wait_seconds} seconds.")]
     RateLimited { wait_seconds: u64 },
 
+    /// Twitter itself refused the request for exceeding its rate limit
+    #[error("Twitter is rate limiting this account. Please wait {wait_seconds} seconds.")]
+    Throttled { wait_seconds: u64 },
+
     /// Tweet not found
     #[error("Tweet not found: {tweet_id}")]
     TweetNotFound { tweet_id: String },
@@ -29,6 +49,10 @@ pub enum TwitterError {
     #[error("User not found: {username}")]
     UserNotFound { username: String },
 
+    /// The account was suspended by Twitter
+    #[error("User is suspended: {username}")]
+    UserSuspended { username: String },
+
     /// Network error
     #[error("Network error: {message}")]
     NetworkError { message: String },
@@ -51,14 +75,30 @@ impl TwitterError {
     pub fn is_recoverable(&self) -> bool {
         matches!(
             self,
-            TwitterError::RateLimited { .. } | TwitterError::NetworkError { .. }
+            TwitterError::RateLimited { .. }
+                | TwitterError::Throttled { .. }
+                | TwitterError::NetworkError { .. }
+        )
+    }
+
+    /// Check if a human has to act (log in, solve a challenge...) before
+    /// the operation can succeed, so retrying is pointless
+    pub fn needs_human(&self) -> bool {
+        matches!(
+            self,
+            TwitterError::SessionExpired
+                | TwitterError::LoginRequired
+                | TwitterError::ChallengeRequired
+                | TwitterError::AgeRestricted
+                | TwitterError::AccountLocked
         )
     }
 
     /// Get the suggested wait time before retry (in seconds)
     pub fn retry_after(&self) -> Option<u64> {
         match self {
-            TwitterError::RateLimited { wait_seconds } => Some(*wait_seconds),
+            TwitterError::RateLimited { wait_seconds }
+            | TwitterError::Throttled { wait_seconds } => Some(*wait_seconds),
             TwitterError::NetworkError { .. } => Some(5),
             _ => None,
         }
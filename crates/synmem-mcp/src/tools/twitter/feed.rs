@@ -0,0 +1,219 @@
+//! Paginated tweet feeds
+//!
+//! Timelines and search results are infinite-scroll pages. [`collect`]
+//! scrolls one until enough new tweets are found and returns a
+//! [`FeedCursor`] that lets the next call resume where this one stopped.
+//! Most feeds render full tweets; the media tab of a profile renders a
+//! grid of thumbnails (see [`FeedLayout`]).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use synmem_core::ports::outbound::BrowserDriverPort;
+
+use super::page::{self, MEDIA_CELL_SELECTOR, TWEET_SELECTOR};
+use super::parser::{parse_media_grid, parse_tweets};
+use super::{Tweet, TwitterError};
+use crate::tools::Progress;
+
+/// Maximum number of scrolls while filling one page
+const MAX_SCROLLS: usize = 20;
+
+/// Number of returned tweet IDs a cursor remembers for de-duplication
+const MAX_SEEN_IDS: usize = 200;
+
+/// Position in a feed, handed to callers as an opaque string
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedCursor {
+    /// URL of the feed the cursor belongs to
+    pub query: String,
+    /// ID of the last tweet returned
+    pub last_id: String,
+    /// Vertical scroll offset reached, in pixels
+    pub scroll_y: u64,
+    /// IDs of tweets already returned, oldest first
+    #[serde(default)]
+    pub seen: Vec<String>,
+    /// Notifications: IDs returned while catching up with `seen`, which
+    /// are skipped without stopping there, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending: Vec<String>,
+}
+
+impl FeedCursor {
+    /// Encode as an opaque string
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("cursor serializes");
+        BASE64.encode(json)
+    }
+
+    /// Decode a cursor returned for the feed at `query`
+    pub fn decode(cursor: &str, query: &str) -> Result<Self, TwitterError> {
+        let invalid = || TwitterError::InvalidInput {
+            message: "Invalid pagination cursor".to_string(),
+        };
+        let json = BASE64.decode(cursor.trim()).map_err(|_| invalid())?;
+        let cursor: Self = serde_json::from_slice(&json).map_err(|_| invalid())?;
+        if cursor.query != query {
+            return Err(TwitterError::InvalidInput {
+                message: "Cursor belongs to a different query".to_string(),
+            });
+        }
+        Ok(cursor)
+    }
+}
+
+/// How a feed renders its entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FeedLayout {
+    /// Full tweet articles
+    Tweets,
+    /// Thumbnails linking to tweets, as on a profile's media tab
+    MediaGrid,
+}
+
+impl FeedLayout {
+    /// Selector matching one entry
+    fn selector(self) -> &'static str {
+        match self {
+            FeedLayout::Tweets => TWEET_SELECTOR,
+            FeedLayout::MediaGrid => MEDIA_CELL_SELECTOR,
+        }
+    }
+
+    fn parse(self, html: &str) -> Vec<Tweet> {
+        match self {
+            FeedLayout::Tweets => parse_tweets(html),
+            FeedLayout::MediaGrid => parse_media_grid(html),
+        }
+    }
+}
+
+/// One page of a feed
+#[derive(Debug)]
+pub(super) struct FeedPage {
+    pub tweets: Vec<Tweet>,
+    /// `None` once the end of the feed was reached
+    pub next_cursor: Option<String>,
+}
+
+/// Collect up to `count` tweets from the feed at `url`, rendered as `layout`
+///
+/// Resumes from `cursor` when given, skipping every tweet it has already
+/// returned.
+pub(super) async fn collect<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    url: &str,
+    layout: FeedLayout,
+    cursor: Option<FeedCursor>,
+    count: usize,
+    progress: &Progress,
+) -> Result<FeedPage, TwitterError> {
+    let mut seen = Seen::new(cursor.as_ref().map_or(&[][..], |c| &c.seen));
+    let mut last_id = cursor.as_ref().map(|c| c.last_id.clone());
+
+    if !page::open(driver, url, layout.selector()).await? {
+        return Ok(FeedPage {
+            tweets: vec![],
+            next_cursor: None,
+        });
+    }
+    if let Some(cursor) = &cursor {
+        page::scroll_to(driver, cursor.scroll_y).await?;
+    }
+
+    let mut tweets = Vec::new();
+    let mut scrolls = 0;
+    let exhausted = loop {
+        let snapshot = layout.parse(&page::html(driver).await?);
+        let mut found_new = false;
+        for tweet in after(snapshot, last_id.as_deref()) {
+            if tweets.len() == count {
+                break;
+            }
+            if seen.insert(&tweet.id) {
+                found_new = true;
+                tweets.push(tweet);
+            }
+        }
+        progress.report(tweets.len() as u64, Some(count as u64));
+
+        if tweets.len() == count {
+            break false;
+        }
+        if !found_new && scrolls > 0 {
+            break true;
+        }
+        if scrolls == MAX_SCROLLS {
+            break false;
+        }
+        // The anchor only matters until the page has moved past it
+        last_id = None;
+        page::scroll(driver).await?;
+        scrolls += 1;
+    };
+
+    let next_cursor = if exhausted {
+        None
+    } else {
+        let last_id = tweets
+            .last()
+            .map(|tweet| tweet.id.clone())
+            .or_else(|| cursor.map(|c| c.last_id))
+            .unwrap_or_default();
+        let cursor = FeedCursor {
+            query: url.to_string(),
+            last_id,
+            scroll_y: page::scroll_position(driver).await?,
+            seen: seen.into_recent(),
+            pending: vec![],
+        };
+        Some(cursor.encode())
+    };
+
+    Ok(FeedPage {
+        tweets,
+        next_cursor,
+    })
+}
+
+/// Tweets following `last_id` in `snapshot`, or all of them when it is absent
+fn after(snapshot: Vec<Tweet>, last_id: Option<&str>) -> Vec<Tweet> {
+    let anchor = last_id.and_then(|id| snapshot.iter().position(|tweet| tweet.id == id));
+    match anchor {
+        Some(index) => snapshot.into_iter().skip(index + 1).collect(),
+        None => snapshot,
+    }
+}
+
+/// IDs returned so far, in order
+struct Seen {
+    ids: HashSet<String>,
+    order: Vec<String>,
+}
+
+impl Seen {
+    fn new(ids: &[String]) -> Self {
+        Self {
+            ids: ids.iter().cloned().collect(),
+            order: ids.to_vec(),
+        }
+    }
+
+    fn insert(&mut self, id: &str) -> bool {
+        if !self.ids.insert(id.to_string()) {
+            return false;
+        }
+        self.order.push(id.to_string());
+        true
+    }
+
+    /// The most recent IDs, bounded to keep cursors small
+    fn into_recent(mut self) -> Vec<String> {
+        let excess = self.order.len().saturating_sub(MAX_SEEN_IDS);
+        self.order.drain(..excess);
+        self.order
+    }
+}
@@ -0,0 +1,52 @@
+//! Locks shared between processes
+//!
+//! An advisory lock on a file, so that only one process holds it. The OS
+//! releases the lock when its holder exits, so a crashed process cannot
+//! leave it behind. The lock is polled rather than waited on, so waiting on
+//! another process does not hold up the runtime.
+
+use std::fs::{File, TryLockError};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Delay between attempts to take a lock
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Lock on a file, released on drop
+pub(super) struct FileLock {
+    /// Holds the lock until it is closed
+    _file: File,
+}
+
+impl FileLock {
+    /// Lock `path`, waiting up to `timeout` for another holder to release
+    /// it
+    ///
+    /// Returns `None` when it is still held once `timeout` has passed. The
+    /// file and its parents are created as needed.
+    pub(super) async fn acquire(path: &Path, timeout: Duration) -> std::io::Result<Option<Self>> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .await?
+            .into_std()
+            .await;
+
+        let started = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Some(Self { _file: file })),
+                Err(TryLockError::WouldBlock) if started.elapsed() < timeout => {
+                    tokio::time::sleep(RETRY_INTERVAL).await;
+                }
+                Err(TryLockError::WouldBlock) => return Ok(None),
+                Err(TryLockError::Error(e)) => return Err(e),
+            }
+        }
+    }
+}
@@ -0,0 +1,989 @@
+//! MCP adapters for the Twitter tools
+//!
+//! Wraps the tool functions in [`Tool`] implementations and registers them
+//! into a [`ToolRegistry`].
+
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use super::{
+    archive_media, retry, run_scheduler, twitter_cancel_scheduled, twitter_get_notifications,
+    twitter_get_profile, twitter_get_timeline, twitter_list_conversations, twitter_list_scheduled,
+    twitter_post, twitter_post_thread, twitter_read_dm, twitter_read_thread,
+    twitter_reschedule_post, twitter_schedule_post, twitter_search, twitter_send_dm,
+    twitter_tweet_action, twitter_user_action, MediaStore, PostQueue, RateLimiter, RateLimitConfig,
+    TweetAction, TwitterCancelScheduledInput, TwitterError, TwitterGetNotificationsInput,
+    TwitterGetProfileInput, TwitterGetTimelineInput, TwitterListConversationsInput,
+    TwitterListScheduledInput, TwitterPostInput, TwitterPostThreadInput, TwitterReadDmInput,
+    TwitterReadThreadInput, TwitterReschedulePostInput, TwitterSchedulePostInput,
+    TwitterSearchInput, TwitterSendDmInput, TwitterSession, TwitterTweetActionInput,
+    TwitterUserActionInput, UserAction,
+};
+use crate::config::RetryPolicy;
+use crate::protocol::{CallToolResult, Content};
+use crate::schema::InputSchema;
+use crate::tools::{
+    decode_arguments, LeasedDriver, RequestContext, Tool, ToolContext, ToolRegistry,
+};
+
+/// Rate limiter key for posting
+pub const POST_LIMITER: &str = "twitter.post";
+/// Rate limiter key for thread reading
+pub const READ_LIMITER: &str = "twitter.read";
+/// Rate limiter key for searching
+pub const SEARCH_LIMITER: &str = "twitter.search";
+/// Rate limiter key for timeline reading
+pub const TIMELINE_LIMITER: &str = "twitter.timeline";
+/// Rate limiter key for profile lookups
+pub const PROFILE_LIMITER: &str = "twitter.profile";
+/// Rate limiter key for reading notifications
+pub const NOTIFICATIONS_LIMITER: &str = "twitter.notifications";
+/// Rate limiter key for liking and unliking
+pub const LIKE_LIMITER: &str = "twitter.like";
+/// Rate limiter key for retweeting
+pub const RETWEET_LIMITER: &str = "twitter.retweet";
+/// Rate limiter key for bookmarking
+pub const BOOKMARK_LIMITER: &str = "twitter.bookmark";
+/// Rate limiter key for following and unfollowing
+pub const FOLLOW_LIMITER: &str = "twitter.follow";
+/// Rate limiter key for direct messages
+pub const DM_LIMITER: &str = "twitter.dm";
+/// Rate limiter key for the budget shared by all the tools of an account
+pub const ACCOUNT_LIMITER: &str = "twitter.account";
+
+/// Register all Twitter tools acting with `session`
+pub fn register_tools<D: BrowserDriverPort>(registry: &mut ToolRegistry<D>, session: TwitterSession) {
+    let session = Arc::new(session);
+    registry.register(TwitterPostTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterPostThreadTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterSchedulePostTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterListScheduledTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterReschedulePostTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterCancelScheduledTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterReadThreadTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterSearchTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterGetTimelineTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterGetProfileTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterGetNotificationsTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterListConversationsTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterReadDmTool {
+        session: session.clone(),
+    });
+    registry.register(TwitterSendDmTool {
+        session: session.clone(),
+    });
+    for action in TweetAction::ALL {
+        registry.register(TwitterTweetActionTool {
+            session: session.clone(),
+            action,
+        });
+    }
+    for action in UserAction::ALL {
+        registry.register(TwitterUserActionTool {
+            session: session.clone(),
+            action,
+        });
+    }
+}
+
+/// Start publishing the tweets scheduled for `session` in the background
+///
+/// The worker shares the post rate limiter and the browser lease of the MCP
+/// tools. It runs until the returned task is aborted.
+pub fn spawn_scheduler<D: BrowserDriverPort + 'static>(
+    context: &ToolContext<D>,
+    session: TwitterSession,
+) -> JoinHandle<()> {
+    let limiter = account_limiter(context, &session, POST_LIMITER, RateLimitConfig::for_post());
+    let queue = post_queue(context, &session);
+    tokio::spawn(run_scheduler(
+        queue,
+        Arc::new(session),
+        limiter,
+        context.driver.clone(),
+        context.browser_lease.clone(),
+    ))
+}
+
+/// Rate limiter for `operation` on the session's account
+///
+/// Limiters are keyed by account, and every operation also draws from the
+/// account's overall budget.
+fn account_limiter<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    session: &TwitterSession,
+    operation: &str,
+    config: RateLimitConfig,
+) -> Arc<RateLimiter> {
+    let account = session.account_key();
+    let budget = context.rate_limiters.get_or_create(
+        &format!("{}/{}", account, ACCOUNT_LIMITER),
+        RateLimitConfig::for_account(),
+    );
+    context.rate_limiters.get_or_create_within(
+        &format!("{}/{}", account, operation),
+        config,
+        &budget,
+    )
+}
+
+/// Queue of the tweets scheduled for the session's account
+fn post_queue<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    session: &TwitterSession,
+) -> PostQueue {
+    let queue = PostQueue::new(context.storage.clone(), &session.account_key());
+    match &context.lock_dir {
+        Some(root) => queue.with_lock_dir(root),
+        None => queue,
+    }
+}
+
+/// Run a Twitter operation, retrying it according to `policy`, and
+/// convert its result into MCP content
+///
+/// Every attempt Twitter throttles makes `limiter` back off. Each attempt
+/// gives its turn at `browser` back when it ends, so other calls can use the
+/// page while this one backs off. When attempts were retried, their history
+/// follows the result as a second item.
+async fn run<D, T, F, Fut>(
+    limiter: &RateLimiter,
+    policy: RetryPolicy,
+    browser: &LeasedDriver<D>,
+    operation: F,
+) -> CallToolResult
+where
+    D: BrowserDriverPort,
+    T: Serialize,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, TwitterError>>,
+{
+    let operation = &operation;
+    let outcome = retry(&policy, move || async move {
+        let result = operation().await;
+        browser.release().await;
+        if let Err(TwitterError::Throttled { .. }) = &result {
+            if let Err(e) = limiter.back_off().await {
+                warn!(error = %e, "Failed to back off the rate limiter");
+            }
+        }
+        result
+    })
+    .await;
+
+    let mut result = tool_result(outcome.result);
+    if !outcome.attempts.is_empty() {
+        let history = serde_json::json!({ "attempts": outcome.attempts });
+        result.content.push(Content::text(history.to_string()));
+    }
+    result
+}
+
+/// Media store for a call, `None` unless its input asked for downloads
+///
+/// Fails when downloads were asked for but no store is configured.
+fn media_store<D: BrowserDriverPort>(
+    context: &ToolContext<D>,
+    download_media: bool,
+) -> Result<Option<&MediaStore>, CallToolResult> {
+    if !download_media {
+        return Ok(None);
+    }
+    match &context.media_store {
+        Some(store) => Ok(Some(store)),
+        None => Err(tool_result::<()>(Err(TwitterError::InvalidInput {
+            message: "Media downloads are not configured".to_string(),
+        }))),
+    }
+}
+
+/// Convert a tool result into MCP content
+fn tool_result<T: Serialize>(result: Result<T, TwitterError>) -> CallToolResult {
+    match result {
+        Ok(value) => CallToolResult::json(&value),
+        Err(e) => {
+            warn!(error = %e, "Twitter tool failed");
+            CallToolResult::error(e.to_string())
+        }
+    }
+}
+
+/// `twitter_post` tool
+struct TwitterPostTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterPostTool {
+    fn name(&self) -> &str {
+        "twitter_post"
+    }
+
+    fn description(&self) -> &str {
+        "Post a tweet, optionally with media or as a reply"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterPostInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterPostInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, POST_LIMITER, RateLimitConfig::for_post());
+        let policy = context
+            .retry_policies
+            .exact_policy_for(Tool::<D>::name(self))
+            .unwrap_or_else(RetryPolicy::single_attempt);
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_post(input.clone(), &self.session, &limiter, &browser)
+        })
+        .await
+    }
+}
+
+/// `twitter_post_thread` tool
+struct TwitterPostThreadTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterPostThreadTool {
+    fn name(&self) -> &str {
+        "twitter_post_thread"
+    }
+
+    fn description(&self) -> &str {
+        "Post a thread of tweets as a reply chain"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterPostThreadInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterPostThreadInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, POST_LIMITER, RateLimitConfig::for_post());
+        let policy = context
+            .retry_policies
+            .exact_policy_for(Tool::<D>::name(self))
+            .unwrap_or_else(RetryPolicy::single_attempt);
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_post_thread(
+                input.clone(),
+                &self.session,
+                &limiter,
+                &browser,
+                request.progress(),
+            )
+        })
+        .await
+    }
+}
+
+/// `twitter_schedule_post` tool
+struct TwitterSchedulePostTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterSchedulePostTool {
+    fn name(&self) -> &str {
+        "twitter_schedule_post"
+    }
+
+    fn description(&self) -> &str {
+        "Schedule a tweet to be posted at a later time"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterSchedulePostInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        _request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterSchedulePostInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(twitter_schedule_post(input, &post_queue(context, &self.session)).await)
+    }
+}
+
+/// `twitter_list_scheduled` tool
+struct TwitterListScheduledTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterListScheduledTool {
+    fn name(&self) -> &str {
+        "twitter_list_scheduled"
+    }
+
+    fn description(&self) -> &str {
+        "List the scheduled tweets and their status, by publish time"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterListScheduledInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        _request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterListScheduledInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(twitter_list_scheduled(input, &post_queue(context, &self.session)).await)
+    }
+}
+
+/// `twitter_reschedule_post` tool
+struct TwitterReschedulePostTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterReschedulePostTool {
+    fn name(&self) -> &str {
+        "twitter_reschedule_post"
+    }
+
+    fn description(&self) -> &str {
+        "Move a pending scheduled tweet to another time"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterReschedulePostInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        _request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterReschedulePostInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(twitter_reschedule_post(input, &post_queue(context, &self.session)).await)
+    }
+}
+
+/// `twitter_cancel_scheduled` tool
+struct TwitterCancelScheduledTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterCancelScheduledTool {
+    fn name(&self) -> &str {
+        "twitter_cancel_scheduled"
+    }
+
+    fn description(&self) -> &str {
+        "Cancel a pending scheduled tweet"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterCancelScheduledInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        _request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterCancelScheduledInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        tool_result(twitter_cancel_scheduled(input, &post_queue(context, &self.session)).await)
+    }
+}
+
+/// `twitter_read_thread` tool
+struct TwitterReadThreadTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterReadThreadTool {
+    fn name(&self) -> &str {
+        "twitter_read_thread"
+    }
+
+    fn description(&self) -> &str {
+        "Read all tweets in a Twitter thread"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterReadThreadInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterReadThreadInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, READ_LIMITER, RateLimitConfig::for_read());
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let store = match media_store(context, input.download_media) {
+            Ok(store) => store,
+            Err(result) => return result,
+        };
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || async {
+            let mut result = twitter_read_thread(
+                input.clone(),
+                &self.session,
+                &limiter,
+                &browser,
+                request.progress(),
+            )
+            .await?;
+            // Downloads do not need the page
+            browser.release().await;
+            if let Some(store) = store {
+                archive_media(&mut result.tweets, store).await;
+            }
+            Ok(result)
+        })
+        .await
+    }
+}
+
+/// `twitter_search` tool
+struct TwitterSearchTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterSearchTool {
+    fn name(&self) -> &str {
+        "twitter_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search for tweets matching a query"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterSearchInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterSearchInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, SEARCH_LIMITER, RateLimitConfig::for_search());
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let store = match media_store(context, input.download_media) {
+            Ok(store) => store,
+            Err(result) => return result,
+        };
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || async {
+            let mut result = twitter_search(
+                input.clone(),
+                &self.session,
+                &limiter,
+                &browser,
+                request.progress(),
+            )
+            .await?;
+            // Downloads do not need the page
+            browser.release().await;
+            if let Some(store) = store {
+                archive_media(&mut result.tweets, store).await;
+            }
+            Ok(result)
+        })
+        .await
+    }
+}
+
+/// `twitter_get_timeline` tool
+struct TwitterGetTimelineTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterGetTimelineTool {
+    fn name(&self) -> &str {
+        "twitter_get_timeline"
+    }
+
+    fn description(&self) -> &str {
+        "Get the home, following, user, list, bookmarks, likes, replies or media timeline"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterGetTimelineInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterGetTimelineInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, TIMELINE_LIMITER, RateLimitConfig::for_read());
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let store = match media_store(context, input.download_media) {
+            Ok(store) => store,
+            Err(result) => return result,
+        };
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || async {
+            let mut result = twitter_get_timeline(
+                input.clone(),
+                &self.session,
+                &limiter,
+                &browser,
+                request.progress(),
+            )
+            .await?;
+            // Downloads do not need the page
+            browser.release().await;
+            if let Some(store) = store {
+                archive_media(&mut result.tweets, store).await;
+            }
+            Ok(result)
+        })
+        .await
+    }
+}
+
+/// `twitter_get_profile` tool
+struct TwitterGetProfileTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterGetProfileTool {
+    fn name(&self) -> &str {
+        "twitter_get_profile"
+    }
+
+    fn description(&self) -> &str {
+        "Get the public profile of a Twitter account"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterGetProfileInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterGetProfileInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, PROFILE_LIMITER, RateLimitConfig::for_read());
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_get_profile(input.clone(), &self.session, &limiter, &browser)
+        })
+        .await
+    }
+}
+
+/// `twitter_get_notifications` tool
+struct TwitterGetNotificationsTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterGetNotificationsTool {
+    fn name(&self) -> &str {
+        "twitter_get_notifications"
+    }
+
+    fn description(&self) -> &str {
+        "Get notifications (mentions, replies, likes, follows, retweets), optionally only new ones"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterGetNotificationsInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterGetNotificationsInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter = account_limiter(
+            context,
+            &self.session,
+            NOTIFICATIONS_LIMITER,
+            RateLimitConfig::for_read(),
+        );
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_get_notifications(
+                input.clone(),
+                &self.session,
+                &limiter,
+                &browser,
+                request.progress(),
+            )
+        })
+        .await
+    }
+}
+
+/// `twitter_list_conversations` tool
+struct TwitterListConversationsTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterListConversationsTool {
+    fn name(&self) -> &str {
+        "twitter_list_conversations"
+    }
+
+    fn description(&self) -> &str {
+        "List direct message conversations, most recent first"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterListConversationsInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterListConversationsInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, DM_LIMITER, RateLimitConfig::for_dm());
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_list_conversations(input.clone(), &self.session, &limiter, &browser)
+        })
+        .await
+    }
+}
+
+/// `twitter_read_dm` tool
+struct TwitterReadDmTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterReadDmTool {
+    fn name(&self) -> &str {
+        "twitter_read_dm"
+    }
+
+    fn description(&self) -> &str {
+        "Read the most recent messages of a direct message conversation"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterReadDmInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterReadDmInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, DM_LIMITER, RateLimitConfig::for_dm());
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_read_dm(input.clone(), &self.session, &limiter, &browser)
+        })
+        .await
+    }
+}
+
+/// `twitter_send_dm` tool
+struct TwitterSendDmTool {
+    session: Arc<TwitterSession>,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterSendDmTool {
+    fn name(&self) -> &str {
+        "twitter_send_dm"
+    }
+
+    fn description(&self) -> &str {
+        "Send a direct message to a conversation or an account"
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterSendDmInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterSendDmInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, DM_LIMITER, RateLimitConfig::for_dm());
+        let policy = context
+            .retry_policies
+            .exact_policy_for(Tool::<D>::name(self))
+            .unwrap_or_else(RetryPolicy::single_attempt);
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_send_dm(input.clone(), &self.session, &limiter, &browser)
+        })
+        .await
+    }
+}
+
+/// `twitter_like`, `twitter_unlike`, `twitter_retweet` and `twitter_bookmark`
+struct TwitterTweetActionTool {
+    session: Arc<TwitterSession>,
+    action: TweetAction,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterTweetActionTool {
+    fn name(&self) -> &str {
+        match self.action {
+            TweetAction::Like => "twitter_like",
+            TweetAction::Unlike => "twitter_unlike",
+            TweetAction::Retweet => "twitter_retweet",
+            TweetAction::Bookmark => "twitter_bookmark",
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self.action {
+            TweetAction::Like => "Like a tweet (no-op if already liked)",
+            TweetAction::Unlike => "Remove a like from a tweet (no-op if not liked)",
+            TweetAction::Retweet => "Retweet a tweet (no-op if already retweeted)",
+            TweetAction::Bookmark => "Bookmark a tweet (no-op if already bookmarked)",
+        }
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterTweetActionInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterTweetActionInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let (key, config) = match self.action {
+            TweetAction::Like | TweetAction::Unlike => (LIKE_LIMITER, RateLimitConfig::for_like()),
+            TweetAction::Retweet => (RETWEET_LIMITER, RateLimitConfig::for_retweet()),
+            TweetAction::Bookmark => (BOOKMARK_LIMITER, RateLimitConfig::for_bookmark()),
+        };
+        let limiter = account_limiter(context, &self.session, key, config);
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_tweet_action(
+                self.action,
+                input.clone(),
+                &self.session,
+                &limiter,
+                &browser,
+            )
+        })
+        .await
+    }
+}
+
+/// `twitter_follow` and `twitter_unfollow`
+struct TwitterUserActionTool {
+    session: Arc<TwitterSession>,
+    action: UserAction,
+}
+
+#[async_trait]
+impl<D: BrowserDriverPort> Tool<D> for TwitterUserActionTool {
+    fn name(&self) -> &str {
+        match self.action {
+            UserAction::Follow => "twitter_follow",
+            UserAction::Unfollow => "twitter_unfollow",
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self.action {
+            UserAction::Follow => "Follow an account (no-op if already following)",
+            UserAction::Unfollow => "Unfollow an account (no-op if not following)",
+        }
+    }
+
+    fn input_schema(&self) -> Value {
+        TwitterUserActionInput::input_schema()
+    }
+
+    async fn call(
+        &self,
+        arguments: Value,
+        context: &ToolContext<D>,
+        request: &RequestContext,
+    ) -> CallToolResult {
+        let input: TwitterUserActionInput = match decode_arguments(arguments) {
+            Ok(input) => input,
+            Err(result) => return result,
+        };
+        let limiter =
+            account_limiter(context, &self.session, FOLLOW_LIMITER, RateLimitConfig::for_follow());
+        let policy = context
+            .retry_policies
+            .policy_for(Tool::<D>::name(self))
+            .unwrap_or_default();
+        let browser = context.browser(request);
+        run(&limiter, policy, &browser, || {
+            twitter_user_action(
+                self.action,
+                input.clone(),
+                &self.session,
+                &limiter,
+                &browser,
+            )
+        })
+        .await
+    }
+}
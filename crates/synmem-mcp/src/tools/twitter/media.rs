@@ -0,0 +1,315 @@
+//! Media attachments for posts
+//!
+//! The compose box only accepts local files, so every entry of
+//! `media_urls` is written to a temporary directory first: http(s) URLs are
+//! downloaded and `data:` URLs decoded.
+//!
+//! Downloads only connect to public addresses, redirects included, so that
+//! a media URL cannot make the server reach its own network.
+
+use std::error::Error as _;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use tempfile::TempDir;
+use tracing::debug;
+
+use super::TwitterError;
+
+/// Largest attachment accepted (X's limit for videos)
+const MAX_MEDIA_BYTES: usize = 512 * 1024 * 1024;
+
+/// How long a single download may take
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Most redirects followed by a download
+const MAX_REDIRECTS: usize = 10;
+
+/// Attachments written to disk, removed when dropped
+#[derive(Debug)]
+pub(super) struct MediaFiles {
+    _dir: TempDir,
+    /// One file per media URL, in order
+    pub(super) paths: Vec<PathBuf>,
+}
+
+/// Write every media URL to a temporary file
+pub(super) async fn prepare(urls: &[String]) -> Result<MediaFiles, TwitterError> {
+    let dir = tempfile::Builder::new()
+        .prefix("synmem-media-")
+        .tempdir()
+        .map_err(|e| TwitterError::Unknown {
+            message: format!("Failed to create a directory for media: {}", e),
+        })?;
+
+    let mut paths = Vec::with_capacity(urls.len());
+    for (index, url) in urls.iter().enumerate() {
+        let (bytes, mime) = if is_data_url(url) {
+            decode_data_url(url)?
+        } else {
+            download(url).await?
+        };
+        if bytes.len() > MAX_MEDIA_BYTES {
+            return Err(too_large(url));
+        }
+
+        let path = dir
+            .path()
+            .join(format!("media-{}.{}", index, extension(&mime, url)));
+        tokio::fs::write(&path, &bytes)
+            .await
+            .map_err(|e| TwitterError::Unknown {
+                message: format!("Failed to write {}: {}", path.display(), e),
+            })?;
+        debug!(path = %path.display(), size = bytes.len(), "Prepared media");
+        paths.push(path);
+    }
+
+    Ok(MediaFiles { _dir: dir, paths })
+}
+
+/// Decode a `data:[<mime>][;base64],<data>` URL into its bytes and MIME type
+pub(super) fn decode_data_url(url: &str) -> Result<(Vec<u8>, String), TwitterError> {
+    let invalid = || TwitterError::InvalidInput {
+        message: "Invalid data URL".to_string(),
+    };
+    if !is_data_url(url) {
+        return Err(invalid());
+    }
+    let (meta, data) = url["data:".len()..].split_once(',').ok_or_else(invalid)?;
+    let (mime, is_base64) = match meta.strip_suffix(";base64") {
+        Some(mime) => (mime, true),
+        None => (meta, false),
+    };
+    let mime = mime.split(';').next().unwrap_or_default().trim();
+
+    let bytes = if is_base64 {
+        let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+        BASE64.decode(data).map_err(|_| invalid())?
+    } else {
+        percent_decode(data).ok_or_else(invalid)?
+    };
+    Ok((bytes, mime.to_ascii_lowercase()))
+}
+
+pub(super) async fn download(url: &str) -> Result<(Vec<u8>, String), TwitterError> {
+    download_at_most(url, MAX_MEDIA_BYTES, is_public).await
+}
+
+/// Download `url`, giving up as soon as it exceeds `max_bytes`
+///
+/// Only addresses `allowed` accepts are connected to, whether `url` names
+/// them or a redirect does. The body is streamed, so a response without
+/// `Content-Length` cannot make it buffer more than the limit.
+pub(super) async fn download_at_most(
+    url: &str,
+    max_bytes: usize,
+    allowed: fn(IpAddr) -> bool,
+) -> Result<(Vec<u8>, String), TwitterError> {
+    let failed = |e: reqwest::Error| {
+        // The cause (e.g. a refused address) is only in the sources
+        let mut message = format!("Failed to download {}: {}", url, e);
+        let mut source = e.source();
+        while let Some(cause) = source {
+            message.push_str(&format!(": {}", cause));
+            source = cause.source();
+        }
+        TwitterError::NetworkError { message }
+    };
+    let parsed = reqwest::Url::parse(url).map_err(|_| TwitterError::InvalidInput {
+        message: format!("Invalid media URL: {}", url),
+    })?;
+    check_address(&parsed, allowed).map_err(|message| TwitterError::InvalidInput {
+        message: format!("Media {} {}", url, message),
+    })?;
+
+    let client = reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        // A proxy would resolve the host itself, past the check
+        .no_proxy()
+        .dns_resolver(Arc::new(Resolver { allowed }))
+        .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error("too many redirects");
+            }
+            match check_address(attempt.url(), allowed) {
+                Ok(()) => attempt.follow(),
+                Err(message) => attempt.error(format!("redirect {}", message)),
+            }
+        }))
+        .build()
+        .map_err(failed)?;
+    let mut response = client
+        .get(parsed)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(failed)?;
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        return Err(too_large(url));
+    }
+
+    let mime = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(failed)? {
+        if bytes.len() + chunk.len() > max_bytes {
+            return Err(too_large(url));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok((bytes, mime))
+}
+
+/// Check the address in `url`, when it names one rather than a host
+///
+/// Host names are checked once resolved, by [`Resolver`].
+fn check_address(url: &reqwest::Url, allowed: fn(IpAddr) -> bool) -> Result<(), String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("uses unsupported scheme {}", url.scheme()));
+    }
+    let host = url.host_str().ok_or("has no host")?;
+    let literal = host.trim_start_matches('[').trim_end_matches(']');
+    match literal.parse::<IpAddr>() {
+        Ok(ip) if !allowed(ip) => Err(format!("points to {}, which is not a public address", ip)),
+        _ => Ok(()),
+    }
+}
+
+/// Resolves host names to the addresses a download may connect to
+struct Resolver {
+    allowed: fn(IpAddr) -> bool,
+}
+
+impl reqwest::dns::Resolve for Resolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let allowed = self.allowed;
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+                .await?
+                .filter(|addr| allowed(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// Whether `ip` is reachable on the public internet
+///
+/// Loopback, private, link-local, shared, documentation, benchmarking,
+/// multicast and reserved ranges are not.
+pub(super) fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // Shared address space (carrier-grade NAT)
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking
+        || (a == 198 && (b == 18 || b == 19))
+        // Reserved
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let [first, second, ..] = ip.segments();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local
+        || (first & 0xfe00) == 0xfc00
+        // Link-local
+        || (first & 0xffc0) == 0xfe80
+        // Documentation
+        || (first == 0x2001 && second == 0x0db8)
+        // IPv4-compatible and other reserved addresses
+        || first == 0)
+}
+
+/// File extension for a MIME type, falling back to the URL's own extension
+pub(super) fn extension(mime: &str, url: &str) -> String {
+    let known = match mime {
+        "image/jpeg" | "image/jpg" => Some("jpg"),
+        "image/png" => Some("png"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        "video/mp4" => Some("mp4"),
+        "video/quicktime" => Some("mov"),
+        _ => None,
+    };
+    if let Some(known) = known {
+        return known.to_string();
+    }
+
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .filter(|ext| ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| "bin".to_string())
+}
+
+pub(super) fn is_data_url(url: &str) -> bool {
+    url.get(..5)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+}
+
+fn percent_decode(data: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(data.len());
+    let mut input = data.bytes();
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            let high = (input.next()? as char).to_digit(16)?;
+            let low = (input.next()? as char).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    Some(bytes)
+}
+
+fn too_large(url: &str) -> TwitterError {
+    let name = if is_data_url(url) { "data URL" } else { url };
+    TwitterError::InvalidInput {
+        message: format!(
+            "Media {} exceeds the {} MB limit",
+            name,
+            MAX_MEDIA_BYTES / (1024 * 1024)
+        ),
+    }
+}
@@ -0,0 +1,128 @@
+//! Content-addressed store of downloaded tweet media
+//!
+//! Files are named after the SHA-256 of their content, under
+//! `<root>/objects/<first two hex digits>/<hash>.<ext>`, so the same photo
+//! posted by many tweets is kept once. Each source URL is also recorded in
+//! `<root>/urls/<hash of the URL>.json`, which lets later calls skip the
+//! download altogether.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use ring::digest::{digest, SHA256};
+use tracing::debug;
+
+use super::media::extension;
+use super::{LocalMedia, TwitterError};
+
+/// MIME type of files whose type is unknown
+const UNKNOWN_MIME: &str = "application/octet-stream";
+
+/// Directory of downloaded media
+#[derive(Debug, Clone)]
+pub struct MediaStore {
+    root: PathBuf,
+}
+
+impl MediaStore {
+    /// Create a store under `root` (created on first write)
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Directory holding the store
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The file already downloaded from `url`, if it is still there
+    pub async fn lookup(&self, url: &str) -> Option<LocalMedia> {
+        let content = tokio::fs::read(self.url_path(url)).await.ok()?;
+        let media: LocalMedia = serde_json::from_slice(&content).ok()?;
+        let metadata = tokio::fs::metadata(&media.path).await.ok()?;
+        (metadata.len() == media.size).then_some(media)
+    }
+
+    /// Store `bytes` downloaded from `url`
+    ///
+    /// An empty `mime` is guessed from the content. Content already in the
+    /// store is not written again.
+    pub async fn put(
+        &self,
+        url: &str,
+        bytes: &[u8],
+        mime: &str,
+    ) -> Result<LocalMedia, TwitterError> {
+        let sha256 = sha256_hex(bytes);
+        let mime = if mime.is_empty() {
+            sniff_mime(bytes).unwrap_or(UNKNOWN_MIME)
+        } else {
+            mime
+        };
+        let name = format!("{}.{}", sha256, extension(mime, url));
+        let path = self.root.join("objects").join(&sha256[..2]).join(name);
+
+        let exists = tokio::fs::metadata(&path)
+            .await
+            .is_ok_and(|metadata| metadata.len() == bytes.len() as u64);
+        if exists {
+            debug!(path = %path.display(), "Media already stored");
+        } else {
+            write_atomically(&path, bytes).await?;
+            debug!(path = %path.display(), size = bytes.len(), "Stored media");
+        }
+
+        let media = LocalMedia {
+            path,
+            size: bytes.len() as u64,
+            mime_type: mime.to_string(),
+            sha256,
+            source_url: url.to_string(),
+        };
+        let record = serde_json::to_vec(&media).expect("media serializes");
+        write_atomically(&self.url_path(url), &record).await?;
+        Ok(media)
+    }
+
+    fn url_path(&self, url: &str) -> PathBuf {
+        self.root
+            .join("urls")
+            .join(format!("{}.json", sha256_hex(url.as_bytes())))
+    }
+}
+
+/// Write `bytes` to `path` through a sibling file, so that readers never
+/// see a partial file
+async fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), TwitterError> {
+    let failed = |e: std::io::Error| TwitterError::Unknown {
+        message: format!("Failed to write {}: {}", path.display(), e),
+    };
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(failed)?;
+    }
+    let temp = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4().simple()));
+    tokio::fs::write(&temp, bytes).await.map_err(failed)?;
+    tokio::fs::rename(&temp, path).await.map_err(failed)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    digest(&SHA256, bytes)
+        .as_ref()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+/// MIME type of the media formats X serves, from their magic numbers
+fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0xff, 0xd8, 0xff, ..] => Some("image/jpeg"),
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [b'G', b'I', b'F', b'8', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => Some("video/mp4"),
+        _ => None,
+    }
+}
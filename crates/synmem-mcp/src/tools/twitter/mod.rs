@@ -1,27 +1,85 @@
 //! Twitter/X Automation Tools
 //!
 //! This module provides tools for Twitter/X automation including:
-//! - Posting tweets
+//! - Posting tweets and threads
+//! - Liking, retweeting and bookmarking tweets, following accounts
 //! - Reading threads
 //! - Searching tweets
 //! - Getting timelines
+//! - Looking up profiles
+//! - Reading notifications and mentions
+//! - Reading and sending direct messages
+//! - Archiving the media of the tweets read in a local media store
+//! - Scheduling tweets, posted later by a background worker
 //!
 //! All tools require a valid Twitter session and implement rate limiting
-//! to avoid account suspension.
+//! to avoid account suspension. When X shows a login wall, a challenge, an
+//! age gate or a locked account page instead of the requested one, tools
+//! fail with an error for which [`TwitterError::needs_human`] is true.
+//! When X reports a rate limit, they fail with [`TwitterError::Throttled`]
+//! and the MCP tools make their rate limiters back off. The MCP tools also
+//! retry recoverable errors, as set by the `tools.retry` configuration;
+//! tools that post are tried once unless an entry names them, as patterns
+//! do not apply to them.
 
+mod archive;
+mod dm;
+mod engage;
 mod error;
+mod feed;
+mod file_lock;
+mod mcp;
+mod media;
+mod media_store;
+mod notifications;
+mod page;
+mod parser;
 mod post;
+mod post_thread;
+mod profile;
+mod rate_limit_store;
 mod rate_limiter;
 mod read_thread;
+mod retry;
+mod schedule;
+mod schema;
 mod search;
+mod session;
 mod timeline;
 mod types;
 
+pub use archive::archive_media;
+pub use dm::{
+    create_dm_rate_limiter, twitter_list_conversations, twitter_read_dm, twitter_send_dm,
+};
+pub use engage::{
+    twitter_bookmark, twitter_follow, twitter_like, twitter_retweet, twitter_tweet_action,
+    twitter_unfollow, twitter_unlike, twitter_user_action, TweetAction, UserAction,
+};
 pub use error::TwitterError;
+pub use feed::FeedCursor;
+pub use mcp::{
+    register_tools, spawn_scheduler, ACCOUNT_LIMITER, BOOKMARK_LIMITER, DM_LIMITER, FOLLOW_LIMITER,
+    LIKE_LIMITER, NOTIFICATIONS_LIMITER, POST_LIMITER, PROFILE_LIMITER, READ_LIMITER,
+    RETWEET_LIMITER, SEARCH_LIMITER, TIMELINE_LIMITER,
+};
+pub use media_store::MediaStore;
+pub use notifications::{create_notifications_rate_limiter, twitter_get_notifications};
+pub use parser::{
+    parse_conversations, parse_count, parse_messages, parse_notifications, parse_tweets,
+};
 pub use post::{create_post_rate_limiter, twitter_post};
-pub use rate_limiter::{RateLimitConfig, RateLimiter};
+pub use post_thread::{split_thread, twitter_post_thread};
+pub use profile::{create_profile_rate_limiter, twitter_get_profile};
+pub use rate_limit_store::{RateLimitState, RateLimitStore};
+pub use rate_limiter::{RateLimitConfig, RateLimitMode, RateLimiter};
 pub use read_thread::{create_read_rate_limiter, twitter_read_thread};
-pub use search::{create_search_rate_limiter, twitter_search};
+pub use retry::{retry, RetryAttempt, RetryOutcome};
+pub use schedule::{
+    publish_due, run_scheduler, twitter_cancel_scheduled, twitter_list_scheduled,
+    twitter_reschedule_post, twitter_schedule_post, PostQueue,
+};
+pub use search::{build_search_query, create_search_rate_limiter, twitter_search};
 pub use timeline::{create_timeline_rate_limiter, twitter_get_timeline};
 pub use types::*;
 
@@ -0,0 +1,205 @@
+//! Twitter notifications tool
+//!
+//! Read the notifications or mentions tab, optionally only what arrived
+//! since the previous poll.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+
+use super::feed::FeedCursor;
+use super::page::{self, NOTIFICATION_SELECTOR, TWITTER_BASE_URL};
+use super::parser::parse_notifications;
+use super::{
+    NotificationTab, RateLimiter, RateLimitConfig, TwitterError, TwitterGetNotificationsInput,
+    TwitterGetNotificationsResult, TwitterNotification, TwitterSession, MAX_RESULTS_PER_REQUEST,
+};
+use crate::tools::Progress;
+
+/// Maximum number of scrolls while collecting notifications
+const MAX_SCROLLS: usize = 10;
+
+/// Number of notification IDs a `since` cursor remembers
+const MAX_SINCE_IDS: usize = 200;
+
+/// Get Twitter notifications
+///
+/// Notifications are returned newest first. With `since`, reading stops
+/// at the first notification already returned by an earlier call. If more
+/// than `count` arrived in between, the returned cursor keeps that
+/// boundary, so the next calls return the rest before moving past it.
+///
+/// # Arguments
+/// * `input` - The tab, count and `since` cursor
+/// * `session` - Valid Twitter session
+/// * `rate_limiter` - Rate limiter to prevent API abuse
+/// * `driver` - Browser driver used to load the notifications
+/// * `progress` - Receives the number of notifications collected out of `count`
+///
+/// # Returns
+/// Result containing the notifications and the `since` cursor for the
+/// next poll
+///
+/// # Example
+/// ```ignore
+/// let mut since = None;
+/// loop {
+///     let input = TwitterGetNotificationsInput {
+///         tab: NotificationTab::Mentions,
+///         count: 20,
+///         since: since.take(),
+///     };
+///     let result =
+///         twitter_get_notifications(input, &session, &limiter, &driver, &Progress::none()).await?;
+///     for notification in result.notifications {
+///         println!("{:?} from {:?}", notification.kind, notification.actors);
+///     }
+///     since = result.since;
+/// }
+/// ```
+pub async fn twitter_get_notifications<D: BrowserDriverPort>(
+    input: TwitterGetNotificationsInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+    progress: &Progress,
+) -> Result<TwitterGetNotificationsResult, TwitterError> {
+    // Validate input
+    if input.count == 0 {
+        return Err(TwitterError::InvalidInput {
+            message: "Count must be greater than 0".to_string(),
+        });
+    }
+    if input.count > MAX_RESULTS_PER_REQUEST {
+        return Err(TwitterError::InvalidInput {
+            message: format!("Count cannot exceed {}", MAX_RESULTS_PER_REQUEST),
+        });
+    }
+    let url = match input.tab {
+        NotificationTab::All => format!("{}/notifications", TWITTER_BASE_URL),
+        NotificationTab::Mentions => format!("{}/notifications/mentions", TWITTER_BASE_URL),
+    };
+    let since = input
+        .since
+        .as_deref()
+        .map(|since| FeedCursor::decode(since, &url))
+        .transpose()?;
+
+    // Check rate limit
+    rate_limiter.acquire().await?;
+
+    // Validate session
+    validate_session(session)?;
+
+    let (notifications, caught_up) = if page::open(driver, &url, NOTIFICATION_SELECTOR).await? {
+        collect(driver, since.as_ref(), input.count, progress).await?
+    } else {
+        (vec![], true)
+    };
+
+    // A first poll has no earlier notifications to catch up with
+    let caught_up = caught_up || since.is_none();
+    let since = match since {
+        None if notifications.is_empty() => None,
+        since => {
+            let (last_id, mut seen, mut pending) = since
+                .map(|since| (since.last_id, since.seen, since.pending))
+                .unwrap_or_default();
+            // Remember IDs oldest first so the newest survive truncation
+            pending.extend(notifications.iter().rev().map(|n| n.id.clone()));
+            if caught_up {
+                seen.append(&mut pending);
+            }
+            let cursor = FeedCursor {
+                query: url,
+                last_id: notifications.first().map_or(last_id, |n| n.id.clone()),
+                scroll_y: 0,
+                seen: most_recent(seen),
+                pending: most_recent(pending),
+            };
+            Some(cursor.encode())
+        }
+    };
+
+    Ok(TwitterGetNotificationsResult {
+        success: true,
+        notifications,
+        since,
+        error: None,
+    })
+}
+
+/// Collect up to `count` notifications newer than `since`
+///
+/// Also returns whether reading reached the notifications `since` had
+/// seen, or the end of the page, rather than stopping at `count`.
+async fn collect<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    since: Option<&FeedCursor>,
+    count: usize,
+    progress: &Progress,
+) -> Result<(Vec<TwitterNotification>, bool), TwitterError> {
+    let known: HashSet<&str> = since
+        .map(|since| since.seen.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+    let pending: HashSet<&str> = since
+        .map(|since| since.pending.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+
+    let mut notifications: Vec<TwitterNotification> = Vec::new();
+    let mut ids = HashSet::new();
+    let mut scrolls = 0;
+    loop {
+        let mut caught_up = false;
+        let mut found_new = false;
+        for notification in parse_notifications(&page::html(driver).await?) {
+            if known.contains(notification.id.as_str()) {
+                caught_up = true;
+                break;
+            }
+            if notifications.len() == count {
+                break;
+            }
+            if !ids.insert(notification.id.clone()) {
+                continue;
+            }
+            found_new = true;
+            // Returned by an earlier call that stopped at `count`
+            if !pending.contains(notification.id.as_str()) {
+                notifications.push(notification);
+            }
+        }
+        progress.report(notifications.len() as u64, Some(count as u64));
+
+        let exhausted = !found_new && scrolls > 0;
+        if caught_up || exhausted {
+            return Ok((notifications, true));
+        }
+        if notifications.len() == count || scrolls == MAX_SCROLLS {
+            return Ok((notifications, false));
+        }
+        page::scroll(driver).await?;
+        scrolls += 1;
+    }
+}
+
+/// The last `MAX_SINCE_IDS` of `ids`, to keep cursors small
+fn most_recent(mut ids: Vec<String>) -> Vec<String> {
+    let excess = ids.len().saturating_sub(MAX_SINCE_IDS);
+    ids.drain(..excess);
+    ids
+}
+
+/// Validate the Twitter session
+fn validate_session(session: &TwitterSession) -> Result<(), TwitterError> {
+    if session.cookies.is_empty() || session.csrf_token.is_empty() {
+        return Err(TwitterError::NoSession);
+    }
+    Ok(())
+}
+
+/// Create a rate limiter configured for reading notifications
+pub fn create_notifications_rate_limiter() -> RateLimiter {
+    RateLimiter::new(RateLimitConfig::for_read())
+}
@@ -0,0 +1,218 @@
+//! Browser helpers shared by the Twitter tools
+//!
+//! Thin wrappers over the core services that map driver failures to
+//! [`TwitterError`].
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::{AutomationService, ExtractionService, NavigationService};
+use tracing::{debug, warn};
+
+use super::parser::{detect_interstitial, is_interstitial_url, Interstitial};
+use super::TwitterError;
+
+/// Base URL of the web client
+pub(super) const TWITTER_BASE_URL: &str = "https://x.com";
+
+/// Selector matching a rendered tweet
+pub(super) const TWEET_SELECTOR: &str = "article[data-testid=\"tweet\"]";
+
+/// Selector matching the tweet a conversation page is focused on
+pub(super) const FOCAL_TWEET_SELECTOR: &str =
+    "article[data-testid=\"tweet\"][tabindex=\"-1\"]";
+
+/// Selector matching a thumbnail of a profile's media grid
+pub(super) const MEDIA_CELL_SELECTOR: &str = "li[role=\"listitem\"] a[href*=\"/status/\"]";
+
+/// Selector matching the entries of the notifications page
+pub(super) const NOTIFICATION_SELECTOR: &str =
+    "article[data-testid=\"tweet\"], article[data-testid=\"notification\"]";
+
+/// Selector matching a conversation in the messages list
+pub(super) const CONVERSATION_SELECTOR: &str = "div[data-testid=\"conversation\"]";
+
+/// Selector matching a message in a conversation
+pub(super) const MESSAGE_SELECTOR: &str = "div[data-testid=\"messageEntry\"]";
+
+/// Selector matching the header of a profile page
+pub(super) const PROFILE_SELECTOR: &str = "div[data-testid=\"UserName\"]";
+
+/// Selector matching the notification shown after an action
+pub(super) const TOAST_SELECTOR: &str = "div[data-testid=\"toast\"]";
+
+/// How long to wait after Twitter reports a rate limit, one of its windows
+const THROTTLE_WAIT_SECONDS: u64 = 900;
+
+/// How long to wait for the first tweet to render
+pub(super) const RENDER_TIMEOUT_MS: u64 = 15_000;
+
+/// How long to let the page load more content after scrolling
+const SCROLL_SETTLE: Duration = Duration::from_millis(500);
+
+const SCROLL_SCRIPT: &str = "window.scrollBy(0, window.innerHeight * 2)";
+
+const SCROLL_POSITION_SCRIPT: &str = "Math.round(window.scrollY)";
+
+/// Open `url` and wait until `selector` is present
+///
+/// Returns `false` when the page loaded but `selector` never appeared, and
+/// an error when X redirected to, or rendered, an interstitial instead
+/// (login wall, challenge, age gate, locked account or rate limit).
+pub(super) async fn open<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    url: &str,
+    selector: &str,
+) -> Result<bool, TwitterError> {
+    debug!(url = %url, "Opening page");
+    NavigationService::new(driver.clone())
+        .goto(url)
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to open {}: {}", url, e),
+        })?;
+
+    let found = wait(driver, selector, RENDER_TIMEOUT_MS).await;
+    // Only look at the page when something looks off, so reading content
+    // costs nothing extra
+    let current_url = current_url(driver).await?;
+    if found && !is_interstitial_url(&current_url) {
+        return Ok(true);
+    }
+    match detect_interstitial(&current_url, &html(driver).await?) {
+        Some(interstitial) => {
+            warn!(url = %url, ?interstitial, "Twitter showed an interstitial");
+            Err(interstitial_error(interstitial))
+        }
+        None => Ok(found),
+    }
+}
+
+fn interstitial_error(interstitial: Interstitial) -> TwitterError {
+    match interstitial {
+        Interstitial::LoginWall => TwitterError::LoginRequired,
+        Interstitial::Challenge => TwitterError::ChallengeRequired,
+        Interstitial::AgeGate => TwitterError::AgeRestricted,
+        Interstitial::AccountLocked => TwitterError::AccountLocked,
+        Interstitial::RateLimited => TwitterError::Throttled {
+            wait_seconds: THROTTLE_WAIT_SECONDS,
+        },
+    }
+}
+
+/// Wait up to `timeout_ms` for `selector`, returning whether it appeared
+pub(super) async fn wait<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    selector: &str,
+    timeout_ms: u64,
+) -> bool {
+    AutomationService::new(driver.clone())
+        .wait_for_element(selector, timeout_ms)
+        .await
+        .is_ok()
+}
+
+/// Click the element matching `selector`
+pub(super) async fn click<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    selector: &str,
+) -> Result<(), TwitterError> {
+    AutomationService::new(driver.clone())
+        .click(selector)
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to click {}: {}", selector, e),
+        })
+}
+
+/// Type `text` into the element matching `selector`
+pub(super) async fn type_text<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    selector: &str,
+    text: &str,
+) -> Result<(), TwitterError> {
+    AutomationService::new(driver.clone())
+        .type_text(selector, text)
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to type into {}: {}", selector, e),
+        })
+}
+
+/// Attach local files to the file input matching `selector`
+pub(super) async fn upload<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    selector: &str,
+    paths: &[PathBuf],
+) -> Result<(), TwitterError> {
+    AutomationService::new(driver.clone())
+        .upload_files(selector, paths)
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to upload media: {}", e),
+        })
+}
+
+/// HTML of the current page
+pub(super) async fn html<D: BrowserDriverPort>(driver: &Arc<D>) -> Result<String, TwitterError> {
+    ExtractionService::new(driver.clone())
+        .get_html()
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to read the page: {}", e),
+        })
+}
+
+/// URL of the current page
+pub(super) async fn current_url<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+) -> Result<String, TwitterError> {
+    NavigationService::new(driver.clone())
+        .current_url()
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to read the page URL: {}", e),
+        })
+}
+
+/// Scroll down and give the page time to load more content
+pub(super) async fn scroll<D: BrowserDriverPort>(driver: &Arc<D>) -> Result<(), TwitterError> {
+    ExtractionService::new(driver.clone())
+        .evaluate_js(SCROLL_SCRIPT)
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to scroll: {}", e),
+        })?;
+    tokio::time::sleep(SCROLL_SETTLE).await;
+    Ok(())
+}
+
+/// Scroll to `y` pixels from the top and let the page load content there
+pub(super) async fn scroll_to<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    y: u64,
+) -> Result<(), TwitterError> {
+    ExtractionService::new(driver.clone())
+        .evaluate_js(&format!("window.scrollTo(0, {})", y))
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to scroll: {}", e),
+        })?;
+    tokio::time::sleep(SCROLL_SETTLE).await;
+    Ok(())
+}
+
+/// Current vertical scroll offset in pixels
+pub(super) async fn scroll_position<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+) -> Result<u64, TwitterError> {
+    let position = ExtractionService::new(driver.clone())
+        .evaluate_js(SCROLL_POSITION_SCRIPT)
+        .await
+        .map_err(|e| TwitterError::NetworkError {
+            message: format!("Failed to read the scroll position: {}", e),
+        })?;
+    Ok(position.trim().parse().unwrap_or(0))
+}
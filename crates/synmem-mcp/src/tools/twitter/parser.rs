@@ -0,0 +1,748 @@
+//! Tweet DOM parser
+//!
+//! Turns the HTML rendered by the X web client into [`Tweet`] values. Used
+//! by every tool that reads tweets off a page (threads, timelines, search).
+//!
+//! Tweets are `article[data-testid="tweet"]` elements. Within one:
+//! - the permalink (`a[href*="/status/"]`) wraps the `time` element and
+//!   carries the author and ID
+//! - `div[data-testid="User-Name"]` holds the display name
+//! - `div[data-testid="tweetText"]` holds the text
+//! - the reply/retweet/like buttons carry the counts, either exactly in
+//!   their `aria-label` or abbreviated ("1.2K") in their visible text
+//! - a quoted tweet is rendered as a nested `div[role="link"]` card with
+//!   its own name, text and media, which must not leak into the outer tweet
+//!
+//! The notifications page mixes regular tweet articles (mentions and
+//! replies) with `article[data-testid="notification"]` entries whose first
+//! `div[dir="ltr"]` holds the message ("Ferris Fan liked your post"),
+//! linking to the accounts involved and to the tweet concerned.
+//!
+//! The media tab of a profile is a grid of thumbnails instead: every
+//! `li[role="listitem"]` links to `/user/status/123/photo/1` (or `/video/1`)
+//! around the preview image, without text, time or counts.
+//!
+//! In the messages UI, each `div[data-testid="conversation"]` links to
+//! `/messages/<id>` and shows the names, the time and a
+//! `div[data-testid="messagePreview"]` of the last message. Inside a
+//! conversation, every `div[data-testid="messageEntry"]` holds one message
+//! in a `tweetText` element; received messages also link to the sender's
+//! profile, sent ones do not.
+//!
+//! Profile pages have a `div[data-testid="UserName"]` header followed by
+//! the bio, join date and follow counts. Accounts that cannot be shown
+//! replace the timeline with a `div[data-testid="emptyState"]` message.
+//!
+//! When the session is no longer trusted, X shows an interstitial instead
+//! of the page: the login flow (`/i/flow/login`), the `/account/access`
+//! page of locked or challenged accounts (with an Arkose iframe for the
+//! challenge), or an age verification notice. When the account sends too
+//! many requests, X (or the browser, for a bare 429 response) shows a
+//! "rate limit exceeded" page instead. They are told apart by URL and by
+//! their wording, ignoring any tweet text on the page.
+
+use scraper::{ElementRef, Html, Selector};
+
+use super::page::{
+    CONVERSATION_SELECTOR, MEDIA_CELL_SELECTOR, MESSAGE_SELECTOR, NOTIFICATION_SELECTOR,
+    PROFILE_SELECTOR, TOAST_SELECTOR, TWEET_SELECTOR,
+};
+use super::{
+    NotificationKind, Tweet, TweetMedia, TwitterConversation, TwitterDirectMessage,
+    TwitterNotification, TwitterProfile,
+};
+
+/// Parse every tweet on a page, in page order
+pub fn parse_tweets(html: &str) -> Vec<Tweet> {
+    let document = Html::parse_document(html);
+    document
+        .select(&selector(TWEET_SELECTOR))
+        .filter_map(parse_article)
+        .collect()
+}
+
+/// Parse the tweets of a profile's media grid, in page order
+///
+/// Only the ID, author and media are known; thumbnails of the same tweet
+/// are merged.
+pub fn parse_media_grid(html: &str) -> Vec<Tweet> {
+    let document = Html::parse_document(html);
+    let mut tweets: Vec<Tweet> = Vec::new();
+    for cell in document.select(&selector(MEDIA_CELL_SELECTOR)) {
+        let Some(href) = cell.value().attr("href") else {
+            continue;
+        };
+        let Some((author, id)) = parse_status_path(href) else {
+            continue;
+        };
+        let media = cell
+            .select(&selector("img"))
+            .next()
+            .and_then(parse_media)
+            .map(|mut media| {
+                // The thumbnail of a video is a still image
+                if href.contains("/video/") {
+                    media.media_type = "video".to_string();
+                }
+                media
+            });
+
+        match tweets.iter_mut().find(|tweet| tweet.id == id) {
+            Some(tweet) => tweet.media.extend(media),
+            None => tweets.push(Tweet {
+                id,
+                text: String::new(),
+                author_display_name: author.clone(),
+                author,
+                timestamp: String::new(),
+                likes: 0,
+                retweets: 0,
+                replies: 0,
+                media: media.into_iter().collect(),
+                quoted_tweet: None,
+            }),
+        }
+    }
+    tweets
+}
+
+/// Parse every notification on a page, newest first
+///
+/// Entries of unknown kinds are skipped.
+pub fn parse_notifications(html: &str) -> Vec<TwitterNotification> {
+    let document = Html::parse_document(html);
+    document
+        .select(&selector(NOTIFICATION_SELECTOR))
+        .filter_map(|article| match article.value().attr("data-testid") {
+            Some("notification") => parse_notification(article),
+            _ => parse_mention(article),
+        })
+        .collect()
+}
+
+/// Parse the conversations of the messages list, most recent first
+pub fn parse_conversations(html: &str) -> Vec<TwitterConversation> {
+    let document = Html::parse_document(html);
+    document
+        .select(&selector(CONVERSATION_SELECTOR))
+        .filter_map(parse_conversation)
+        .collect()
+}
+
+/// Parse the messages of an open conversation, oldest first
+pub fn parse_messages(html: &str) -> Vec<TwitterDirectMessage> {
+    let document = Html::parse_document(html);
+    document
+        .select(&selector(MESSAGE_SELECTOR))
+        .filter_map(parse_message)
+        .collect()
+}
+
+/// Usernames suggested by the recipient search of the new message dialog
+pub(super) fn parse_typeahead(html: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+    document
+        .select(&selector("div[data-testid=\"TypeaheadUser\"]"))
+        .filter_map(|user| {
+            user.select(&selector("span"))
+                .map(text_of)
+                .find_map(|text| text.strip_prefix('@').map(str::to_string))
+        })
+        .collect()
+}
+
+/// Outcome announced by the toast shown after an action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Toast {
+    /// The toast links to a tweet, e.g. "Your post was sent. View"
+    Tweet { author: String, id: String },
+    /// Any other message, e.g. "Whoops! You already said that."
+    Message(String),
+}
+
+/// Parse the toast on a page, if one is shown
+pub(super) fn parse_toast(html: &str) -> Option<Toast> {
+    let document = Html::parse_document(html);
+    let toast = document.select(&selector(TOAST_SELECTOR)).next()?;
+    let link = toast
+        .select(&selector("a[href*=\"/status/\"]"))
+        .find_map(|link| parse_status_path(link.value().attr("href")?));
+    Some(match link {
+        Some((author, id)) => Toast::Tweet { author, id },
+        None => Toast::Message(text_of(toast)),
+    })
+}
+
+/// Why a profile page does not show an account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum MissingAccount {
+    /// "This account doesn't exist"
+    NotFound,
+    /// "Account suspended"
+    Suspended,
+}
+
+/// Detect a profile page for an account that cannot be shown
+///
+/// Protected accounts also show an empty state ("These posts are
+/// protected") but are not missing.
+pub(super) fn missing_account(html: &str) -> Option<MissingAccount> {
+    let document = Html::parse_document(html);
+    let message = document
+        .select(&selector("div[data-testid=\"emptyState\"]"))
+        .next()
+        .map(text_of)?
+        .to_lowercase()
+        .replace('’', "'");
+    if message.contains("suspended") {
+        Some(MissingAccount::Suspended)
+    } else if message.contains("doesn't exist") {
+        Some(MissingAccount::NotFound)
+    } else {
+        None
+    }
+}
+
+/// Parse the profile of `username` from its profile page
+pub(super) fn parse_profile(html: &str, username: &str) -> Option<TwitterProfile> {
+    let document = Html::parse_document(html);
+    let header = document.select(&selector(PROFILE_SELECTOR)).next()?;
+
+    let display_name = header
+        .select(&selector("span"))
+        .map(text_of)
+        .find(|name| !name.is_empty() && !name.starts_with('@'))
+        .unwrap_or_else(|| username.to_string());
+    let has_icon =
+        |icon: &str| has_descendant(header, &format!("svg[data-testid=\"{}\"]", icon));
+
+    let bio = document
+        .select(&selector("div[data-testid=\"UserDescription\"]"))
+        .next()
+        .map(tweet_text)
+        .unwrap_or_default();
+    let joined = document
+        .select(&selector("span[data-testid=\"UserJoinDate\"]"))
+        .next()
+        .map(|date| text_of(date).trim_start_matches("Joined").trim().to_string())
+        .filter(|date| !date.is_empty());
+
+    // Pinned tweets are the ones flagged "Pinned" in their social context
+    let pinned_tweet = document
+        .select(&selector(TWEET_SELECTOR))
+        .filter(|article| {
+            article
+                .select(&selector("div[data-testid=\"socialContext\"]"))
+                .any(|context| text_of(context).contains("Pinned"))
+        })
+        .find_map(parse_article)
+        .map(Box::new);
+
+    Some(TwitterProfile {
+        username: username.to_string(),
+        display_name,
+        bio,
+        joined,
+        followers: follow_count(&document, &["followers", "verified_followers"]),
+        following: follow_count(&document, &["following"]),
+        verified: has_icon("icon-verified"),
+        protected: has_icon("icon-lock"),
+        pinned_tweet,
+    })
+}
+
+/// Whether an element matching `css` is on the page
+pub(super) fn has_element(html: &str, css: &str) -> bool {
+    Html::parse_document(html)
+        .select(&selector(css))
+        .next()
+        .is_some()
+}
+
+/// Interstitial shown instead of the requested page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Interstitial {
+    /// The login flow or a "Log in" prompt
+    LoginWall,
+    /// An "unusual activity" verification challenge
+    Challenge,
+    /// An age verification gate
+    AgeGate,
+    /// "Your account has been locked"
+    AccountLocked,
+    /// "Rate limit exceeded" or an HTTP 429 error page
+    RateLimited,
+}
+
+/// Paths of the login flow
+const LOGIN_PATHS: [&str; 3] = ["/login", "/i/flow/login", "/i/flow/signup"];
+
+/// Path of the locked and challenged account page
+const ACCESS_PATH: &str = "/account/access";
+
+/// Prompts of the login wall
+const LOGIN_WALL_SELECTOR: &str =
+    "a[href=\"/login\"], a[href=\"/i/flow/login\"], input[autocomplete=\"username\"]";
+
+/// Iframe of the verification challenge
+const CHALLENGE_SELECTOR: &str = "iframe#arkose_iframe, iframe[src*=\"arkoselabs\"]";
+
+/// Detect an interstitial at `url` showing `html`
+pub(super) fn detect_interstitial(url: &str, html: &str) -> Option<Interstitial> {
+    let path = url_path(url);
+    let document = Html::parse_document(html);
+    let has = |css: &str| document.select(&selector(css)).next().is_some();
+    let text = interstitial_text(&document);
+    let says = |phrases: &[&str]| phrases.iter().any(|phrase| text.contains(phrase));
+
+    if says(&["account has been locked", "account is locked", "temporarily locked"]) {
+        Some(Interstitial::AccountLocked)
+    } else if has(CHALLENGE_SELECTOR)
+        || says(&["unusual activity", "verify you're human", "prove you're not a robot"])
+    {
+        Some(Interstitial::Challenge)
+    } else if is_path_under(path, ACCESS_PATH) {
+        Some(Interstitial::AccountLocked)
+    } else if says(&["rate limit exceeded", "http error 429", "too many requests"]) {
+        Some(Interstitial::RateLimited)
+    } else if says(&["age-restricted", "confirm your age", "verify your age"]) {
+        Some(Interstitial::AgeGate)
+    } else if LOGIN_PATHS.iter().any(|login| is_path_under(path, login))
+        || has(LOGIN_WALL_SELECTOR)
+    {
+        Some(Interstitial::LoginWall)
+    } else {
+        None
+    }
+}
+
+/// Whether `url` belongs to an interstitial rather than to content
+pub(super) fn is_interstitial_url(url: &str) -> bool {
+    let path = url_path(url);
+    is_path_under(path, ACCESS_PATH) || LOGIN_PATHS.iter().any(|login| is_path_under(path, login))
+}
+
+/// Path of `url`, without query or fragment
+fn url_path(url: &str) -> &str {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+        None => url,
+    };
+    path.split(['?', '#']).next().unwrap_or(path)
+}
+
+fn is_path_under(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Lowercase text of the page outside tweets, which may quote any wording
+fn interstitial_text(document: &Html) -> String {
+    document
+        .root_element()
+        .descendants()
+        .filter(|node| {
+            !node.ancestors().any(|ancestor| {
+                ancestor
+                    .value()
+                    .as_element()
+                    .is_some_and(|e| matches!(e.name(), "article" | "script" | "style"))
+            })
+        })
+        .filter_map(|node| node.value().as_text().map(|text| text.to_string()))
+        .collect::<String>()
+        .to_lowercase()
+        .replace('’', "'")
+}
+
+/// Parse a count as displayed by X ("987", "12,345", "1.2K", "3.4M")
+pub fn parse_count(text: &str) -> Option<u64> {
+    let text: String = text
+        .trim()
+        .chars()
+        .filter(|c| *c != ',' && !c.is_whitespace())
+        .collect();
+    let (number, multiplier) = match text.chars().last()? {
+        'K' | 'k' => (&text[..text.len() - 1], 1_000.0),
+        'M' | 'm' => (&text[..text.len() - 1], 1_000_000.0),
+        'B' | 'b' => (&text[..text.len() - 1], 1_000_000_000.0),
+        _ => (text.as_str(), 1.0),
+    };
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    let value: f64 = number.parse().ok()?;
+    Some((value * multiplier).round() as u64)
+}
+
+fn parse_article(article: ElementRef<'_>) -> Option<Tweet> {
+    let quote = quote_card(article);
+    let own = |element: &ElementRef<'_>| match quote {
+        Some(card) => !is_inside(element, card),
+        None => true,
+    };
+
+    let (author, id, timestamp) = article
+        .select(&selector("a[href*=\"/status/\"]"))
+        .filter(own)
+        .find_map(permalink)?;
+
+    let mut tweet = parse_content(article, &own, author, id, timestamp);
+    tweet.likes = action_count(article, &["like", "unlike"]);
+    tweet.retweets = action_count(article, &["retweet", "unretweet"]);
+    tweet.replies = action_count(article, &["reply"]);
+    tweet.quoted_tweet = quote.and_then(parse_quote).map(Box::new);
+    Some(tweet)
+}
+
+/// A tweet on the notifications page: a mention or a reply
+fn parse_mention(article: ElementRef<'_>) -> Option<TwitterNotification> {
+    let tweet = parse_article(article)?;
+    let is_reply = article.select(&selector("div")).any(|div| {
+        text_of(div).starts_with("Replying to")
+            && !div
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(|parent| parent.value().attr("data-testid") == Some("tweetText"))
+    });
+    Some(TwitterNotification {
+        id: tweet.id.clone(),
+        kind: if is_reply {
+            NotificationKind::Reply
+        } else {
+            NotificationKind::Mention
+        },
+        timestamp: Some(tweet.timestamp).filter(|timestamp| !timestamp.is_empty()),
+        actors: vec![tweet.author],
+        tweet_id: Some(tweet.id),
+        text: tweet.text,
+    })
+}
+
+/// A like, retweet or follow notification
+fn parse_notification(article: ElementRef<'_>) -> Option<TwitterNotification> {
+    let message = article
+        .select(&selector("div[dir=\"ltr\"]"))
+        .map(text_of)
+        .find(|text| !text.is_empty())?;
+    let lowercase = message.to_lowercase();
+    let (kind, name) = if lowercase.contains("followed you") {
+        (NotificationKind::Follow, "follow")
+    } else if lowercase.contains("liked") {
+        (NotificationKind::Like, "like")
+    } else if lowercase.contains("reposted") || lowercase.contains("retweeted") {
+        (NotificationKind::Retweet, "retweet")
+    } else {
+        return None;
+    };
+
+    let mut actors: Vec<String> = Vec::new();
+    let mut tweet_id = None;
+    for link in article.select(&selector("a[href]")) {
+        let href = link.value().attr("href").unwrap_or_default();
+        if let Some((_, id)) = parse_status_path(href) {
+            tweet_id.get_or_insert(id);
+            continue;
+        }
+        let username = href.trim_start_matches('/');
+        let is_profile = !username.is_empty()
+            && username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_profile && !actors.iter().any(|actor| actor == username) {
+            actors.push(username.to_string());
+        }
+    }
+
+    let timestamp = article
+        .select(&selector("time"))
+        .next()
+        .and_then(|time| time.value().attr("datetime"))
+        .map(str::to_string);
+    Some(TwitterNotification {
+        // Grouped notifications ("A and 2 others liked") have no ID of their
+        // own; a new actor yields a new notification
+        id: format!(
+            "{}:{}:{}",
+            name,
+            tweet_id.as_deref().unwrap_or_default(),
+            actors.join(",")
+        ),
+        kind,
+        timestamp,
+        actors,
+        tweet_id,
+        text: message,
+    })
+}
+
+fn parse_conversation(item: ElementRef<'_>) -> Option<TwitterConversation> {
+    let conversation_id = item
+        .select(&selector("a[href^=\"/messages/\"]"))
+        .filter_map(|link| link.value().attr("href"))
+        .find_map(|href| {
+            let id = href.trim_start_matches("/messages/").split(['/', '?']).next()?;
+            let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_digit() || c == '-');
+            valid.then(|| id.to_string())
+        })?;
+
+    let preview = item
+        .select(&selector("div[data-testid=\"messagePreview\"]"))
+        .next();
+    let names: Vec<String> = item
+        .select(&selector("span"))
+        .filter(|span| !preview.is_some_and(|preview| is_inside(span, preview)))
+        .map(text_of)
+        .filter(|text| !text.is_empty())
+        .collect();
+    let participants = names
+        .iter()
+        .filter_map(|name| name.strip_prefix('@'))
+        .map(str::to_string)
+        .collect();
+    let display_name = names
+        .iter()
+        .find(|name| !name.starts_with('@') && !name.starts_with('·'))
+        .cloned()
+        .unwrap_or_default();
+
+    Some(TwitterConversation {
+        conversation_id,
+        display_name,
+        participants,
+        last_message: preview.map(tweet_text).unwrap_or_default(),
+        timestamp: item
+            .select(&selector("time"))
+            .next()
+            .and_then(|time| time.value().attr("datetime"))
+            .map(str::to_string),
+        unread: has_descendant(item, "div[data-testid=\"unreadIndicator\"]"),
+    })
+}
+
+fn parse_message(entry: ElementRef<'_>) -> Option<TwitterDirectMessage> {
+    let body = entry
+        .select(&selector("div[data-testid=\"tweetText\"]"))
+        .next()?;
+    let sender = entry
+        .select(&selector("a[href]"))
+        .filter(|link| !is_inside(link, body))
+        .filter_map(|link| link.value().attr("href"))
+        .map(|href| href.trim_start_matches('/'))
+        .find(|username| {
+            !username.is_empty()
+                && username
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .map(str::to_string);
+
+    Some(TwitterDirectMessage {
+        outgoing: sender.is_none(),
+        sender,
+        text: tweet_text(body),
+        timestamp: entry
+            .select(&selector("time"))
+            .next()
+            .and_then(|time| time.value().attr("datetime"))
+            .map(str::to_string),
+    })
+}
+
+/// Parse the quoted tweet card
+///
+/// The card does not always link to the quoted tweet; its ID is empty then.
+fn parse_quote(card: ElementRef<'_>) -> Option<Tweet> {
+    let (author, id, timestamp) = match card
+        .select(&selector("a[href*=\"/status/\"]"))
+        .find_map(permalink)
+    {
+        Some(link) => link,
+        None => {
+            let handle = card
+                .select(&selector("div[data-testid=\"User-Name\"] span"))
+                .map(text_of)
+                .find(|text| text.starts_with('@'))?;
+            let timestamp = card
+                .select(&selector("time"))
+                .next()
+                .and_then(|time| time.value().attr("datetime"))
+                .unwrap_or_default()
+                .to_string();
+            (
+                handle.trim_start_matches('@').to_string(),
+                String::new(),
+                timestamp,
+            )
+        }
+    };
+    Some(parse_content(card, &|_| true, author, id, timestamp))
+}
+
+/// Name, text and media of a tweet or quote card
+fn parse_content(
+    root: ElementRef<'_>,
+    own: &dyn Fn(&ElementRef<'_>) -> bool,
+    author: String,
+    id: String,
+    timestamp: String,
+) -> Tweet {
+    let author_display_name = root
+        .select(&selector("div[data-testid=\"User-Name\"] span"))
+        .filter(own)
+        .map(text_of)
+        .find(|name| !name.is_empty() && !name.starts_with('@'))
+        .unwrap_or_else(|| author.clone());
+
+    let text = root
+        .select(&selector("div[data-testid=\"tweetText\"]"))
+        .find(own)
+        .map(tweet_text)
+        .unwrap_or_default();
+
+    let media = root
+        .select(&selector(
+            "div[data-testid=\"tweetPhoto\"] img, div[data-testid=\"videoPlayer\"] video",
+        ))
+        .filter(own)
+        .filter_map(parse_media)
+        .collect();
+
+    Tweet {
+        id,
+        text,
+        author,
+        author_display_name,
+        timestamp,
+        likes: 0,
+        retweets: 0,
+        replies: 0,
+        media,
+        quoted_tweet: None,
+    }
+}
+
+/// Author, ID and timestamp from a `/user/status/123` link wrapping a `time`
+fn permalink(link: ElementRef<'_>) -> Option<(String, String, String)> {
+    let time = link.select(&selector("time")).next()?;
+    let (author, id) = parse_status_path(link.value().attr("href")?)?;
+    let timestamp = time.value().attr("datetime").unwrap_or_default();
+    Some((author, id, timestamp.to_string()))
+}
+
+/// Split `/user/status/123` into the username and tweet ID
+fn parse_status_path(href: &str) -> Option<(String, String)> {
+    let mut segments = href.trim_start_matches('/').split('/');
+    let author = segments.next()?;
+    if segments.next()? != "status" {
+        return None;
+    }
+    let id = segments.next()?.split('?').next()?;
+    if author.is_empty() || id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((author.to_string(), id.to_string()))
+}
+
+fn parse_media(element: ElementRef<'_>) -> Option<TweetMedia> {
+    let attrs = element.value();
+    if attrs.name() == "img" {
+        return Some(TweetMedia {
+            media_type: "image".to_string(),
+            url: attrs.attr("src")?.to_string(),
+            alt_text: attrs
+                .attr("alt")
+                .filter(|alt| !alt.is_empty() && *alt != "Image")
+                .map(str::to_string),
+            local: None,
+        });
+    }
+
+    // Videos stream from blob: URLs, so prefer the poster frame; GIFs are
+    // served as plain MP4 files from tweet_video/
+    let src = attrs.attr("src").filter(|src| !src.starts_with("blob:"));
+    let is_gif = src.is_some_and(|src| src.contains("/tweet_video/"));
+    Some(TweetMedia {
+        media_type: if is_gif { "gif" } else { "video" }.to_string(),
+        url: src.or_else(|| attrs.attr("poster"))?.to_string(),
+        alt_text: attrs
+            .attr("aria-label")
+            .filter(|label| !label.is_empty() && *label != "Embedded video")
+            .map(str::to_string),
+        local: None,
+    })
+}
+
+/// Count on the first matching action button
+///
+/// Uses the exact number from the `aria-label` ("1512 Likes. Like") when
+/// present, falling back to the abbreviated visible text ("1.5K").
+fn action_count(article: ElementRef<'_>, actions: &[&str]) -> u64 {
+    let Some(button) = actions.iter().find_map(|action| {
+        let css = format!("button[data-testid=\"{}\"]", action);
+        article.select(&selector(&css)).next()
+    }) else {
+        return 0;
+    };
+
+    button
+        .value()
+        .attr("aria-label")
+        .and_then(|label| label.split_whitespace().next())
+        .and_then(parse_count)
+        .or_else(|| parse_count(&text_of(button)))
+        .unwrap_or(0)
+}
+
+/// Count shown on the first profile link ending in one of `pages`
+///
+/// The links read e.g. "1.2M Followers", with the number first.
+fn follow_count(document: &Html, pages: &[&str]) -> Option<u64> {
+    pages.iter().find_map(|page| {
+        let css = format!("a[href$=\"/{}\"]", page);
+        let link = document.select(&selector(&css)).next()?;
+        text_of(link).split_whitespace().next().and_then(parse_count)
+    })
+}
+
+/// The quoted tweet card inside `article`, if any
+fn quote_card(article: ElementRef<'_>) -> Option<ElementRef<'_>> {
+    article
+        .select(&selector("div[role=\"link\"]"))
+        .find(|card| {
+            card.select(&selector("div[data-testid=\"User-Name\"]"))
+                .next()
+                .is_some()
+        })
+}
+
+/// Text of a `tweetText` element, keeping emoji rendered as images
+fn tweet_text(element: ElementRef<'_>) -> String {
+    let mut text = String::new();
+    for node in element.descendants() {
+        if let Some(chunk) = node.value().as_text() {
+            text.push_str(chunk);
+        } else if let Some(img) = node.value().as_element().filter(|e| e.name() == "img") {
+            text.push_str(img.attr("alt").unwrap_or_default());
+        }
+    }
+    text.trim().to_string()
+}
+
+fn has_descendant(element: ElementRef<'_>, css: &str) -> bool {
+    element.select(&selector(css)).next().is_some()
+}
+
+fn is_inside(element: &ElementRef<'_>, container: ElementRef<'_>) -> bool {
+    element
+        .ancestors()
+        .any(|ancestor| ancestor.id() == container.id())
+}
+
+fn text_of(element: ElementRef<'_>) -> String {
+    element.text().collect::<String>().trim().to_string()
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("selector is valid")
+}
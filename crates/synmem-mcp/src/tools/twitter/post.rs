@@ -1,18 +1,53 @@
 //! Twitter post tool
 //!
-//! Posts a tweet with optional media and reply support.
+//! Posts a tweet with optional media and reply support by driving the web
+//! client's compose box.
 
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tracing::{info, warn};
+
+use super::media;
+use super::page::{
+    self, FOCAL_TWEET_SELECTOR, RENDER_TIMEOUT_MS, TOAST_SELECTOR, TWEET_SELECTOR,
+    TWITTER_BASE_URL,
+};
+use super::parser::{parse_toast, Toast};
+use super::read_thread::extract_tweet_id;
 use super::{
     RateLimiter, RateLimitConfig, TwitterError, TwitterPostInput, TwitterPostResult,
-    TwitterSession, TWEET_MAX_LENGTH,
+    TwitterSession, MAX_MEDIA_PER_TWEET, TWEET_MAX_LENGTH,
 };
 
+/// Text area of the compose box
+const COMPOSE_TEXT_SELECTOR: &str = "div[data-testid=\"tweetTextarea_0\"]";
+
+/// File input of the compose box
+const MEDIA_INPUT_SELECTOR: &str = "input[data-testid=\"fileInput\"]";
+
+/// Button submitting the compose box
+const POST_BUTTON_SELECTOR: &str = "button[data-testid=\"tweetButton\"]";
+
+/// The submit button once the text and media are ready
+const POST_BUTTON_READY_SELECTOR: &str =
+    "button[data-testid=\"tweetButton\"]:not([aria-disabled=\"true\"])";
+
+/// How long to wait for attachments to finish uploading
+const UPLOAD_TIMEOUT_MS: u64 = 120_000;
+
 /// Post a tweet
 ///
+/// Validation, rate limit and session failures are returned as errors, as
+/// are the pages X shows instead of the compose box when a human must act
+/// or the account is throttled. Other failures once the browser is
+/// involved are reported in [`TwitterPostResult::error`] instead.
+///
 /// # Arguments
 /// * `input` - The tweet content and options
 /// * `session` - Valid Twitter session
 /// * `rate_limiter` - Rate limiter to prevent API abuse
+/// * `driver` - Browser driver used to compose the tweet
 ///
 /// # Returns
 /// Result containing the posted tweet info or an error
@@ -24,12 +59,13 @@ use super::{
 ///     media_urls: vec![],
 ///     reply_to: None,
 /// };
-/// let result = twitter_post(input, &session, &rate_limiter).await?;
+/// let result = twitter_post(input, &session, &rate_limiter, &driver).await?;
 /// ```
-pub async fn twitter_post(
+pub async fn twitter_post<D: BrowserDriverPort>(
     input: TwitterPostInput,
     session: &TwitterSession,
     rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
 ) -> Result<TwitterPostResult, TwitterError> {
     // Validate input
     validate_post_input(&input)?;
@@ -40,24 +76,104 @@ pub async fn twitter_post(
     // Validate session
     validate_session(session)?;
 
-    // In a real implementation, this would:
-    // 1. Prepare the tweet payload
-    // 2. Upload media if provided
-    // 3. Make the API call to post the tweet
-    // 4. Return the result
-
-    // For now, return a placeholder result
-    // The actual implementation would use chromiumoxide to automate the browser
-    Ok(TwitterPostResult {
-        success: true,
-        tweet_id: Some("placeholder_tweet_id".to_string()),
-        tweet_url: Some("https://twitter.com/user/status/placeholder_tweet_id".to_string()),
-        error: None,
-    })
+    match publish(&input, driver).await {
+        Ok((author, tweet_id)) => {
+            info!(tweet_id = %tweet_id, "Posted tweet");
+            let tweet_url = format!("{}/{}/status/{}", TWITTER_BASE_URL, author, tweet_id);
+            Ok(TwitterPostResult {
+                success: true,
+                tweet_url: Some(tweet_url),
+                tweet_id: Some(tweet_id),
+                error: None,
+            })
+        }
+        Err(e) if is_blocked(&e) => Err(e),
+        Err(e) => {
+            warn!(error = %e, "Failed to post tweet");
+            Ok(TwitterPostResult {
+                success: false,
+                tweet_id: None,
+                tweet_url: None,
+                error: Some(e.to_string()),
+            })
+        }
+    }
+}
+
+/// Compose and submit the tweet, returning its author and ID
+async fn publish<D: BrowserDriverPort>(
+    input: &TwitterPostInput,
+    driver: &Arc<D>,
+) -> Result<(String, String), TwitterError> {
+    // Fetch media first so a bad URL fails before anything is typed
+    let media = media::prepare(&input.media_urls).await?;
+
+    open_composer(driver, input.reply_to.as_deref()).await?;
+    if !input.text.is_empty() {
+        page::type_text(driver, COMPOSE_TEXT_SELECTOR, &input.text).await?;
+    }
+    if !media.paths.is_empty() {
+        page::upload(driver, MEDIA_INPUT_SELECTOR, &media.paths).await?;
+    }
+    if !page::wait(driver, POST_BUTTON_READY_SELECTOR, UPLOAD_TIMEOUT_MS).await {
+        return Err(TwitterError::Unknown {
+            message: "The post button never became enabled".to_string(),
+        });
+    }
+    page::click(driver, POST_BUTTON_SELECTOR).await?;
+
+    if !page::wait(driver, TOAST_SELECTOR, RENDER_TIMEOUT_MS).await {
+        return Err(TwitterError::Unknown {
+            message: "Twitter did not confirm the post".to_string(),
+        });
+    }
+    match parse_toast(&page::html(driver).await?) {
+        Some(Toast::Tweet { author, id }) => Ok((author, id)),
+        Some(Toast::Message(message)) => Err(TwitterError::Unknown { message }),
+        None => Err(TwitterError::Unknown {
+            message: "Twitter did not confirm the post".to_string(),
+        }),
+    }
+}
+
+/// Open the compose box, replying to `reply_to` when given
+async fn open_composer<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    reply_to: Option<&str>,
+) -> Result<(), TwitterError> {
+    let Some(reply_to) = reply_to else {
+        let url = format!("{}/compose/post", TWITTER_BASE_URL);
+        if !page::open(driver, &url, COMPOSE_TEXT_SELECTOR).await? {
+            return Err(TwitterError::Unknown {
+                message: "The compose box did not load".to_string(),
+            });
+        }
+        return Ok(());
+    };
+
+    let tweet_id = extract_tweet_id(reply_to)?;
+    let url = format!("{}/i/status/{}", TWITTER_BASE_URL, tweet_id);
+    if !page::open(driver, &url, TWEET_SELECTOR).await? {
+        return Err(TwitterError::TweetNotFound { tweet_id });
+    }
+    let reply_button = format!("{} button[data-testid=\"reply\"]", FOCAL_TWEET_SELECTOR);
+    page::click(driver, &reply_button).await?;
+    if !page::wait(driver, COMPOSE_TEXT_SELECTOR, RENDER_TIMEOUT_MS).await {
+        return Err(TwitterError::Unknown {
+            message: "The reply box did not load".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Whether X stopped the post with a page the caller must handle: a login
+/// wall, a challenge or a rate limit
+pub(super) fn is_blocked(error: &TwitterError) -> bool {
+    error.needs_human() || matches!(error, TwitterError::Throttled { .. })
 }
 
 /// Validate the post input
-fn validate_post_input(input: &TwitterPostInput) -> Result<(), TwitterError> {
+pub(super) fn validate_post_input(input: &TwitterPostInput) -> Result<(), TwitterError> {
     // Check tweet length
     let char_count = input.text.chars().count();
     if char_count > TWEET_MAX_LENGTH {
@@ -74,6 +190,16 @@ fn validate_post_input(input: &TwitterPostInput) -> Result<(), TwitterError> {
         });
     }
 
+    if input.media_urls.len() > MAX_MEDIA_PER_TWEET {
+        return Err(TwitterError::InvalidInput {
+            message: format!("A tweet can have at most {} media attachments", MAX_MEDIA_PER_TWEET),
+        });
+    }
+
+    if let Some(reply_to) = &input.reply_to {
+        extract_tweet_id(reply_to)?;
+    }
+
     // Validate media URLs
     for url in &input.media_urls {
         if !is_valid_media_url(url) {
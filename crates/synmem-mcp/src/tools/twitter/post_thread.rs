@@ -0,0 +1,355 @@
+//! Twitter thread composer tool
+//!
+//! Posts a list of tweets as a reply chain, splitting long text into
+//! numbered tweets when needed.
+
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tracing::warn;
+
+use super::page::{
+    self, FOCAL_TWEET_SELECTOR, TOAST_SELECTOR, TWEET_SELECTOR, TWITTER_BASE_URL,
+};
+use super::post::is_blocked;
+use super::{
+    twitter_post, PostedTweet, RateLimiter, TwitterError, TwitterPostInput,
+    TwitterPostThreadInput, TwitterPostThreadResult, TwitterSession, MAX_THREAD_LENGTH,
+    TWEET_MAX_LENGTH,
+};
+use crate::tools::Progress;
+
+/// Item of the tweet menu that deletes one's own tweet
+const DELETE_MENU_ITEM_SELECTOR: &str =
+    "div[data-testid=\"Dropdown\"] div[role=\"menuitem\"]:first-child";
+
+/// Button confirming a deletion
+const CONFIRM_SELECTOR: &str = "button[data-testid=\"confirmationSheetConfirm\"]";
+
+/// How long to wait for menus and dialogs to open
+const MENU_TIMEOUT_MS: u64 = 5_000;
+
+/// Post a thread
+///
+/// Each tweet replies to the previous one and goes through `rate_limiter`.
+/// The whole thread must fit in the limiter's current window, so it is
+/// never cut short by the limit.
+///
+/// # Arguments
+/// * `input` - The segments or long text and options
+/// * `session` - Valid Twitter session
+/// * `rate_limiter` - The post rate limiter
+/// * `driver` - Browser driver used to compose the tweets
+/// * `progress` - Receives the number of tweets posted out of the total
+///
+/// # Returns
+/// Result containing the posted tweets. If a tweet fails, the ones already
+/// posted are listed (or deleted with `rollback_on_failure`) and the
+/// failure is reported in `error`. When X blocks the first tweet with a
+/// login wall, a challenge or a rate limit, that error is returned.
+///
+/// # Example
+/// ```ignore
+/// let input = TwitterPostThreadInput {
+///     segments: vec![],
+///     text: Some(long_text),
+///     rollback_on_failure: true,
+/// };
+/// let result =
+///     twitter_post_thread(input, &session, &rate_limiter, &driver, &Progress::none()).await?;
+/// ```
+pub async fn twitter_post_thread<D: BrowserDriverPort>(
+    input: TwitterPostThreadInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+    progress: &Progress,
+) -> Result<TwitterPostThreadResult, TwitterError> {
+    // Validate input
+    let segments = thread_segments(&input)?;
+
+    // Validate session
+    validate_session(session)?;
+
+    // Check that the whole thread fits in the rate limit
+    rate_limiter.check_capacity(segments.len() as u64).await?;
+
+    let total = segments.len();
+    let mut tweets: Vec<PostedTweet> = Vec::with_capacity(total);
+    for (index, text) in segments.into_iter().enumerate() {
+        let post = TwitterPostInput {
+            text,
+            media_urls: vec![],
+            reply_to: tweets.last().map(|tweet| tweet.tweet_id.clone()),
+        };
+        let failure = match twitter_post(post, session, rate_limiter, driver).await {
+            Ok(result) => match (result.tweet_id, result.tweet_url) {
+                (Some(tweet_id), Some(tweet_url)) if result.success => {
+                    tweets.push(PostedTweet {
+                        tweet_id,
+                        tweet_url,
+                    });
+                    progress.report(tweets.len() as u64, Some(total as u64));
+                    continue;
+                }
+                _ => result
+                    .error
+                    .unwrap_or_else(|| "Tweet was not posted".to_string()),
+            },
+            // Nothing to report yet, so let the caller see what blocked it
+            Err(e) if tweets.is_empty() && is_blocked(&e) => return Err(e),
+            Err(e) => e.to_string(),
+        };
+
+        let error = format!("Tweet {} of {} failed: {}", index + 1, total, failure);
+        warn!(error = %error, posted = tweets.len(), "Thread interrupted");
+        return Ok(abort(driver, tweets, total, error, input.rollback_on_failure).await);
+    }
+
+    Ok(TwitterPostThreadResult {
+        success: true,
+        tweets,
+        total,
+        rolled_back: false,
+        error: None,
+    })
+}
+
+/// Report a failed thread, deleting the posted tweets when `rollback` is set
+async fn abort<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    mut tweets: Vec<PostedTweet>,
+    total: usize,
+    mut error: String,
+    rollback: bool,
+) -> TwitterPostThreadResult {
+    let mut rolled_back = false;
+    if rollback && !tweets.is_empty() {
+        // Delete from the end so the thread never has a missing middle
+        while let Some(tweet) = tweets.last() {
+            if let Err(e) = delete_tweet(driver, &tweet.tweet_id).await {
+                error.push_str(&format!(
+                    "; rollback stopped at tweet {}: {}",
+                    tweet.tweet_id, e
+                ));
+                break;
+            }
+            tweets.pop();
+        }
+        rolled_back = tweets.is_empty();
+    }
+
+    TwitterPostThreadResult {
+        success: false,
+        tweets,
+        total,
+        rolled_back,
+        error: Some(error),
+    }
+}
+
+/// Delete one of the account's own tweets
+async fn delete_tweet<D: BrowserDriverPort>(
+    driver: &Arc<D>,
+    tweet_id: &str,
+) -> Result<(), TwitterError> {
+    let url = format!("{}/i/status/{}", TWITTER_BASE_URL, tweet_id);
+    if !page::open(driver, &url, TWEET_SELECTOR).await? {
+        return Err(TwitterError::TweetNotFound {
+            tweet_id: tweet_id.to_string(),
+        });
+    }
+
+    let menu_button = format!("{} button[data-testid=\"caret\"]", FOCAL_TWEET_SELECTOR);
+    page::click(driver, &menu_button).await?;
+    for selector in [DELETE_MENU_ITEM_SELECTOR, CONFIRM_SELECTOR] {
+        if !page::wait(driver, selector, MENU_TIMEOUT_MS).await {
+            return Err(TwitterError::Unknown {
+                message: format!("{} did not appear", selector),
+            });
+        }
+        page::click(driver, selector).await?;
+    }
+
+    if !page::wait(driver, TOAST_SELECTOR, MENU_TIMEOUT_MS).await {
+        return Err(TwitterError::Unknown {
+            message: "Twitter did not confirm the deletion".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// The tweets to post, validated
+fn thread_segments(input: &TwitterPostThreadInput) -> Result<Vec<String>, TwitterError> {
+    let segments = match (&input.text, input.segments.is_empty()) {
+        (Some(_), false) => {
+            return Err(TwitterError::InvalidInput {
+                message: "Provide either segments or text, not both".to_string(),
+            })
+        }
+        (Some(text), true) => split_thread(text),
+        (None, false) => input.segments.clone(),
+        (None, true) => vec![],
+    };
+
+    if segments.is_empty() {
+        return Err(TwitterError::InvalidInput {
+            message: "A thread needs at least one tweet".to_string(),
+        });
+    }
+    if segments.len() > MAX_THREAD_LENGTH {
+        return Err(TwitterError::InvalidInput {
+            message: format!(
+                "A thread can have at most {} tweets (got {})",
+                MAX_THREAD_LENGTH,
+                segments.len()
+            ),
+        });
+    }
+
+    for (index, segment) in segments.iter().enumerate() {
+        if segment.trim().is_empty() {
+            return Err(TwitterError::InvalidInput {
+                message: format!("Tweet {} of the thread is empty", index + 1),
+            });
+        }
+        let char_count = segment.chars().count();
+        if char_count > TWEET_MAX_LENGTH {
+            return Err(TwitterError::TweetTooLong {
+                max: TWEET_MAX_LENGTH,
+                actual: char_count,
+            });
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Split `text` into tweets on sentence boundaries
+///
+/// Text that fits in one tweet is returned as is. Otherwise every tweet
+/// ends with its position, e.g. "1/3". Sentences longer than a tweet are
+/// split between words, and words longer than a tweet anywhere.
+pub fn split_thread(text: &str) -> Vec<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return vec![];
+    }
+    if text.chars().count() <= TWEET_MAX_LENGTH {
+        return vec![text.to_string()];
+    }
+
+    let sentences = sentences(text);
+    // The numbering's width depends on the number of tweets, so retry with
+    // a wider reservation until it fits
+    let mut digits = 1;
+    loop {
+        let budget = TWEET_MAX_LENGTH - (2 * digits + 2);
+        let chunks = pack(&sentences, budget);
+        let total = chunks.len();
+        if total.to_string().len() <= digits {
+            return chunks
+                .into_iter()
+                .enumerate()
+                .map(|(index, chunk)| format!("{} {}/{}", chunk, index + 1, total))
+                .collect();
+        }
+        digits += 1;
+    }
+}
+
+/// Sentences of `text`, each with the separator that follows it
+fn sentences(text: &str) -> Vec<(String, &'static str)> {
+    let mut sentences = Vec::new();
+    for line in text.lines() {
+        let mut sentence = String::new();
+        for word in line.split_whitespace() {
+            if !sentence.is_empty() {
+                sentence.push(' ');
+            }
+            sentence.push_str(word);
+            if ends_sentence(word) {
+                sentences.push((std::mem::take(&mut sentence), " "));
+            }
+        }
+        if !sentence.is_empty() {
+            sentences.push((sentence, " "));
+        }
+        // Keep line breaks between paragraphs
+        if let Some(last) = sentences.last_mut() {
+            last.1 = "\n";
+        }
+    }
+    sentences
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.trim_end_matches(['"', '\'', ')', '”', '’'])
+        .ends_with(['.', '!', '?', '…'])
+}
+
+/// Greedily pack sentences into chunks of at most `budget` characters
+fn pack(sentences: &[(String, &'static str)], budget: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut separator = "";
+    for (sentence, next_separator) in sentences {
+        for piece in fit(sentence, budget) {
+            let length = current.chars().count() + separator.len() + piece.chars().count();
+            if !current.is_empty() && length > budget {
+                chunks.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push_str(separator);
+            }
+            current.push_str(&piece);
+            separator = " ";
+        }
+        separator = next_separator;
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Break a sentence into pieces of at most `budget` characters
+fn fit(sentence: &str, budget: usize) -> Vec<String> {
+    if sentence.chars().count() <= budget {
+        return vec![sentence.to_string()];
+    }
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for word in sentence.split(' ') {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > budget {
+            if !current.is_empty() {
+                pieces.push(std::mem::take(&mut current));
+            }
+            pieces.push(word.drain(..budget).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+        if !current.is_empty() && current.chars().count() + 1 + word.len() > budget {
+            pieces.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.extend(word);
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// Validate the Twitter session
+fn validate_session(session: &TwitterSession) -> Result<(), TwitterError> {
+    if session.cookies.is_empty() || session.csrf_token.is_empty() {
+        return Err(TwitterError::NoSession);
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! Twitter profile tool
+//!
+//! Read the public profile of an account.
+
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+
+use super::page::{self, PROFILE_SELECTOR, TWITTER_BASE_URL};
+use super::parser::{missing_account, parse_profile, MissingAccount};
+use super::timeline::is_valid_username;
+use super::{
+    RateLimiter, RateLimitConfig, TwitterError, TwitterGetProfileInput, TwitterGetProfileResult,
+    TwitterSession,
+};
+
+/// Get a Twitter profile
+///
+/// # Arguments
+/// * `input` - The username to look up
+/// * `session` - Valid Twitter session
+/// * `rate_limiter` - Rate limiter to prevent API abuse
+/// * `driver` - Browser driver used to load the profile
+///
+/// # Returns
+/// Result containing the profile, `UserNotFound` when the account does not
+/// exist, or `UserSuspended` when it is suspended
+///
+/// # Example
+/// ```ignore
+/// let input = TwitterGetProfileInput {
+///     username: "rustlang".to_string(),
+/// };
+/// let result = twitter_get_profile(input, &session, &rate_limiter, &driver).await?;
+/// if let Some(profile) = result.profile {
+///     println!("{}: {:?} followers", profile.display_name, profile.followers);
+/// }
+/// ```
+pub async fn twitter_get_profile<D: BrowserDriverPort>(
+    input: TwitterGetProfileInput,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+) -> Result<TwitterGetProfileResult, TwitterError> {
+    // Validate input
+    let username = input.username.trim().trim_start_matches('@');
+    if !is_valid_username(username) {
+        return Err(TwitterError::InvalidInput {
+            message: format!("Invalid username format: {}", input.username),
+        });
+    }
+
+    // Check rate limit
+    rate_limiter.acquire().await?;
+
+    // Validate session
+    validate_session(session)?;
+
+    let not_found = || TwitterError::UserNotFound {
+        username: username.to_string(),
+    };
+    let url = format!("{}/{}", TWITTER_BASE_URL, username);
+    let loaded = page::open(driver, &url, PROFILE_SELECTOR).await?;
+    let html = page::html(driver).await?;
+    if let Some(reason) = missing_account(&html) {
+        return Err(missing_account_error(reason, username));
+    }
+    if !loaded {
+        return Err(not_found());
+    }
+
+    let profile = parse_profile(&html, username).ok_or_else(|| TwitterError::Unknown {
+        message: format!("Could not read the profile of {}", username),
+    })?;
+    Ok(TwitterGetProfileResult {
+        success: true,
+        profile: Some(profile),
+        error: None,
+    })
+}
+
+/// Error for the account `username` whose profile page shows `reason`
+pub(super) fn missing_account_error(reason: MissingAccount, username: &str) -> TwitterError {
+    let username = username.to_string();
+    match reason {
+        MissingAccount::NotFound => TwitterError::UserNotFound { username },
+        MissingAccount::Suspended => TwitterError::UserSuspended { username },
+    }
+}
+
+/// Validate the Twitter session
+fn validate_session(session: &TwitterSession) -> Result<(), TwitterError> {
+    if session.cookies.is_empty() || session.csrf_token.is_empty() {
+        return Err(TwitterError::NoSession);
+    }
+    Ok(())
+}
+
+/// Create a rate limiter configured for profile lookups
+pub fn create_profile_rate_limiter() -> RateLimiter {
+    RateLimiter::new(RateLimitConfig::for_read())
+}
@@ -0,0 +1,138 @@
+//! Persistent rate limiter state
+//!
+//! Keeps the state of each rate limiter in a JSON file so that budgets
+//! survive restarts and are shared by every process acting for the same
+//! account. Keys are `<account>/<operation>` and map to
+//! `<root>/<account>/<operation>.json`.
+//!
+//! Updates are read-modify-write cycles guarded by a [`FileLock`] on a
+//! `.lock` file next to the state, so that only one process holds it.
+//! Files are accessed through `tokio::fs`, so waiting on the disk does not
+//! hold up the runtime.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::file_lock::FileLock;
+use super::TwitterError;
+
+/// How long to wait for another process to release a lock
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// State of a rate limiter, in wall-clock time so it can be persisted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitState {
+    /// Requests left in the current window
+    pub tokens: u64,
+    /// Start of the current window, in milliseconds since the Unix epoch
+    pub window_start_ms: u64,
+    /// Time of the last request, in milliseconds since the Unix epoch
+    pub last_request_ms: u64,
+    /// Sliding window mode: times of the requests made in the last
+    /// window, oldest first, in milliseconds since the Unix epoch
+    #[serde(default)]
+    pub request_times_ms: Vec<u64>,
+    /// How many times the budget has been halved after Twitter pushed back
+    #[serde(default)]
+    pub backoff_level: u32,
+    /// When the back-off level last changed, in milliseconds since the
+    /// Unix epoch
+    #[serde(default)]
+    pub backoff_since_ms: u64,
+}
+
+/// Directory of persisted rate limiter states
+#[derive(Debug, Clone)]
+pub struct RateLimitStore {
+    root: PathBuf,
+}
+
+impl RateLimitStore {
+    /// Create a store under `root` (created on first write)
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Lock the state under `key` for a read-modify-write cycle
+    pub(super) async fn lock(&self, key: &str) -> Result<StateLock, TwitterError> {
+        let path = self.path_for(key);
+        let lock_path = with_suffix(&path, ".lock");
+        let io_error = |e: std::io::Error| TwitterError::Unknown {
+            message: format!("Failed to lock the rate limit state of {}: {}", key, e),
+        };
+        match FileLock::acquire(&lock_path, LOCK_TIMEOUT).await.map_err(io_error)? {
+            Some(lock) => Ok(StateLock { path, _lock: lock }),
+            None => Err(TwitterError::Unknown {
+                message: format!("The rate limit state of {} stayed locked", key),
+            }),
+        }
+    }
+
+    /// Path of the state file for `key`, with unsafe characters replaced
+    fn path_for(&self, key: &str) -> PathBuf {
+        let mut path = self.root.clone();
+        for segment in key.split('/').filter(|segment| !segment.is_empty()) {
+            let segment: String = segment
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c,
+                    _ => '_',
+                })
+                .collect();
+            // Never let "." or ".." escape the root
+            if segment.chars().all(|c| c == '.') {
+                path.push("_");
+            } else {
+                path.push(segment);
+            }
+        }
+        with_suffix(&path, ".json")
+    }
+}
+
+/// Exclusive access to one persisted state, released on drop
+pub(super) struct StateLock {
+    path: PathBuf,
+    _lock: FileLock,
+}
+
+impl StateLock {
+    /// The saved state, if any
+    ///
+    /// A state that cannot be read is treated as missing, which resets
+    /// the budget instead of blocking every request.
+    pub(super) async fn load(&self) -> Option<RateLimitState> {
+        let content = tokio::fs::read_to_string(&self.path).await.ok()?;
+        match serde_json::from_str(&content) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                warn!(path = %self.path.display(), error = %e, "Ignoring corrupt rate limit state");
+                None
+            }
+        }
+    }
+
+    /// Save `state`
+    pub(super) async fn save(&self, state: &RateLimitState) -> Result<(), TwitterError> {
+        let content = serde_json::to_string(state).expect("state serializes");
+        // Write to a sibling file first so readers never see a partial state
+        let temp = with_suffix(&self.path, ".tmp");
+        let saved = match tokio::fs::write(&temp, content).await {
+            Ok(()) => tokio::fs::rename(&temp, &self.path).await,
+            Err(e) => Err(e),
+        };
+        saved.map_err(|e| TwitterError::Unknown {
+            message: format!("Failed to save the rate limit state: {}", e),
+        })
+    }
+}
+
+/// `path` with `suffix` appended to its file name
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
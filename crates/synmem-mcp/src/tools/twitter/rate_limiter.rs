@@ -1,15 +1,41 @@
 //! Rate limiter for Twitter API calls
 //!
 //! Implements a token bucket algorithm to enforce rate limits
-//! and prevent account suspension.
+//! and prevent account suspension. State can be persisted per account and
+//! operation, see [`RateLimitStore`].
+//!
+//! A limiter either refills its whole budget at the end of each window or,
+//! in [`RateLimitMode::SlidingWindow`], keeps the times of the requests made
+//! in the last window so no burst can straddle a window boundary. When Twitter
+//! pushes back, [`RateLimiter::back_off`] halves the budget and doubles the
+//! delay between requests; each quiet stretch of two windows undoes
+//! one halving.
 
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 
+use super::rate_limit_store::{RateLimitState, RateLimitStore};
 use super::TwitterError;
 
+/// Highest back-off level, at which the budget is an eighth of normal
+const MAX_BACKOFF_LEVEL: u32 = 3;
+
+/// Quiet windows needed to recover one back-off level
+const RECOVERY_WINDOWS: u64 = 2;
+
+/// How a limiter spreads its budget over the window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// The whole budget is available again when each window ends
+    #[default]
+    FixedWindow,
+    /// Each request frees up one window after it was made, so any
+    /// window-long stretch holds at most `max_requests` requests
+    SlidingWindow,
+}
+
 /// Rate limits for different Twitter operations
 #[derive(Debug, Clone, Copy)]
 pub struct RateLimitConfig {
@@ -19,6 +45,10 @@ pub struct RateLimitConfig {
     pub window_seconds: u64,
     /// Minimum delay between requests in milliseconds
     pub min_delay_ms: u64,
+    /// How the budget is spread over the window
+    pub mode: RateLimitMode,
+    /// Random extra delay of up to this many milliseconds per request
+    pub jitter_ms: u64,
 }
 
 impl Default for RateLimitConfig {
@@ -27,6 +57,8 @@ impl Default for RateLimitConfig {
             max_requests: 50,
             window_seconds: 900, // 15 minutes
             min_delay_ms: 1000,  // 1 second between requests
+            mode: RateLimitMode::FixedWindow,
+            jitter_ms: 0,
         }
     }
 }
@@ -38,6 +70,8 @@ impl RateLimitConfig {
             max_requests: 25,
             window_seconds: 900,
             min_delay_ms: 2000,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 1000,
         }
     }
 
@@ -47,6 +81,8 @@ impl RateLimitConfig {
             max_requests: 100,
             window_seconds: 900,
             min_delay_ms: 500,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 250,
         }
     }
 
@@ -56,30 +92,155 @@ impl RateLimitConfig {
             max_requests: 50,
             window_seconds: 900,
             min_delay_ms: 1000,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 500,
+        }
+    }
+
+    /// Configuration for liking and unliking tweets
+    pub fn for_like() -> Self {
+        Self {
+            max_requests: 50,
+            window_seconds: 900,
+            min_delay_ms: 1500,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 750,
+        }
+    }
+
+    /// Configuration for retweeting
+    pub fn for_retweet() -> Self {
+        Self {
+            max_requests: 25,
+            window_seconds: 900,
+            min_delay_ms: 2000,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 1000,
+        }
+    }
+
+    /// Configuration for bookmarking
+    pub fn for_bookmark() -> Self {
+        Self {
+            max_requests: 50,
+            window_seconds: 900,
+            min_delay_ms: 1000,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 500,
+        }
+    }
+
+    /// Configuration for following and unfollowing
+    pub fn for_follow() -> Self {
+        Self {
+            max_requests: 15,
+            window_seconds: 900,
+            min_delay_ms: 3000,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 1500,
+        }
+    }
+
+    /// Budget shared by every operation of one account
+    pub fn for_account() -> Self {
+        Self {
+            max_requests: 300,
+            window_seconds: 900,
+            min_delay_ms: 0,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 0,
+        }
+    }
+
+    /// Configuration for direct messages (most restrictive)
+    pub fn for_dm() -> Self {
+        Self {
+            max_requests: 10,
+            window_seconds: 900,
+            min_delay_ms: 5000,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 2500,
         }
     }
 }
 
 /// Rate limiter using token bucket algorithm
+///
+/// The state lives in memory unless the limiter is [`persistent`], in
+/// which case every acquisition goes through the [`RateLimitStore`] so the
+/// budget survives restarts and is shared with other processes.
+///
+/// [`persistent`]: RateLimiter::persistent
 #[derive(Debug)]
 pub struct RateLimiter {
     config: RateLimitConfig,
+    /// Tokens left as of the last operation, for the synchronous accessors
     tokens: AtomicU64,
-    last_request: Arc<Mutex<Instant>>,
-    window_start: Arc<Mutex<Instant>>,
+    state: Mutex<RateLimitState>,
+    persistence: Option<Persistence>,
+    /// Budget also drawn from by every acquisition, e.g. the account's
+    shared: Option<Arc<RateLimiter>>,
+}
+
+/// A token spent by [`RateLimiter::acquire`]
+struct Reservation {
+    /// Time the request may go ahead, in milliseconds since the Unix epoch
+    slot_ms: u64,
+    /// Start of the window the token was spent in
+    window_start_ms: u64,
+    /// How long to wait for the slot
+    delay_ms: u64,
+}
+
+/// Where a persistent limiter keeps its state
+#[derive(Debug)]
+struct Persistence {
+    store: RateLimitStore,
+    key: String,
 }
 
 impl RateLimiter {
     /// Create a new rate limiter with the given configuration
     pub fn new(config: RateLimitConfig) -> Self {
+        let now = now_ms();
         Self {
             config,
             tokens: AtomicU64::new(config.max_requests as u64),
-            last_request: Arc::new(Mutex::new(Instant::now())),
-            window_start: Arc::new(Mutex::new(Instant::now())),
+            state: Mutex::new(RateLimitState {
+                tokens: config.max_requests as u64,
+                window_start_ms: now,
+                last_request_ms: now,
+                request_times_ms: Vec::new(),
+                backoff_level: 0,
+                backoff_since_ms: now,
+            }),
+            persistence: None,
+            shared: None,
+        }
+    }
+
+    /// Create a rate limiter whose state is kept in `store` under `key`
+    ///
+    /// Limiters of any process using the same store and key share one
+    /// budget. Keys are `<account>/<operation>`, e.g. `"123/twitter.post"`.
+    pub fn persistent(config: RateLimitConfig, store: RateLimitStore, key: &str) -> Self {
+        Self {
+            persistence: Some(Persistence {
+                store,
+                key: key.to_string(),
+            }),
+            ..Self::new(config)
         }
     }
 
+    /// Also draw every request from `budget`
+    ///
+    /// Used to cap the total rate of all the tools acting for an account.
+    pub fn with_shared_budget(mut self, budget: Arc<RateLimiter>) -> Self {
+        self.shared = Some(budget);
+        self
+    }
+
     /// Create a rate limiter with default configuration
     pub fn default_limiter() -> Self {
         Self::new(RateLimitConfig::default())
@@ -88,59 +249,189 @@ impl RateLimiter {
     /// Acquire permission to make a request
     ///
     /// This will wait if necessary to comply with rate limits.
-    /// Returns an error if rate limit would be exceeded.
+    /// Returns an error if rate limit would be exceeded. A request the shared
+    /// budget refuses spends nothing of this limiter's own budget.
     pub async fn acquire(&self) -> Result<(), TwitterError> {
-        // Check and refill tokens if window has passed
-        self.refill_if_needed().await;
-
-        // Try to consume a token using compare-and-swap for thread safety
-        loop {
-            let current = self.tokens.load(Ordering::SeqCst);
-            if current == 0 {
-                let window_start = self.window_start.lock().await;
-                let elapsed = window_start.elapsed().as_secs();
-                let wait_seconds = self.config.window_seconds.saturating_sub(elapsed);
-                return Err(TwitterError::RateLimited { wait_seconds });
-            }
+        // Don't spend a token here when the shared budget would refuse
+        if let Some(shared) = &self.shared {
+            shared.check_capacity(1).await?;
+        }
 
-            // Atomically try to decrement the token count
-            match self
-                .tokens
-                .compare_exchange(current, current - 1, Ordering::SeqCst, Ordering::SeqCst)
-            {
-                Ok(_) => break,
-                Err(_) => continue, // Another thread modified it, retry
+        let config = self.config;
+        let jitter_ms = jitter(config.jitter_ms);
+        let reservation = self
+            .update(|state, now| {
+                if state.tokens == 0 {
+                    return Err(TwitterError::RateLimited {
+                        wait_seconds: wait_seconds(&config, state, now, 1),
+                    });
+                }
+                state.tokens -= 1;
+
+                // Reserve the next slot respecting the minimum delay
+                let min_delay_ms = config
+                    .min_delay_ms
+                    .saturating_mul(1 << state.backoff_level);
+                let slot = now.max(state.last_request_ms.saturating_add(min_delay_ms)) + jitter_ms;
+                state.last_request_ms = slot;
+                if config.mode == RateLimitMode::SlidingWindow {
+                    state.request_times_ms.push(slot);
+                }
+                Ok(Reservation {
+                    slot_ms: slot,
+                    window_start_ms: state.window_start_ms,
+                    delay_ms: slot - now,
+                })
+            })
+            .await?;
+
+        if let Some(shared) = &self.shared {
+            // The budget may have been drained since it was checked
+            if let Err(e) = Box::pin(shared.acquire()).await {
+                self.refund(&reservation).await?;
+                return Err(e);
             }
         }
+        if reservation.delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(reservation.delay_ms)).await;
+        }
+        Ok(())
+    }
 
-        // Enforce minimum delay between requests
-        let mut last_request = self.last_request.lock().await;
-        let elapsed = last_request.elapsed();
-        let min_delay = Duration::from_millis(self.config.min_delay_ms);
+    /// Give back the token of a reservation whose request did not go ahead
+    async fn refund(&self, reservation: &Reservation) -> Result<(), TwitterError> {
+        let config = self.config;
+        self.update(|state, _| {
+            match config.mode {
+                RateLimitMode::FixedWindow => {
+                    // Spent in a window that has been reset since
+                    if state.window_start_ms != reservation.window_start_ms {
+                        return Ok(());
+                    }
+                    state.tokens = (state.tokens + 1).min(max_requests(&config, state));
+                }
+                RateLimitMode::SlidingWindow => {
+                    let times = &mut state.request_times_ms;
+                    if let Some(index) = times.iter().position(|&t| t == reservation.slot_ms) {
+                        times.remove(index);
+                        state.tokens += 1;
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
 
-        if elapsed < min_delay {
-            let wait_time = min_delay - elapsed;
-            tokio::time::sleep(wait_time).await;
+    /// Check that `count` requests fit in the current window
+    ///
+    /// Nothing is consumed; use this before a batch that should not be
+    /// interrupted half-way by the limit.
+    pub async fn check_capacity(&self, count: u64) -> Result<(), TwitterError> {
+        if let Some(shared) = &self.shared {
+            Box::pin(shared.check_capacity(count)).await?;
         }
 
-        *last_request = Instant::now();
+        let config = self.config;
+        self.update(|state, now| {
+            if state.tokens >= count {
+                return Ok(());
+            }
+            Err(TwitterError::RateLimited {
+                wait_seconds: wait_seconds(&config, state, now, count),
+            })
+        })
+        .await
+    }
+
+    /// Slow down after Twitter reported a rate limit
+    ///
+    /// Spends what is left of the current budget, then halves the budget
+    /// and doubles the minimum delay, down to an eighth of normal. The
+    /// shared budget backs off too.
+    pub async fn back_off(&self) -> Result<(), TwitterError> {
+        let config = self.config;
+        self.update(|state, now| {
+            state.backoff_level = (state.backoff_level + 1).min(MAX_BACKOFF_LEVEL);
+            state.backoff_since_ms = now;
+            state.tokens = 0;
+            state.window_start_ms = now;
+            if config.mode == RateLimitMode::SlidingWindow {
+                // Fill the window so nothing frees up before it has passed
+                state.request_times_ms = vec![now; max_requests(&config, state) as usize];
+            }
+            Ok(())
+        })
+        .await?;
+
+        if let Some(shared) = &self.shared {
+            Box::pin(shared.back_off()).await?;
+        }
         Ok(())
     }
 
-    /// Check if tokens should be refilled
-    async fn refill_if_needed(&self) {
-        let mut window_start = self.window_start.lock().await;
-        let elapsed = window_start.elapsed();
+    /// Run `operation` on the up-to-date state, refilled if the window has
+    /// passed, and save the result
+    async fn update<T>(
+        &self,
+        operation: impl FnOnce(&mut RateLimitState, u64) -> Result<T, TwitterError>,
+    ) -> Result<T, TwitterError> {
+        let mut state = self.state.lock().await;
+        let lock = match &self.persistence {
+            Some(persistence) => {
+                let lock = persistence.store.lock(&persistence.key).await?;
+                if let Some(saved) = lock.load().await {
+                    *state = saved;
+                }
+                Some(lock)
+            }
+            None => None,
+        };
+
+        let now = now_ms();
+        self.refresh(&mut state, now);
+        let result = operation(&mut state, now);
+        if let Some(lock) = lock {
+            lock.save(&state).await?;
+        }
+        self.tokens.store(state.tokens, Ordering::SeqCst);
+        result
+    }
+
+    /// Recover from back-off and refill the tokens as time has passed
+    fn refresh(&self, state: &mut RateLimitState, now: u64) {
+        let window_ms = window_ms(&self.config);
+        let recovery_ms = window_ms.saturating_mul(RECOVERY_WINDOWS).max(1);
+        while state.backoff_level > 0
+            && now.saturating_sub(state.backoff_since_ms) >= recovery_ms
+        {
+            state.backoff_level -= 1;
+            state.backoff_since_ms = state.backoff_since_ms.saturating_add(recovery_ms);
+        }
 
-        if elapsed >= Duration::from_secs(self.config.window_seconds) {
-            // Reset the window
-            *window_start = Instant::now();
-            self.tokens
-                .store(self.config.max_requests as u64, Ordering::SeqCst);
+        let max_requests = max_requests(&self.config, state);
+        match self.config.mode {
+            RateLimitMode::FixedWindow => {
+                if now.saturating_sub(state.window_start_ms) >= window_ms {
+                    // Reset the window
+                    state.window_start_ms = now;
+                    state.tokens = max_requests;
+                }
+                // The budget may have shrunk since the state was saved
+                state.tokens = state.tokens.min(max_requests);
+            }
+            RateLimitMode::SlidingWindow => {
+                // Forget the requests that have left the window
+                let window_start_ms = now.saturating_sub(window_ms);
+                state.request_times_ms.retain(|&time| time > window_start_ms);
+                state.tokens = max_requests.saturating_sub(state.request_times_ms.len() as u64);
+            }
         }
     }
 
     /// Get the number of remaining tokens
+    ///
+    /// For persistent limiters, as of this limiter's last operation.
     pub fn remaining_tokens(&self) -> u64 {
         self.tokens.load(Ordering::SeqCst)
     }
@@ -156,6 +447,49 @@ impl RateLimiter {
     }
 }
 
+/// Seconds until `count` requests fit in the budget
+fn wait_seconds(config: &RateLimitConfig, state: &RateLimitState, now: u64, count: u64) -> u64 {
+    let ready_ms = match config.mode {
+        RateLimitMode::FixedWindow => state.window_start_ms.saturating_add(window_ms(config)),
+        RateLimitMode::SlidingWindow => {
+            // The oldest requests that have to leave the window first
+            let times = &state.request_times_ms;
+            let leaving = (times.len() as u64 + count).saturating_sub(max_requests(config, state));
+            match (leaving as usize).min(times.len()) {
+                0 => now,
+                leaving => times[leaving - 1].saturating_add(window_ms(config)),
+            }
+        }
+    };
+    let remaining = Duration::from_millis(ready_ms.saturating_sub(now));
+    remaining.as_secs_f64().ceil() as u64
+}
+
+fn window_ms(config: &RateLimitConfig) -> u64 {
+    config.window_seconds.saturating_mul(1000)
+}
+
+/// Budget per window after back-off
+fn max_requests(config: &RateLimitConfig, state: &RateLimitState) -> u64 {
+    (config.max_requests as u64 >> state.backoff_level).max(1)
+}
+
+/// Random delay of up to `max_ms` milliseconds
+fn jitter(max_ms: u64) -> u64 {
+    if max_ms == 0 {
+        return 0;
+    }
+    // v4 UUIDs are random, which saves a dependency on a random crate
+    (uuid::Uuid::new_v4().as_u128() % (max_ms as u128 + 1)) as u64
+}
+
+/// Milliseconds since the Unix epoch
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
 impl Default for RateLimiter {
     fn default() -> Self {
         Self::default_limiter()
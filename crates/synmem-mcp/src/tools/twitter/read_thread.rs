@@ -1,11 +1,23 @@
 //! Twitter read thread tool
 //!
-//! Reads an entire Twitter thread and extracts all tweets.
+//! Opens a tweet's conversation page and follows its author's self-reply
+//! chain, scrolling until the chain ends or `max_tweets` is reached.
 
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+
+use super::page::{self, TWEET_SELECTOR, TWITTER_BASE_URL};
+use super::parser::parse_tweets;
 use super::{
     RateLimiter, RateLimitConfig, Tweet, TwitterError, TwitterReadThreadInput,
     TwitterReadThreadResult, TwitterSession,
 };
+use crate::tools::Progress;
+
+/// Maximum number of scrolls while following a thread
+const MAX_SCROLLS: usize = 20;
 
 /// Read a Twitter thread
 ///
@@ -13,6 +25,8 @@ use super::{
 /// * `input` - The thread URL or ID and options
 /// * `session` - Valid Twitter session
 /// * `rate_limiter` - Rate limiter to prevent API abuse
+/// * `driver` - Browser driver used to load the conversation
+/// * `progress` - Receives the number of tweets collected out of `max_tweets`
 ///
 /// # Returns
 /// Result containing all tweets in the thread or an error
@@ -22,16 +36,20 @@ use super::{
 /// let input = TwitterReadThreadInput {
 ///     tweet_url_or_id: "https://twitter.com/user/status/123456".to_string(),
 ///     max_tweets: 50,
+///     download_media: false,
 /// };
-/// let result = twitter_read_thread(input, &session, &rate_limiter).await?;
+/// let result =
+///     twitter_read_thread(input, &session, &rate_limiter, &driver, &Progress::none()).await?;
 /// for tweet in result.tweets {
 ///     println!("{}: {}", tweet.author, tweet.text);
 /// }
 /// ```
-pub async fn twitter_read_thread(
+pub async fn twitter_read_thread<D: BrowserDriverPort>(
     input: TwitterReadThreadInput,
     session: &TwitterSession,
     rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+    progress: &Progress,
 ) -> Result<TwitterReadThreadResult, TwitterError> {
     // Validate input
     let tweet_id = extract_tweet_id(&input.tweet_url_or_id)?;
@@ -42,35 +60,135 @@ pub async fn twitter_read_thread(
     // Validate session
     validate_session(session)?;
 
-    // In a real implementation, this would:
-    // 1. Navigate to the tweet URL
-    // 2. Extract the main tweet and all replies in the thread
-    // 3. Follow the conversation thread
-    // 4. Collect all tweets up to max_tweets
-
-    // For now, return a placeholder result
-    let placeholder_tweet = Tweet {
-        id: tweet_id.clone(),
-        text: "Placeholder tweet text".to_string(),
-        author: "placeholder_user".to_string(),
-        author_display_name: "Placeholder User".to_string(),
-        timestamp: "2024-01-01T00:00:00Z".to_string(),
-        likes: 0,
-        retweets: 0,
-        replies: 0,
-        media: vec![],
-    };
+    let url = format!("{}/i/status/{}", TWITTER_BASE_URL, tweet_id);
+    if !page::open(driver, &url, TWEET_SELECTOR).await? {
+        return Err(TwitterError::TweetNotFound { tweet_id });
+    }
+
+    let mut thread = ThreadCollector::new(tweet_id.clone(), input.max_tweets);
+    let mut scrolls = 0;
+    loop {
+        let snapshot = parse_tweets(&page::html(driver).await?);
+        let grew = thread.add_snapshot(snapshot);
+        if !thread.found() {
+            return Err(TwitterError::TweetNotFound { tweet_id });
+        }
+        progress.report(thread.len() as u64, Some(input.max_tweets as u64));
+
+        if thread.is_complete() || (scrolls > 0 && !grew) || scrolls == MAX_SCROLLS {
+            break;
+        }
+        page::scroll(driver).await?;
+        scrolls += 1;
+    }
 
+    let tweets = thread.into_tweets();
     Ok(TwitterReadThreadResult {
         success: true,
-        tweets: vec![placeholder_tweet],
-        total_count: 1,
+        total_count: tweets.len(),
+        tweets,
         error: None,
     })
 }
 
+/// Accumulates the author's self-reply chain across page snapshots
+///
+/// The conversation view lists the focal tweet's ancestors above it and
+/// the author's own replies directly below it, followed by replies from
+/// other accounts. The chain is the run of tweets by the focal tweet's
+/// author around the focal tweet.
+struct ThreadCollector {
+    focal_id: String,
+    max_tweets: usize,
+    author: Option<String>,
+    tweets: Vec<Tweet>,
+    seen: HashSet<String>,
+    ended: bool,
+}
+
+impl ThreadCollector {
+    fn new(focal_id: String, max_tweets: usize) -> Self {
+        Self {
+            focal_id,
+            max_tweets,
+            author: None,
+            tweets: Vec::new(),
+            seen: HashSet::new(),
+            ended: false,
+        }
+    }
+
+    /// Merge the tweets currently rendered on the page
+    ///
+    /// Returns whether the snapshot contained any tweet not seen before.
+    fn add_snapshot(&mut self, snapshot: Vec<Tweet>) -> bool {
+        let mut grew = false;
+        for tweet in &snapshot {
+            grew |= self.seen.insert(tweet.id.clone());
+        }
+
+        let start = match &self.author {
+            None => match snapshot.iter().position(|t| t.id == self.focal_id) {
+                Some(focal) => {
+                    let author = snapshot[focal].author.clone();
+                    let first = snapshot[..focal]
+                        .iter()
+                        .rposition(|t| !t.author.eq_ignore_ascii_case(&author))
+                        .map_or(0, |i| i + 1);
+                    self.author = Some(author);
+                    first
+                }
+                None => return grew,
+            },
+            Some(_) => self
+                .tweets
+                .last()
+                .and_then(|last| snapshot.iter().position(|t| t.id == last.id))
+                .map_or(0, |i| i + 1),
+        };
+
+        self.follow(snapshot.into_iter().skip(start));
+        grew
+    }
+
+    fn follow(&mut self, tweets: impl Iterator<Item = Tweet>) {
+        let Some(author) = self.author.clone() else {
+            return;
+        };
+        for tweet in tweets {
+            if self.is_complete() {
+                return;
+            }
+            if !tweet.author.eq_ignore_ascii_case(&author) {
+                self.ended = true;
+                return;
+            }
+            if !self.tweets.iter().any(|t| t.id == tweet.id) {
+                self.tweets.push(tweet);
+            }
+        }
+    }
+
+    /// Whether the focal tweet has been found
+    fn found(&self) -> bool {
+        self.author.is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.tweets.len()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.ended || self.tweets.len() >= self.max_tweets
+    }
+
+    fn into_tweets(self) -> Vec<Tweet> {
+        self.tweets
+    }
+}
+
 /// Extract tweet ID from URL or use as-is if already an ID
-fn extract_tweet_id(url_or_id: &str) -> Result<String, TwitterError> {
+pub(super) fn extract_tweet_id(url_or_id: &str) -> Result<String, TwitterError> {
     // If it's already a numeric ID, return it
     if url_or_id.chars().all(|c| c.is_ascii_digit()) {
         return Ok(url_or_id.to_string());
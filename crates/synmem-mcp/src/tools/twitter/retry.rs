@@ -0,0 +1,101 @@
+//! Retries of failed Twitter operations
+//!
+//! Errors say themselves whether they are worth retrying
+//! ([`TwitterError::is_recoverable`]) and how long to wait before trying
+//! again ([`TwitterError::retry_after`]). [`retry`] runs an operation until it
+//! succeeds, fails for good, or the [`RetryPolicy`] runs out of attempts or
+//! time, and keeps a record of the failed attempts.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::debug;
+
+use super::TwitterError;
+use crate::config::RetryPolicy;
+
+/// A failed attempt that was retried
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RetryAttempt {
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    /// Why the attempt failed
+    pub error: String,
+    /// Delay before the next attempt in milliseconds
+    pub backoff_ms: u64,
+}
+
+/// Final result of an operation and the failed attempts before it
+#[derive(Debug)]
+pub struct RetryOutcome<T> {
+    /// Result of the last attempt
+    pub result: Result<T, TwitterError>,
+    /// Attempts that failed and were retried, oldest first
+    pub attempts: Vec<RetryAttempt>,
+}
+
+/// Run `operation`, retrying recoverable errors according to `policy`
+///
+/// The delay doubles from `initial_backoff_ms` after each failure, up to
+/// `max_backoff_ms`, but is never shorter than the error's own
+/// `retry_after`: retrying sooner would fail the same way. An error is
+/// returned as is when it is not recoverable, when no attempt is left, or
+/// when waiting would pass the deadline.
+///
+/// # Example
+/// ```ignore
+/// let outcome =
+///     retry(&policy, || twitter_search(input.clone(), &session, &limiter, &driver)).await;
+/// ```
+pub async fn retry<T, F, Fut>(policy: &RetryPolicy, mut operation: F) -> RetryOutcome<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, TwitterError>>,
+{
+    let started = Instant::now();
+    let deadline = Duration::from_secs(policy.deadline_seconds);
+    let mut attempts = Vec::new();
+    let mut attempt = 1;
+    loop {
+        let error = match operation().await {
+            Ok(value) => {
+                return RetryOutcome {
+                    result: Ok(value),
+                    attempts,
+                }
+            }
+            Err(error) => error,
+        };
+
+        let backoff = backoff(policy, attempt, error.retry_after());
+        if !error.is_recoverable()
+            || attempt >= policy.max_attempts
+            || started.elapsed() + backoff > deadline
+        {
+            return RetryOutcome {
+                result: Err(error),
+                attempts,
+            };
+        }
+
+        debug!(attempt, error = %error, backoff_ms = backoff.as_millis() as u64, "Retrying");
+        attempts.push(RetryAttempt {
+            attempt,
+            error: error.to_string(),
+            backoff_ms: backoff.as_millis() as u64,
+        });
+        tokio::time::sleep(backoff).await;
+        attempt += 1;
+    }
+}
+
+/// Delay after the `attempt`-th failure
+fn backoff(policy: &RetryPolicy, attempt: u32, retry_after_seconds: Option<u64>) -> Duration {
+    let exponential = policy
+        .initial_backoff_ms
+        .saturating_mul(1u64.checked_shl(attempt - 1).unwrap_or(u64::MAX));
+    let backoff_ms = exponential.min(policy.max_backoff_ms);
+    let retry_after_ms = retry_after_seconds.map_or(0, |seconds| seconds.saturating_mul(1000));
+    Duration::from_millis(backoff_ms.max(retry_after_ms))
+}
@@ -0,0 +1,507 @@
+//! Scheduled tweets
+//!
+//! Tweets are queued with a publish time in the server
+//! [`Storage`](crate::storage::Storage), one JSON entry per tweet under
+//! `twitter/scheduled/<account>/<id>`, so the queue survives restarts.
+//! [`run_scheduler`] posts them once due, drawing from the same post
+//! [`RateLimiter`] as `twitter_post`: when its budget is spent, the tweet
+//! waits until the limiter allows it instead of failing.
+//!
+//! An entry is marked `publishing` while it is posted and can no longer be
+//! rescheduled or cancelled: the status checks and the changes they allow
+//! happen under a lock shared by every [`PostQueue`] of the account. With a
+//! lock directory (see [`PostQueue::with_lock_dir`]) the lock is also
+//! shared with the other processes using it, and the worker publishing an
+//! entry holds a claim on it, so no entry is posted twice. A tweet the
+//! browser did not confirm is not retried, as it may have gone out anyway;
+//! neither is an entry left `publishing` by a crash, which is marked failed
+//! once nobody holds its claim. Tweets X blocks before they are composed
+//! (rate limit, login wall, missing session) stay pending.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use chrono::Utc;
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tokio::sync::{Mutex, MutexGuard};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::file_lock::FileLock;
+use super::post::validate_post_input;
+use super::{
+    twitter_post, RateLimiter, ScheduledPost, ScheduledPostStatus, TwitterCancelScheduledInput,
+    TwitterError, TwitterListScheduledInput, TwitterListScheduledResult,
+    TwitterReschedulePostInput, TwitterSchedulePostInput, TwitterScheduledPostResult,
+    TwitterSession,
+};
+use crate::storage::Storage;
+use crate::tools::{BrowserTurn, LeasedDriver};
+
+/// Storage key prefix of the queue
+const QUEUE_PREFIX: &str = "twitter/scheduled";
+
+/// How often the queue is checked for due tweets
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How long to wait before trying again a tweet that needs a human, e.g.
+/// to log in again
+const BLOCKED_WAIT_SECONDS: u64 = 900;
+
+/// How long to wait for another process to release the queue
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tweets scheduled for one account
+#[derive(Clone)]
+pub struct PostQueue {
+    storage: Arc<Storage>,
+    prefix: String,
+    /// Serializes the changes that depend on an entry's status
+    lock: Arc<Mutex<()>>,
+    /// Where the locks shared with other processes are kept, if any
+    lock_dir: Option<PathBuf>,
+}
+
+/// Exclusive access to a queue, released on drop
+struct QueueGuard<'a> {
+    _guard: MutexGuard<'a, ()>,
+    _lock: Option<FileLock>,
+}
+
+/// A worker's claim on the entry it is publishing, released on drop
+struct Claim {
+    _lock: Option<FileLock>,
+}
+
+impl PostQueue {
+    /// Queue of `account` (see [`TwitterSession::account_key`]) in `storage`
+    pub fn new(storage: Arc<Storage>, account: &str) -> Self {
+        let prefix = format!("{}/{}/", QUEUE_PREFIX, account);
+        Self {
+            storage,
+            lock: account_lock(&prefix),
+            prefix,
+            lock_dir: None,
+        }
+    }
+
+    /// Also share the queue's lock with the other processes keeping their
+    /// locks under `root`
+    ///
+    /// Processes sharing a queue's storage need this not to post its
+    /// entries twice.
+    pub fn with_lock_dir(mut self, root: impl Into<PathBuf>) -> Self {
+        self.lock_dir = Some(root.into().join(&self.prefix));
+        self
+    }
+
+    /// Every entry, by publish time
+    ///
+    /// Entries that cannot be read are skipped.
+    pub async fn list(&self) -> Result<Vec<ScheduledPost>, TwitterError> {
+        let keys = self
+            .storage
+            .list_keys(Some(&self.prefix))
+            .await
+            .map_err(storage_error)?;
+        let mut posts = Vec::with_capacity(keys.len());
+        for key in keys {
+            let Some(json) = self.storage.retrieve(&key).await.map_err(storage_error)? else {
+                continue;
+            };
+            match serde_json::from_str::<ScheduledPost>(&json) {
+                Ok(post) => posts.push(post),
+                Err(e) => warn!(key = %key, error = %e, "Skipping unreadable scheduled post"),
+            }
+        }
+        posts.sort_by_key(|post| post.publish_at);
+        Ok(posts)
+    }
+
+    /// The entry with `id`, if any
+    pub async fn get(&self, id: &str) -> Result<Option<ScheduledPost>, TwitterError> {
+        let Some(key) = self.key(id) else {
+            return Ok(None);
+        };
+        let json = self.storage.retrieve(&key).await.map_err(storage_error)?;
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    /// Add or update an entry
+    pub async fn save(&self, post: &ScheduledPost) -> Result<(), TwitterError> {
+        let key = self.key(&post.id).ok_or_else(|| invalid_id(&post.id))?;
+        let json = serde_json::to_string_pretty(post).expect("scheduled post serializes");
+        self.storage.store(&key, &json).await.map_err(storage_error)
+    }
+
+    /// Remove the entry with `id`
+    pub async fn remove(&self, id: &str) -> Result<(), TwitterError> {
+        let key = self.key(id).ok_or_else(|| invalid_id(id))?;
+        self.storage.delete(&key).await.map_err(storage_error)
+    }
+
+    /// Hold off the other changes to the account's queue
+    async fn lock(&self) -> Result<QueueGuard<'_>, TwitterError> {
+        let guard = self.lock.lock().await;
+        let lock = match &self.lock_dir {
+            Some(dir) => {
+                let lock = FileLock::acquire(&dir.join("queue.lock"), LOCK_TIMEOUT)
+                    .await
+                    .map_err(lock_error)?;
+                Some(lock.ok_or_else(|| TwitterError::Unknown {
+                    message: "The scheduled post queue stayed locked".to_string(),
+                })?)
+            }
+            None => None,
+        };
+        Ok(QueueGuard {
+            _guard: guard,
+            _lock: lock,
+        })
+    }
+
+    /// Claim the entry with `id` for publishing, `None` when another
+    /// process holds the claim
+    async fn claim(&self, id: &str) -> Result<Option<Claim>, TwitterError> {
+        let Some(dir) = &self.lock_dir else {
+            return Ok(Some(Claim { _lock: None }));
+        };
+        let lock = FileLock::acquire(&dir.join(format!("{}.lock", id)), Duration::ZERO)
+            .await
+            .map_err(lock_error)?;
+        Ok(lock.map(|lock| Claim { _lock: Some(lock) }))
+    }
+
+    /// Storage key of `id`, `None` when it is not a queue entry ID
+    fn key(&self, id: &str) -> Option<String> {
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        valid.then(|| format!("{}{}", self.prefix, id))
+    }
+}
+
+/// Queue a tweet to be posted at `input.publish_at`
+///
+/// The tweet is validated now, so that it does not fail for a bad input
+/// hours later.
+///
+/// # Example
+/// ```ignore
+/// let input = TwitterSchedulePostInput {
+///     post: TwitterPostInput {
+///         text: "Good morning!".to_string(),
+///         media_urls: vec![],
+///         reply_to: None,
+///     },
+///     publish_at: Utc::now() + chrono::Duration::hours(8),
+/// };
+/// let result = twitter_schedule_post(input, &queue).await?;
+/// ```
+pub async fn twitter_schedule_post(
+    input: TwitterSchedulePostInput,
+    queue: &PostQueue,
+) -> Result<TwitterScheduledPostResult, TwitterError> {
+    validate_post_input(&input.post)?;
+    validate_publish_time(&input.publish_at)?;
+
+    let post = ScheduledPost {
+        id: Uuid::new_v4().to_string(),
+        post: input.post,
+        publish_at: input.publish_at,
+        created_at: Utc::now(),
+        status: ScheduledPostStatus::Pending,
+        not_before: None,
+        tweet_id: None,
+        tweet_url: None,
+        error: None,
+    };
+    queue.save(&post).await?;
+    info!(id = %post.id, publish_at = %post.publish_at, "Scheduled tweet");
+    Ok(entry_result(post))
+}
+
+/// List the scheduled tweets, by publish time
+pub async fn twitter_list_scheduled(
+    input: TwitterListScheduledInput,
+    queue: &PostQueue,
+) -> Result<TwitterListScheduledResult, TwitterError> {
+    let posts = queue
+        .list()
+        .await?
+        .into_iter()
+        .filter(|post| !input.status.is_some_and(|status| post.status != status))
+        .collect();
+    Ok(TwitterListScheduledResult {
+        success: true,
+        posts,
+        error: None,
+    })
+}
+
+/// Move a pending tweet to another publish time
+pub async fn twitter_reschedule_post(
+    input: TwitterReschedulePostInput,
+    queue: &PostQueue,
+) -> Result<TwitterScheduledPostResult, TwitterError> {
+    validate_publish_time(&input.publish_at)?;
+    let _guard = queue.lock().await?;
+    let mut post = pending(queue, &input.id).await?;
+    post.publish_at = input.publish_at;
+    post.not_before = None;
+    queue.save(&post).await?;
+    info!(id = %post.id, publish_at = %post.publish_at, "Rescheduled tweet");
+    Ok(entry_result(post))
+}
+
+/// Remove a pending tweet from the queue
+pub async fn twitter_cancel_scheduled(
+    input: TwitterCancelScheduledInput,
+    queue: &PostQueue,
+) -> Result<TwitterScheduledPostResult, TwitterError> {
+    let _guard = queue.lock().await?;
+    let post = pending(queue, &input.id).await?;
+    queue.remove(&post.id).await?;
+    info!(id = %post.id, "Cancelled scheduled tweet");
+    Ok(entry_result(post))
+}
+
+/// Post the tweets of `queue` as they become due, until the task is dropped
+///
+/// Checks the queue every few seconds. `browser_lease` is the lease the
+/// MCP tools take their turns at `driver`'s page from.
+pub async fn run_scheduler<D: BrowserDriverPort>(
+    queue: PostQueue,
+    session: Arc<TwitterSession>,
+    rate_limiter: Arc<RateLimiter>,
+    driver: Arc<D>,
+    browser_lease: Arc<Mutex<()>>,
+) {
+    if let Err(e) = fail_interrupted(&queue).await {
+        warn!(error = %e, "Failed to check for interrupted scheduled posts");
+    }
+    loop {
+        if let Err(e) = publish_due(&queue, &session, &rate_limiter, &driver, &browser_lease).await {
+            warn!(error = %e, "Failed to publish scheduled posts");
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Post every due tweet of `queue`, returning how many were processed
+///
+/// Stops early when the rate limiter refuses a post, or X blocks it behind
+/// a rate limit or login page; it and the tweets after it are tried again
+/// later. Fails with [`TwitterError::NoSession`] without a session, leaving
+/// the due tweets pending. Each tweet is posted during a turn at the page
+/// taken from `browser_lease`, so it does not navigate the page away from
+/// under a running tool.
+pub async fn publish_due<D: BrowserDriverPort>(
+    queue: &PostQueue,
+    session: &TwitterSession,
+    rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+    browser_lease: &Arc<Mutex<()>>,
+) -> Result<usize, TwitterError> {
+    let due: Vec<String> = queue
+        .list()
+        .await?
+        .into_iter()
+        .filter(is_due)
+        .map(|post| post.id)
+        .collect();
+    if due.is_empty() {
+        return Ok(0);
+    }
+    validate_session(session)?;
+
+    let mut processed = 0;
+    for id in due {
+        let (mut post, _claim) = {
+            let _guard = queue.lock().await?;
+            // It may have been rescheduled, cancelled or published since the
+            // listing
+            let Some(mut post) = queue.get(&id).await?.filter(is_due) else {
+                continue;
+            };
+            let Some(claim) = queue.claim(&id).await? else {
+                continue;
+            };
+            post.status = ScheduledPostStatus::Publishing;
+            queue.save(&post).await?;
+            (post, claim)
+        };
+
+        let browser = Arc::new(LeasedDriver::new(
+            driver.clone(),
+            browser_lease.clone(),
+            BrowserTurn::default(),
+        ));
+        let posted = twitter_post(post.post.clone(), session, rate_limiter, &browser).await;
+        drop(browser);
+        match posted {
+            Ok(result) if result.success => {
+                info!(id = %post.id, tweet_id = ?result.tweet_id, "Published scheduled tweet");
+                post.status = ScheduledPostStatus::Published;
+                post.tweet_id = result.tweet_id;
+                post.tweet_url = result.tweet_url;
+                post.error = None;
+            }
+            Ok(result) => {
+                post.status = ScheduledPostStatus::Failed;
+                post.error = result.error;
+            }
+            Err(e) if is_blocked(&e) => {
+                if let TwitterError::Throttled { .. } = e {
+                    if let Err(e) = rate_limiter.back_off().await {
+                        warn!(error = %e, "Failed to back off the rate limiter");
+                    }
+                }
+                let wait_seconds = match e {
+                    TwitterError::RateLimited { wait_seconds }
+                    | TwitterError::Throttled { wait_seconds } => wait_seconds,
+                    _ => BLOCKED_WAIT_SECONDS,
+                };
+                warn!(id = %post.id, error = %e, wait_seconds, "Deferred scheduled tweet");
+                post.status = ScheduledPostStatus::Pending;
+                post.not_before = Some(Utc::now() + chrono::Duration::seconds(wait_seconds as i64));
+                queue.save(&post).await?;
+                break;
+            }
+            Err(e) => {
+                post.status = ScheduledPostStatus::Failed;
+                post.error = Some(e.to_string());
+            }
+        }
+        if post.status == ScheduledPostStatus::Failed {
+            warn!(id = %post.id, error = ?post.error, "Failed to publish scheduled tweet");
+        }
+        queue.save(&post).await?;
+        processed += 1;
+    }
+    Ok(processed)
+}
+
+/// Mark the entries a previous run left `publishing` as failed
+///
+/// Entries another process is still publishing are left alone.
+async fn fail_interrupted(queue: &PostQueue) -> Result<(), TwitterError> {
+    let _guard = queue.lock().await?;
+    for post in queue.list().await? {
+        if post.status != ScheduledPostStatus::Publishing {
+            continue;
+        }
+        let Some(_claim) = queue.claim(&post.id).await? else {
+            continue;
+        };
+        // Its worker may have finished before releasing the claim
+        let Some(mut post) = queue.get(&post.id).await? else {
+            continue;
+        };
+        if post.status == ScheduledPostStatus::Publishing {
+            warn!(id = %post.id, "Scheduled tweet was interrupted while publishing");
+            post.status = ScheduledPostStatus::Failed;
+            post.error = Some("Interrupted while publishing; it may have been posted".to_string());
+            queue.save(&post).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether `error` stopped the post before it was composed, for a reason
+/// that clears up with time or once a human acts
+fn is_blocked(error: &TwitterError) -> bool {
+    matches!(
+        error,
+        TwitterError::RateLimited { .. } | TwitterError::Throttled { .. } | TwitterError::NoSession
+    ) || error.needs_human()
+}
+
+fn is_due(post: &ScheduledPost) -> bool {
+    let earliest = post.not_before.map_or(post.publish_at, |not_before| {
+        not_before.max(post.publish_at)
+    });
+    post.status == ScheduledPostStatus::Pending && earliest <= Utc::now()
+}
+
+/// The pending entry with `id`
+async fn pending(queue: &PostQueue, id: &str) -> Result<ScheduledPost, TwitterError> {
+    let post = queue
+        .get(id)
+        .await?
+        .ok_or_else(|| TwitterError::InvalidInput {
+            message: format!("No scheduled post with ID {}", id),
+        })?;
+    if post.status != ScheduledPostStatus::Pending {
+        return Err(TwitterError::InvalidInput {
+            message: format!(
+                "Scheduled post {} is already {}",
+                id,
+                status_name(post.status)
+            ),
+        });
+    }
+    Ok(post)
+}
+
+fn status_name(status: ScheduledPostStatus) -> &'static str {
+    match status {
+        ScheduledPostStatus::Pending => "pending",
+        ScheduledPostStatus::Publishing => "publishing",
+        ScheduledPostStatus::Published => "published",
+        ScheduledPostStatus::Failed => "failed",
+    }
+}
+
+fn validate_publish_time(publish_at: &chrono::DateTime<Utc>) -> Result<(), TwitterError> {
+    if *publish_at <= Utc::now() {
+        return Err(TwitterError::InvalidInput {
+            message: "Publish time must be in the future".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Validate the Twitter session
+fn validate_session(session: &TwitterSession) -> Result<(), TwitterError> {
+    if !session.is_authenticated() {
+        return Err(TwitterError::NoSession);
+    }
+    Ok(())
+}
+
+/// Lock shared by the queues of the account with `prefix`
+fn account_lock(prefix: &str) -> Arc<Mutex<()>> {
+    static LOCKS: OnceLock<std::sync::Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    locks.entry(prefix.to_string()).or_default().clone()
+}
+
+fn entry_result(post: ScheduledPost) -> TwitterScheduledPostResult {
+    TwitterScheduledPostResult {
+        success: true,
+        post: Some(post),
+        error: None,
+    }
+}
+
+fn invalid_id(id: &str) -> TwitterError {
+    TwitterError::InvalidInput {
+        message: format!("Invalid scheduled post ID: {}", id),
+    }
+}
+
+fn lock_error(error: std::io::Error) -> TwitterError {
+    TwitterError::Unknown {
+        message: format!("Failed to lock the scheduled post queue: {}", error),
+    }
+}
+
+fn storage_error(error: crate::storage::StorageError) -> TwitterError {
+    TwitterError::Unknown {
+        message: format!("Scheduled post storage failed: {}", error),
+    }
+}
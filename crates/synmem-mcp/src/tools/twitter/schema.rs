@@ -0,0 +1,441 @@
+//! JSON Schemas for the Twitter tool inputs
+
+use serde_json::{json, Value};
+
+use super::types::{
+    default_conversation_count, default_max_tweets, default_message_count,
+    default_notification_count, default_search_count, default_timeline_count,
+};
+use super::{
+    NotificationTab, ScheduledPostStatus, SearchFilter, TimelineType, TwitterCancelScheduledInput,
+    TwitterGetNotificationsInput, TwitterGetProfileInput, TwitterGetTimelineInput,
+    TwitterListConversationsInput, TwitterListScheduledInput, TwitterPostInput,
+    TwitterPostThreadInput, TwitterReadDmInput, TwitterReadThreadInput, TwitterReschedulePostInput,
+    TwitterSchedulePostInput, TwitterSearchInput, TwitterSendDmInput, TwitterTweetActionInput,
+    TwitterUserActionInput, DM_MAX_LENGTH, MAX_MEDIA_PER_TWEET, MAX_RESULTS_PER_REQUEST,
+    MAX_THREAD_LENGTH, TWEET_MAX_LENGTH, USERNAME_MAX_LENGTH,
+};
+use crate::schema::{InputSchema, Schema};
+
+impl InputSchema for TwitterPostInput {
+    fn input_schema() -> Value {
+        post_schema().into_value()
+    }
+}
+
+/// Properties of a tweet, shared with scheduled tweets
+fn post_schema() -> Schema {
+    Schema::object()
+        .required_property(
+            "text",
+            Schema::string()
+                .max_length(TWEET_MAX_LENGTH)
+                .description("The text content of the tweet"),
+        )
+        .property(
+            "media_urls",
+            Schema::array(Schema::string())
+                .max_items(MAX_MEDIA_PER_TWEET)
+                .default_value(Vec::<String>::new())
+                .description("Media to attach (http(s) or data: URLs)"),
+        )
+        .property(
+            "reply_to",
+            Schema::string()
+                .nullable()
+                .description("Tweet ID to reply to"),
+        )
+}
+
+impl InputSchema for TwitterSchedulePostInput {
+    fn input_schema() -> Value {
+        post_schema()
+            .required_property(
+                "publish_at",
+                date_time_schema().description("When to post the tweet, e.g. 2025-06-01T09:00:00Z"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterListScheduledInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .property(
+                "status",
+                Schema::enumeration(&ScheduledPostStatus::ALL)
+                    .nullable()
+                    .description("Only list the scheduled tweets with this status"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterReschedulePostInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property("id", scheduled_id_schema())
+            .required_property(
+                "publish_at",
+                date_time_schema().description("New time to post the tweet"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterCancelScheduledInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property("id", scheduled_id_schema())
+            .into_value()
+    }
+}
+
+/// An RFC 3339 timestamp
+fn date_time_schema() -> Schema {
+    Schema::string().with("format", json!("date-time"))
+}
+
+fn scheduled_id_schema() -> Schema {
+    Schema::string()
+        .min_length(1)
+        .description("ID of the scheduled tweet")
+}
+
+impl InputSchema for TwitterPostThreadInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .property(
+                "segments",
+                Schema::array(Schema::string().min_length(1).max_length(TWEET_MAX_LENGTH))
+                    .max_items(MAX_THREAD_LENGTH)
+                    .description("The tweets of the thread, in order"),
+            )
+            .property(
+                "text",
+                Schema::string()
+                    .nullable()
+                    .description("Long text to split into numbered tweets (instead of segments)"),
+            )
+            .property(
+                "rollback_on_failure",
+                Schema::boolean()
+                    .default_value(false)
+                    .description("Delete the tweets already posted if one fails"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterReadThreadInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property(
+                "tweet_url_or_id",
+                Schema::string()
+                    .min_length(1)
+                    .description("The URL or ID of the tweet to read the thread from"),
+            )
+            .property(
+                "max_tweets",
+                Schema::integer()
+                    .minimum(1)
+                    .default_value(default_max_tweets())
+                    .description("Maximum number of tweets to retrieve"),
+            )
+            .property("download_media", download_media_schema())
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterSearchInput {
+    fn input_schema() -> Value {
+        let username = |description: &str| {
+            Schema::string()
+                .min_length(1)
+                .max_length(USERNAME_MAX_LENGTH + 1)
+                .description(description)
+        };
+        let date = |description: &str| {
+            Schema::string()
+                .with("pattern", json!(r"^\d{4}-\d{2}-\d{2}$"))
+                .description(description)
+        };
+        Schema::object()
+            .property(
+                "query",
+                Schema::string().description(
+                    "Free-text search query, combined with the operators below. \
+                     Optional when an operator is set",
+                ),
+            )
+            .property("from", username("Only tweets posted by this account"))
+            .property("to", username("Only tweets replying to this account"))
+            .property(
+                "mentions",
+                Schema::array(username("Username")).description(
+                    "Only tweets mentioning all of these accounts, with or without the @",
+                ),
+            )
+            .property(
+                "hashtags",
+                Schema::array(Schema::string().min_length(1))
+                    .description("Only tweets with all of these hashtags, with or without the #"),
+            )
+            .property("since", date("Only tweets posted on or after this date (YYYY-MM-DD)"))
+            .property("until", date("Only tweets posted before this date (YYYY-MM-DD)"))
+            .property(
+                "min_likes",
+                Schema::integer()
+                    .minimum(0)
+                    .description("Only tweets with at least this many likes"),
+            )
+            .property(
+                "min_retweets",
+                Schema::integer()
+                    .minimum(0)
+                    .description("Only tweets with at least this many retweets"),
+            )
+            .property(
+                "lang",
+                Schema::string()
+                    .min_length(2)
+                    .max_length(2)
+                    .description("Only tweets in this language (ISO 639-1 code, e.g. \"en\")"),
+            )
+            .property(
+                "has_media",
+                Schema::boolean()
+                    .default_value(false)
+                    .description("Only tweets with images or videos"),
+            )
+            .property(
+                "exclude_replies",
+                Schema::boolean()
+                    .default_value(false)
+                    .description("Leave out replies"),
+            )
+            .property(
+                "exclude_retweets",
+                Schema::boolean()
+                    .default_value(false)
+                    .description("Leave out retweets"),
+            )
+            .property(
+                "count",
+                Schema::integer()
+                    .minimum(1)
+                    .maximum(MAX_RESULTS_PER_REQUEST as i64)
+                    .default_value(default_search_count())
+                    .description("Maximum number of results"),
+            )
+            .property(
+                "filter",
+                Schema::enumeration(&SearchFilter::ALL)
+                    .default_value(SearchFilter::default())
+                    .description("Result filter"),
+            )
+            .property(
+                "cursor",
+                Schema::string()
+                    .nullable()
+                    .description("Cursor for pagination"),
+            )
+            .property("download_media", download_media_schema())
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterGetTimelineInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .property(
+                "timeline_type",
+                Schema::one_of(vec![
+                    Schema::enumeration(&TimelineType::SIMPLE),
+                    timeline_variant(
+                        "list",
+                        "id",
+                        Schema::string().min_length(1).description("Numeric list ID"),
+                    ),
+                    timeline_variant("likes", "username", username_schema()),
+                    timeline_variant("replies", "username", username_schema()),
+                    timeline_variant("media", "username", username_schema()),
+                ])
+                .default_value(TimelineType::default())
+                .description("Type of timeline to retrieve"),
+            )
+            .property(
+                "username",
+                Schema::string()
+                    .max_length(USERNAME_MAX_LENGTH)
+                    .nullable()
+                    .description("Username (required for the user timeline)"),
+            )
+            .property(
+                "count",
+                Schema::integer()
+                    .minimum(1)
+                    .maximum(MAX_RESULTS_PER_REQUEST as i64)
+                    .default_value(default_timeline_count())
+                    .description("Maximum number of tweets to retrieve"),
+            )
+            .property(
+                "cursor",
+                Schema::string()
+                    .nullable()
+                    .description("Cursor for pagination"),
+            )
+            .property("download_media", download_media_schema())
+            .into_value()
+    }
+}
+
+/// A timeline variant with one parameter, e.g. `{"list": {"id": "1234"}}`
+fn timeline_variant(variant: &str, field: &str, schema: Schema) -> Schema {
+    Schema::object().required_property(variant, Schema::object().required_property(field, schema))
+}
+
+fn download_media_schema() -> Schema {
+    Schema::boolean()
+        .default_value(false)
+        .description("Download the media of the tweets into the local media store")
+}
+
+fn username_schema() -> Schema {
+    Schema::string()
+        .min_length(1)
+        .max_length(USERNAME_MAX_LENGTH)
+        .description("Username without the @")
+}
+
+impl InputSchema for TwitterTweetActionInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property(
+                "tweet_url_or_id",
+                Schema::string()
+                    .min_length(1)
+                    .description("The URL or ID of the tweet"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterUserActionInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property(
+                "username",
+                Schema::string()
+                    .min_length(1)
+                    .max_length(USERNAME_MAX_LENGTH + 1)
+                    .description("Username of the account, with or without the leading @"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterGetProfileInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property(
+                "username",
+                Schema::string()
+                    .min_length(1)
+                    .max_length(USERNAME_MAX_LENGTH + 1)
+                    .description("Username of the account, with or without the leading @"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterGetNotificationsInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .property(
+                "tab",
+                Schema::enumeration(&NotificationTab::ALL)
+                    .default_value(NotificationTab::default())
+                    .description("Notifications tab to read"),
+            )
+            .property(
+                "count",
+                Schema::integer()
+                    .minimum(1)
+                    .maximum(MAX_RESULTS_PER_REQUEST as i64)
+                    .default_value(default_notification_count())
+                    .description("Maximum number of notifications to retrieve"),
+            )
+            .property(
+                "since",
+                Schema::string()
+                    .nullable()
+                    .description("`since` from the previous call, to only get newer notifications"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterListConversationsInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .property(
+                "count",
+                Schema::integer()
+                    .minimum(1)
+                    .maximum(MAX_RESULTS_PER_REQUEST as i64)
+                    .default_value(default_conversation_count())
+                    .description("Maximum number of conversations to list"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterReadDmInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .required_property(
+                "conversation_id",
+                Schema::string()
+                    .min_length(1)
+                    .description("Conversation ID, as returned by twitter_list_conversations"),
+            )
+            .property(
+                "count",
+                Schema::integer()
+                    .minimum(1)
+                    .maximum(MAX_RESULTS_PER_REQUEST as i64)
+                    .default_value(default_message_count())
+                    .description("Maximum number of recent messages to read"),
+            )
+            .into_value()
+    }
+}
+
+impl InputSchema for TwitterSendDmInput {
+    fn input_schema() -> Value {
+        Schema::object()
+            .property(
+                "conversation_id",
+                Schema::string()
+                    .nullable()
+                    .description("Conversation to send the message to"),
+            )
+            .property(
+                "username",
+                Schema::string()
+                    .nullable()
+                    .max_length(USERNAME_MAX_LENGTH + 1)
+                    .description("Account to message, when no conversation_id is given"),
+            )
+            .required_property(
+                "text",
+                Schema::string()
+                    .min_length(1)
+                    .max_length(DM_MAX_LENGTH)
+                    .description("Message text"),
+            )
+            .into_value()
+    }
+}
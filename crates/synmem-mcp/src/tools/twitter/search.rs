@@ -1,11 +1,23 @@
 //! Twitter search tool
 //!
-//! Search for tweets matching a query.
+//! Search for tweets matching a query. Structured [`SearchOperators`]
+//! are compiled into X's advanced search syntax (`from:rustlang`,
+//! `min_faves:100`, `-filter:replies`...) so callers don't have to write
+//! operator strings by hand.
 
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use synmem_core::ports::outbound::BrowserDriverPort;
+
+use super::feed::{self, FeedCursor, FeedLayout};
+use super::page::TWITTER_BASE_URL;
+use super::timeline::is_valid_username;
 use super::{
-    RateLimiter, RateLimitConfig, SearchFilter, Tweet, TwitterError, TwitterSearchInput,
-    TwitterSearchResult, TwitterSession,
+    RateLimiter, RateLimitConfig, SearchFilter, SearchOperators, TwitterError, TwitterSearchInput,
+    TwitterSearchResult, TwitterSession, MAX_RESULTS_PER_REQUEST,
 };
+use crate::tools::Progress;
 
 /// Search for tweets
 ///
@@ -13,29 +25,49 @@ use super::{
 /// * `input` - The search query and options
 /// * `session` - Valid Twitter session
 /// * `rate_limiter` - Rate limiter to prevent API abuse
+/// * `driver` - Browser driver used to run the search
+/// * `progress` - Receives the number of tweets collected out of `count`
 ///
 /// # Returns
-/// Result containing matching tweets or an error
+/// Result containing matching tweets or an error. Pass `next_cursor` back
+/// as `cursor` to continue after the last tweet returned.
 ///
 /// # Example
 /// ```ignore
 /// let input = TwitterSearchInput {
 ///     query: "rust programming".to_string(),
+///     operators: SearchOperators {
+///         lang: Some("en".to_string()),
+///         exclude_retweets: true,
+///         ..Default::default()
+///     },
 ///     count: 20,
 ///     filter: SearchFilter::Latest,
+///     cursor: None,
+///     download_media: false,
 /// };
-/// let result = twitter_search(input, &session, &rate_limiter).await?;
+/// let result =
+///     twitter_search(input, &session, &rate_limiter, &driver, &Progress::none()).await?;
 /// for tweet in result.tweets {
 ///     println!("{}: {}", tweet.author, tweet.text);
 /// }
 /// ```
-pub async fn twitter_search(
+pub async fn twitter_search<D: BrowserDriverPort>(
     input: TwitterSearchInput,
     session: &TwitterSession,
     rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+    progress: &Progress,
 ) -> Result<TwitterSearchResult, TwitterError> {
     // Validate input
     validate_search_input(&input)?;
+    let query = build_search_query(&input.query, &input.operators)?;
+    let search_url = build_search_url(&query, &input.filter);
+    let cursor = input
+        .cursor
+        .as_deref()
+        .map(|cursor| FeedCursor::decode(cursor, &search_url))
+        .transpose()?;
 
     // Check rate limit
     rate_limiter.acquire().await?;
@@ -43,47 +75,165 @@ pub async fn twitter_search(
     // Validate session
     validate_session(session)?;
 
-    // Build the search URL based on filter
-    // TODO: This URL will be used by the browser driver for actual search
-    #[allow(unused_variables)]
-    let search_url = build_search_url(&input.query, &input.filter);
-
-    // In a real implementation, this would:
-    // 1. Navigate to the search URL
-    // 2. Wait for results to load
-    // 3. Extract tweets from the DOM
-    // 4. Handle pagination if needed
-
-    // For now, return a placeholder result
+    let page = feed::collect(
+        driver,
+        &search_url,
+        FeedLayout::Tweets,
+        cursor,
+        input.count,
+        progress,
+    )
+    .await?;
     Ok(TwitterSearchResult {
         success: true,
-        tweets: vec![],
-        next_cursor: None,
+        tweets: page.tweets,
+        next_cursor: page.next_cursor,
         error: None,
     })
 }
 
 /// Validate the search input
+///
+/// The query itself is checked while building it.
 fn validate_search_input(input: &TwitterSearchInput) -> Result<(), TwitterError> {
-    if input.query.trim().is_empty() {
+    if input.count == 0 {
+        return Err(TwitterError::InvalidInput {
+            message: "Count must be greater than 0".to_string(),
+        });
+    }
+
+    if input.count > MAX_RESULTS_PER_REQUEST {
+        return Err(TwitterError::InvalidInput {
+            message: format!("Count cannot exceed {}", MAX_RESULTS_PER_REQUEST),
+        });
+    }
+
+    Ok(())
+}
+
+/// Compile a free-text query and advanced search operators into one query
+///
+/// Usernames may start with `@` and hashtags with `#`. Dates are
+/// `YYYY-MM-DD`, with `since` before `until`.
+///
+/// # Example
+/// ```ignore
+/// let operators = SearchOperators {
+///     from: Some("@rustlang".to_string()),
+///     hashtags: vec!["rust".to_string()],
+///     min_likes: Some(100),
+///     exclude_replies: true,
+///     ..Default::default()
+/// };
+/// let query = build_search_query("release", &operators)?;
+/// assert_eq!(query, "release from:rustlang #rust min_faves:100 -filter:replies");
+/// ```
+pub fn build_search_query(
+    query: &str,
+    operators: &SearchOperators,
+) -> Result<String, TwitterError> {
+    let mut terms = Vec::new();
+    if !query.trim().is_empty() {
+        terms.push(query.trim().to_string());
+    }
+
+    if let Some(from) = &operators.from {
+        terms.push(format!("from:{}", username_operand("from", from)?));
+    }
+    if let Some(to) = &operators.to {
+        terms.push(format!("to:{}", username_operand("to", to)?));
+    }
+    for mention in &operators.mentions {
+        terms.push(format!("@{}", username_operand("mentions", mention)?));
+    }
+    for hashtag in &operators.hashtags {
+        terms.push(format!("#{}", hashtag_operand(hashtag)?));
+    }
+
+    let since = operators.since.as_deref().map(|date| date_operand("since", date)).transpose()?;
+    let until = operators.until.as_deref().map(|date| date_operand("until", date)).transpose()?;
+    if let (Some(since), Some(until)) = (since, until) {
+        if since >= until {
+            return Err(TwitterError::InvalidInput {
+                message: format!("since ({}) must be before until ({})", since, until),
+            });
+        }
+    }
+    if let Some(since) = since {
+        terms.push(format!("since:{}", since));
+    }
+    if let Some(until) = until {
+        terms.push(format!("until:{}", until));
+    }
+
+    if let Some(min_likes) = operators.min_likes {
+        terms.push(format!("min_faves:{}", min_likes));
+    }
+    if let Some(min_retweets) = operators.min_retweets {
+        terms.push(format!("min_retweets:{}", min_retweets));
+    }
+    if let Some(lang) = &operators.lang {
+        terms.push(format!("lang:{}", language_operand(lang)?));
+    }
+    if operators.has_media {
+        terms.push("filter:media".to_string());
+    }
+    if operators.exclude_replies {
+        terms.push("-filter:replies".to_string());
+    }
+    if operators.exclude_retweets {
+        terms.push("-filter:retweets".to_string());
+    }
+
+    if terms.is_empty() {
         return Err(TwitterError::InvalidInput {
             message: "Search query cannot be empty".to_string(),
         });
     }
+    Ok(terms.join(" "))
+}
 
-    if input.count == 0 {
+/// Username given to `field`, without its `@`
+fn username_operand<'a>(field: &str, username: &'a str) -> Result<&'a str, TwitterError> {
+    let username = username.trim().trim_start_matches('@');
+    if !is_valid_username(username) {
         return Err(TwitterError::InvalidInput {
-            message: "Count must be greater than 0".to_string(),
+            message: format!("Invalid username in {}: {}", field, username),
         });
     }
+    Ok(username)
+}
 
-    if input.count > 100 {
+/// Hashtag without its `#`
+///
+/// Hashtags are letters, digits and underscores, and not only digits.
+fn hashtag_operand(hashtag: &str) -> Result<&str, TwitterError> {
+    let tag = hashtag.trim().trim_start_matches('#');
+    let valid = tag.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !tag.chars().all(|c| c.is_ascii_digit());
+    if !valid {
         return Err(TwitterError::InvalidInput {
-            message: "Count cannot exceed 100".to_string(),
+            message: format!("Invalid hashtag: {}", hashtag),
         });
     }
+    Ok(tag)
+}
 
-    Ok(())
+fn date_operand(field: &str, date: &str) -> Result<NaiveDate, TwitterError> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| TwitterError::InvalidInput {
+        message: format!("{} must be a YYYY-MM-DD date, got {}", field, date),
+    })
+}
+
+/// Two-letter ISO 639-1 language code, lowercased
+fn language_operand(lang: &str) -> Result<String, TwitterError> {
+    let lang = lang.trim();
+    if lang.len() != 2 || !lang.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(TwitterError::InvalidInput {
+            message: format!("lang must be a two-letter language code, got {}", lang),
+        });
+    }
+    Ok(lang.to_ascii_lowercase())
 }
 
 /// Build the search URL for the given query and filter
@@ -98,8 +248,8 @@ fn build_search_url(query: &str, filter: &SearchFilter) -> String {
     };
 
     format!(
-        "https://twitter.com/search?q={}{}&src=typed_query",
-        encoded_query, filter_param
+        "{}/search?q={}{}&src=typed_query",
+        TWITTER_BASE_URL, encoded_query, filter_param
     )
 }
 
@@ -138,14 +288,3 @@ fn validate_session(session: &TwitterSession) -> Result<(), TwitterError> {
 pub fn create_search_rate_limiter() -> RateLimiter {
     RateLimiter::new(RateLimitConfig::for_search())
 }
-
-/// Parse a tweet from raw DOM elements
-///
-/// This is a placeholder for the actual implementation that would
-/// extract tweet data from the browser DOM.
-#[allow(dead_code)]
-fn parse_tweet_from_dom(_element: &str) -> Option<Tweet> {
-    // In a real implementation, this would parse the DOM element
-    // and extract tweet information
-    None
-}
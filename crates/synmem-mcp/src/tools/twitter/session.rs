@@ -0,0 +1,176 @@
+//! Twitter sessions from the encrypted session store
+//!
+//! A profile saved with [`SessionManager`] (e.g. "twitter-main") holds the
+//! cookies of a logged-in browser. The session is derived from them: the
+//! cookie header from the x.com cookies and the CSRF token from `ct0`.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::{Cookie, SameSite, SessionError, SessionManager, SimpleCookie};
+use tracing::debug;
+
+use super::{TwitterError, TwitterSession};
+
+/// Cookie holding the login
+const AUTH_COOKIE: &str = "auth_token";
+
+/// Cookie holding the CSRF token
+const CSRF_COOKIE: &str = "ct0";
+
+/// Cookie holding the user ID, e.g. "u%3D123456"
+const USER_ID_COOKIE: &str = "twid";
+
+/// Domains whose cookies belong to the session
+const TWITTER_DOMAINS: [&str; 2] = ["x.com", "twitter.com"];
+
+impl TwitterSession {
+    /// Load the session saved in `profile_name`
+    ///
+    /// Decrypts the profile, derives the session from its cookies and
+    /// injects them into the browser so the tools act as that account.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut manager = SessionManager::default();
+    /// let session =
+    ///     TwitterSession::from_profile(&mut manager, "twitter-main", &password, &driver).await?;
+    /// ```
+    pub async fn from_profile<D: BrowserDriverPort>(
+        session_manager: &mut SessionManager,
+        profile_name: &str,
+        master_password: &str,
+        driver: &Arc<D>,
+    ) -> Result<Self, TwitterError> {
+        let stored = session_manager
+            .load_session(profile_name, master_password)
+            .map_err(|e| profile_error(profile_name, e))?;
+        let cookies: Vec<&Cookie> = stored
+            .cookies()
+            .iter()
+            .filter(|cookie| is_twitter_cookie(cookie))
+            .collect();
+        let session = session_from_cookies(&cookies)?;
+
+        let browser_cookies: Vec<SimpleCookie> = cookies
+            .iter()
+            .map(|cookie| browser_cookie(cookie))
+            .collect();
+        driver
+            .set_cookies(&browser_cookies)
+            .await
+            .map_err(|e| TwitterError::NetworkError {
+                message: format!("Failed to set the session cookies: {}", e),
+            })?;
+
+        debug!(
+            profile = profile_name,
+            cookies = cookies.len(),
+            "Loaded Twitter session"
+        );
+        Ok(session)
+    }
+
+    /// Whether the session has the cookies the tools need
+    pub fn is_authenticated(&self) -> bool {
+        !self.cookies.is_empty() && !self.csrf_token.is_empty()
+    }
+
+    /// Key identifying the account in rate limiter keys
+    ///
+    /// The user ID, or "default" when it is unknown.
+    pub fn account_key(&self) -> String {
+        self.user_id
+            .clone()
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| "default".to_string())
+    }
+
+    /// Derive a session from browser cookies
+    ///
+    /// Cookies of other sites are ignored. The bearer token is not stored
+    /// in cookies and is left empty.
+    pub fn from_cookies(cookies: &[Cookie]) -> Result<Self, TwitterError> {
+        let cookies: Vec<&Cookie> = cookies
+            .iter()
+            .filter(|cookie| is_twitter_cookie(cookie))
+            .collect();
+        session_from_cookies(&cookies)
+    }
+}
+
+fn session_from_cookies(cookies: &[&Cookie]) -> Result<TwitterSession, TwitterError> {
+    let find = |name: &str| cookies.iter().find(|cookie| cookie.name == name);
+    let (Some(auth), Some(csrf)) = (find(AUTH_COOKIE), find(CSRF_COOKIE)) else {
+        return Err(TwitterError::NoSession);
+    };
+    if is_expired(auth) || is_expired(csrf) {
+        return Err(TwitterError::SessionExpired);
+    }
+
+    let header = cookies
+        .iter()
+        .filter(|cookie| !is_expired(cookie))
+        .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+        .collect::<Vec<_>>()
+        .join("; ");
+    Ok(TwitterSession {
+        cookies: header,
+        csrf_token: csrf.value.clone(),
+        bearer_token: String::new(),
+        user_id: find(USER_ID_COOKIE).and_then(|cookie| user_id(&cookie.value)),
+    })
+}
+
+/// User ID from the `twid` cookie, e.g. "u%3D123456" or "u=123456"
+fn user_id(twid: &str) -> Option<String> {
+    let twid = twid.trim_matches('"');
+    let id = twid
+        .strip_prefix("u%3D")
+        .or_else(|| twid.strip_prefix("u="))?;
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_digit())).then(|| id.to_string())
+}
+
+fn is_expired(cookie: &Cookie) -> bool {
+    cookie.expires.is_some_and(|expires| expires <= Utc::now())
+}
+
+fn is_twitter_cookie(cookie: &Cookie) -> bool {
+    let domain = cookie.domain.trim_start_matches('.');
+    TWITTER_DOMAINS
+        .iter()
+        .any(|site| domain == *site || domain.ends_with(&format!(".{}", site)))
+}
+
+fn browser_cookie(cookie: &Cookie) -> SimpleCookie {
+    let same_site = match cookie.same_site.as_deref() {
+        Some(value) if value.eq_ignore_ascii_case("lax") => SameSite::Lax,
+        Some(value) if value.eq_ignore_ascii_case("strict") => SameSite::Strict,
+        _ => SameSite::None,
+    };
+    SimpleCookie {
+        name: cookie.name.clone(),
+        value: cookie.value.clone(),
+        domain: cookie.domain.clone(),
+        path: cookie.path.clone(),
+        secure: cookie.secure,
+        http_only: cookie.http_only,
+        expires: cookie.expires.map(|expires| expires.timestamp()),
+        same_site,
+    }
+}
+
+/// Map a session store failure to the Twitter error it means for the tools
+fn profile_error(profile_name: &str, error: SessionError) -> TwitterError {
+    match error {
+        SessionError::Expired => TwitterError::SessionExpired,
+        SessionError::NotFound(_) => TwitterError::NoSession,
+        SessionError::InvalidPassword => TwitterError::AuthError {
+            message: format!("Wrong master password for profile {}", profile_name),
+        },
+        other => TwitterError::AuthError {
+            message: format!("Failed to load profile {}: {}", profile_name, other),
+        },
+    }
+}
@@ -1,6 +1,7 @@
 //! Tests for Twitter automation tools
 
 use super::*;
+use crate::tools::Progress;
 
 /// Create a mock Twitter session for testing
 fn mock_session() -> TwitterSession {
@@ -22,8 +23,56 @@ fn invalid_session() -> TwitterSession {
     }
 }
 
+/// Create a rate limiter allowing `max_requests` without delays between them
+fn fast_limiter(max_requests: u32) -> RateLimiter {
+    RateLimiter::new(RateLimitConfig {
+        max_requests,
+        window_seconds: 900,
+        min_delay_ms: 0,
+        ..Default::default()
+    })
+}
+
+/// Create a browser on which posting a tweet succeeds
+fn compose_driver() -> std::sync::Arc<crate::testing::MockDriver> {
+    std::sync::Arc::new(crate::testing::MockDriver::new().with_page(
+        "https://x.com/compose/post",
+        &crate::testing::fixture("twitter/post_sent.html"),
+    ))
+}
+
 mod post_tests {
     use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    const COMPOSE_URL: &str = "https://x.com/compose/post";
+    const PNG_DATA_URL: &str = "data:image/png;base64,iVBORw0KGgo=";
+
+    async fn post(
+        input: TwitterPostInput,
+        session: &TwitterSession,
+        rate_limiter: &RateLimiter,
+    ) -> Result<TwitterPostResult, TwitterError> {
+        twitter_post(input, session, rate_limiter, &compose_driver()).await
+    }
+
+    /// Serve `body` as `content_type` at `/media` on a local port
+    async fn serve_media(status: u16, content_type: &'static str, body: &'static [u8]) -> String {
+        use axum::http::{header, StatusCode};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let status = StatusCode::from_u16(status).unwrap();
+        let app = axum::Router::new().route(
+            "/media",
+            axum::routing::get(move || async move {
+                (status, [(header::CONTENT_TYPE, content_type)], body)
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}/media", addr)
+    }
 
     #[tokio::test]
     async fn test_post_valid_tweet() {
@@ -34,12 +83,24 @@ mod post_tests {
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+        let driver = compose_driver();
 
-        let result = twitter_post(input, &session, &rate_limiter).await;
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await;
         assert!(result.is_ok());
         let result = result.unwrap();
         assert!(result.success);
-        assert!(result.tweet_id.is_some());
+        assert_eq!(result.tweet_id.as_deref(), Some("1792000000000000001"));
+        assert_eq!(
+            result.tweet_url.as_deref(),
+            Some("https://x.com/synmem_bot/status/1792000000000000001")
+        );
+        assert!(result.error.is_none());
+
+        let actions = driver.actions();
+        assert_eq!(actions[0], format!("goto {}", COMPOSE_URL));
+        assert!(actions
+            .contains(&"type div[data-testid=\"tweetTextarea_0\"] Hello, Twitter!".to_string()));
+        assert!(actions.contains(&"click button[data-testid=\"tweetButton\"]".to_string()));
     }
 
     #[tokio::test]
@@ -53,7 +114,7 @@ mod post_tests {
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
 
-        let result = twitter_post(input, &session, &rate_limiter).await;
+        let result = post(input, &session, &rate_limiter).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             TwitterError::TweetTooLong { max, actual } => {
@@ -74,7 +135,7 @@ mod post_tests {
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
 
-        let result = twitter_post(input, &session, &rate_limiter).await;
+        let result = post(input, &session, &rate_limiter).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             TwitterError::InvalidInput { message } => {
@@ -94,7 +155,7 @@ mod post_tests {
         let session = invalid_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
 
-        let result = twitter_post(input, &session, &rate_limiter).await;
+        let result = post(input, &session, &rate_limiter).await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), TwitterError::NoSession));
     }
@@ -103,14 +164,116 @@ mod post_tests {
     async fn test_post_with_media() {
         let input = TwitterPostInput {
             text: "Check out this image!".to_string(),
-            media_urls: vec!["https://example.com/image.jpg".to_string()],
+            media_urls: vec![PNG_DATA_URL.to_string()],
             reply_to: None,
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+        let driver = compose_driver();
 
-        let result = twitter_post(input, &session, &rate_limiter).await;
-        assert!(result.is_ok());
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await;
+        assert!(result.unwrap().success);
+
+        let upload = driver
+            .actions()
+            .into_iter()
+            .find(|action| action.starts_with("upload input[data-testid=\"fileInput\"] "))
+            .expect("media was uploaded");
+        assert!(upload.ends_with("media-0.png"));
+    }
+
+    #[tokio::test]
+    async fn test_post_refuses_local_media() {
+        let url = serve_media(200, "image/gif", b"GIF89a").await;
+        let input = TwitterPostInput {
+            text: "Local attachment".to_string(),
+            media_urls: vec![url, PNG_DATA_URL.to_string()],
+            reply_to: None,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+        let driver = compose_driver();
+
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await.unwrap();
+        assert!(!result.success);
+        assert!(result.tweet_id.is_none());
+        assert!(result.error.unwrap().contains("not a public address"));
+        // The compose box is never opened
+        assert!(driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_post_reports_rejection() {
+        let rejected = r#"<div data-testid="toast" role="alert">
+            <span>Whoops! You already said that.</span>
+        </div>"#;
+        let driver = Arc::new(MockDriver::new().with_page(COMPOSE_URL, rejected));
+        let input = TwitterPostInput {
+            text: "Hello again".to_string(),
+            media_urls: vec![],
+            reply_to: None,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("You already said that"));
+    }
+
+    #[tokio::test]
+    async fn test_post_reports_missing_confirmation() {
+        let driver = Arc::new(MockDriver::new());
+        let input = TwitterPostInput {
+            text: "Hello?".to_string(),
+            media_urls: vec![],
+            reply_to: None,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("did not confirm"));
+    }
+
+    #[tokio::test]
+    async fn test_post_behind_login_wall() {
+        let login = "https://x.com/i/flow/login";
+        let driver = Arc::new(
+            MockDriver::new()
+                .with_redirect(COMPOSE_URL, login)
+                .with_page(login, &fixture("twitter/login_wall.html")),
+        );
+        let input = TwitterPostInput {
+            text: "Hello?".to_string(),
+            media_urls: vec![],
+            reply_to: None,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await;
+        assert!(matches!(result, Err(TwitterError::LoginRequired)));
+    }
+
+    #[tokio::test]
+    async fn test_post_throttled() {
+        let driver = Arc::new(
+            MockDriver::new()
+                .with_page(COMPOSE_URL, &fixture("twitter/rate_limited.html"))
+                .with_missing("div[data-testid=\"tweetTextarea_0\"]"),
+        );
+        let input = TwitterPostInput {
+            text: "Hello?".to_string(),
+            media_urls: vec![],
+            reply_to: None,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await;
+        assert!(matches!(result, Err(TwitterError::Throttled { .. })));
     }
 
     #[tokio::test]
@@ -122,9 +285,53 @@ mod post_tests {
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+        let driver = Arc::new(MockDriver::new().with_page(
+            "https://x.com/i/status/123456789",
+            &fixture("twitter/post_sent.html"),
+        ));
 
-        let result = twitter_post(input, &session, &rate_limiter).await;
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await;
         assert!(result.is_ok());
+        assert!(result.unwrap().success);
+
+        let actions = driver.actions();
+        assert_eq!(actions[0], "goto https://x.com/i/status/123456789");
+        assert!(actions.contains(
+            &"click article[data-testid=\"tweet\"][tabindex=\"-1\"] button[data-testid=\"reply\"]"
+                .to_string()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_post_reply_to_url() {
+        let input = TwitterPostInput {
+            text: "This is a reply".to_string(),
+            media_urls: vec![],
+            reply_to: Some("https://x.com/rustlang/status/123456789".to_string()),
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+        let driver = Arc::new(MockDriver::new().with_page(
+            "https://x.com/i/status/123456789",
+            &fixture("twitter/post_sent.html"),
+        ));
+
+        let result = twitter_post(input, &session, &rate_limiter, &driver).await;
+        assert!(result.unwrap().success);
+    }
+
+    #[tokio::test]
+    async fn test_post_reply_to_invalid_id() {
+        let input = TwitterPostInput {
+            text: "This is a reply".to_string(),
+            media_urls: vec![],
+            reply_to: Some("not a tweet".to_string()),
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
+
+        let result = post(input, &session, &rate_limiter).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
     }
 
     #[tokio::test]
@@ -138,192 +345,3212 @@ mod post_tests {
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_post());
 
-        let result = twitter_post(input, &session, &rate_limiter).await;
+        let result = post(input, &session, &rate_limiter).await;
         assert!(result.is_ok());
     }
 }
 
-mod read_thread_tests {
+mod post_thread_tests {
     use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    const COMPOSE_URL: &str = "https://x.com/compose/post";
+    const POSTED_URL: &str = "https://x.com/i/status/1792000000000000001";
+
+    /// Posts succeed on the compose box and in replies to the posted tweet
+    fn thread_driver(reply_snapshots: &[&str]) -> Arc<MockDriver> {
+        Arc::new(
+            MockDriver::new()
+                .with_page(COMPOSE_URL, &fixture("twitter/post_sent.html"))
+                .with_snapshots(POSTED_URL, reply_snapshots),
+        )
+    }
+
+    fn segments(texts: &[&str]) -> TwitterPostThreadInput {
+        TwitterPostThreadInput {
+            segments: texts.iter().map(|text| text.to_string()).collect(),
+            text: None,
+            rollback_on_failure: false,
+        }
+    }
+
+    #[test]
+    fn test_split_thread_short_text() {
+        assert_eq!(split_thread("  Just one tweet.  "), vec!["Just one tweet."]);
+        assert!(split_thread("   ").is_empty());
+    }
+
+    #[test]
+    fn test_split_thread_numbers_tweets() {
+        let sentence = "Rust makes systems programming safer without giving up speed. ";
+        let text = sentence.repeat(12);
+
+        let tweets = split_thread(&text);
+        assert!(tweets.len() > 1);
+        for (index, tweet) in tweets.iter().enumerate() {
+            assert!(tweet.chars().count() <= TWEET_MAX_LENGTH);
+            assert!(tweet.ends_with(&format!(" {}/{}", index + 1, tweets.len())));
+            // Sentences are kept whole
+            assert!(tweet.starts_with("Rust makes"));
+        }
+    }
+
+    #[test]
+    fn test_split_thread_long_sentence() {
+        let text = format!("{} {}", "word".repeat(10), "lorem ipsum ".repeat(60));
+
+        let tweets = split_thread(&text);
+        assert!(tweets.len() > 2);
+        assert!(tweets
+            .iter()
+            .all(|tweet| tweet.chars().count() <= TWEET_MAX_LENGTH));
+        assert!(tweets[0].starts_with(&"word".repeat(10)));
+    }
+
+    #[test]
+    fn test_split_thread_unbroken_text() {
+        let tweets = split_thread(&"x".repeat(600));
+        assert_eq!(tweets.len(), 3);
+        assert!(tweets
+            .iter()
+            .all(|tweet| tweet.chars().count() <= TWEET_MAX_LENGTH));
+        assert!(tweets[2].ends_with(" 3/3"));
+    }
 
     #[tokio::test]
-    async fn test_read_thread_by_url() {
-        let input = TwitterReadThreadInput {
-            tweet_url_or_id: "https://twitter.com/user/status/123456789".to_string(),
-            max_tweets: 50,
-        };
+    async fn test_post_thread_reply_chain() {
+        let post_sent = fixture("twitter/post_sent.html");
+        let driver = thread_driver(&[&post_sent]);
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
+        let rate_limiter = fast_limiter(25);
 
-        let result = twitter_read_thread(input, &session, &rate_limiter).await;
-        assert!(result.is_ok());
-        let result = result.unwrap();
+        let result = twitter_post_thread(
+            segments(&["First", "Second", "Third"]),
+            &session,
+            &rate_limiter,
+            &driver,
+            &Progress::none(),
+        )
+        .await
+        .unwrap();
         assert!(result.success);
+        assert_eq!(result.total, 3);
+        assert_eq!(result.tweets.len(), 3);
+        assert!(result.error.is_none());
+        assert_eq!(
+            result.tweets[0].tweet_url,
+            "https://x.com/synmem_bot/status/1792000000000000001"
+        );
+        assert_eq!(rate_limiter.remaining_tokens(), 22);
+
+        // The first tweet opens the compose box, the others reply
+        let pages: Vec<String> = driver
+            .actions()
+            .into_iter()
+            .filter(|action| action.starts_with("goto "))
+            .collect();
+        assert_eq!(
+            pages,
+            vec![
+                format!("goto {}", COMPOSE_URL),
+                format!("goto {}", POSTED_URL),
+                format!("goto {}", POSTED_URL),
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn test_read_thread_by_id() {
-        let input = TwitterReadThreadInput {
-            tweet_url_or_id: "123456789".to_string(),
-            max_tweets: 50,
+    async fn test_post_thread_from_text() {
+        let post_sent = fixture("twitter/post_sent.html");
+        let driver = thread_driver(&[&post_sent]);
+        let input = TwitterPostThreadInput {
+            segments: vec![],
+            text: Some("A sentence that fills some space in a tweet. ".repeat(10)),
+            rollback_on_failure: false,
         };
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
+        let rate_limiter = fast_limiter(25);
 
-        let result = twitter_read_thread(input, &session, &rate_limiter).await;
-        assert!(result.is_ok());
+        let result =
+            twitter_post_thread(input, &session, &rate_limiter, &driver, &Progress::none())
+                .await
+                .unwrap();
+        assert!(result.success);
+        assert_eq!(result.total, 2);
+        assert!(driver
+            .actions()
+            .iter()
+            .any(|action| action.ends_with(" 2/2")));
     }
 
     #[tokio::test]
-    async fn test_read_thread_x_url() {
-        let input = TwitterReadThreadInput {
-            tweet_url_or_id: "https://x.com/user/status/123456789".to_string(),
-            max_tweets: 50,
+    async fn test_post_thread_invalid_input() {
+        let session = mock_session();
+        let rate_limiter = fast_limiter(25);
+        let driver = thread_driver(&[]);
+
+        let both = TwitterPostThreadInput {
+            segments: vec!["One".to_string()],
+            text: Some("Two".to_string()),
+            rollback_on_failure: false,
         };
+        let too_long = segments(&[&"a".repeat(281)]);
+        let empty = segments(&["First", " "]);
+        let nothing = segments(&[]);
+        let too_many = segments(&vec!["tweet"; MAX_THREAD_LENGTH + 1]);
+
+        for input in [both, empty, nothing, too_many] {
+            let result =
+                twitter_post_thread(input, &session, &rate_limiter, &driver, &Progress::none())
+                    .await;
+            assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+        }
+        let result =
+            twitter_post_thread(too_long, &session, &rate_limiter, &driver, &Progress::none())
+                .await;
+        assert!(matches!(result, Err(TwitterError::TweetTooLong { .. })));
+        assert!(driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_post_thread_no_session() {
+        let session = invalid_session();
+        let rate_limiter = fast_limiter(25);
+        let driver = thread_driver(&[]);
+
+        let result = twitter_post_thread(
+            segments(&["First", "Second"]),
+            &session,
+            &rate_limiter,
+            &driver,
+            &Progress::none(),
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::NoSession)));
+    }
+
+    #[tokio::test]
+    async fn test_post_thread_exceeding_rate_limit() {
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
+        let rate_limiter = fast_limiter(2);
+        let driver = thread_driver(&[]);
 
-        let result = twitter_read_thread(input, &session, &rate_limiter).await;
-        assert!(result.is_ok());
+        let result = twitter_post_thread(
+            segments(&["First", "Second", "Third"]),
+            &session,
+            &rate_limiter,
+            &driver,
+            &Progress::none(),
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::RateLimited { .. })));
+        // Nothing is posted or consumed
+        assert!(driver.actions().is_empty());
+        assert_eq!(rate_limiter.remaining_tokens(), 2);
     }
 
     #[tokio::test]
-    async fn test_read_thread_invalid_url() {
-        let input = TwitterReadThreadInput {
-            tweet_url_or_id: "https://example.com/not-twitter".to_string(),
-            max_tweets: 50,
-        };
+    async fn test_post_thread_behind_login_wall() {
+        let login = "https://x.com/i/flow/login";
+        let driver = Arc::new(
+            MockDriver::new()
+                .with_redirect(COMPOSE_URL, login)
+                .with_page(login, &fixture("twitter/login_wall.html")),
+        );
+
+        let result = twitter_post_thread(
+            segments(&["First", "Second"]),
+            &mock_session(),
+            &fast_limiter(25),
+            &driver,
+            &Progress::none(),
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::LoginRequired)));
+    }
+
+    #[tokio::test]
+    async fn test_post_thread_reports_partial_progress() {
+        let post_sent = fixture("twitter/post_sent.html");
+        let rejected = r#"<div data-testid="toast" role="alert">
+            <span>Whoops! You already said that.</span>
+        </div>"#;
+        let driver = thread_driver(&[&post_sent, rejected]);
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
+        let rate_limiter = fast_limiter(25);
 
-        let result = twitter_read_thread(input, &session, &rate_limiter).await;
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            TwitterError::InvalidInput { .. }
-        ));
+        let result = twitter_post_thread(
+            segments(&["First", "Second", "Third", "Fourth"]),
+            &session,
+            &rate_limiter,
+            &driver,
+            &Progress::none(),
+        )
+        .await
+        .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.total, 4);
+        assert_eq!(result.tweets.len(), 2);
+        assert!(!result.rolled_back);
+        let error = result.error.unwrap();
+        assert!(error.starts_with("Tweet 3 of 4 failed"));
+        assert!(error.contains("You already said that"));
+        // The fourth tweet is never attempted
+        assert!(!driver.actions().iter().any(|action| action.contains("Fourth")));
     }
 
     #[tokio::test]
-    async fn test_read_thread_no_session() {
-        let input = TwitterReadThreadInput {
-            tweet_url_or_id: "123456789".to_string(),
-            max_tweets: 50,
-        };
-        let session = invalid_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
+    async fn test_post_thread_rolls_back() {
+        let post_sent = fixture("twitter/post_sent.html");
+        let driver = thread_driver(&[&post_sent, ""]);
+        let mut input = segments(&["First", "Second", "Third"]);
+        input.rollback_on_failure = true;
+        let session = mock_session();
+        let rate_limiter = fast_limiter(25);
 
-        let result = twitter_read_thread(input, &session, &rate_limiter).await;
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), TwitterError::NoSession));
+        let result =
+            twitter_post_thread(input, &session, &rate_limiter, &driver, &Progress::none())
+                .await
+                .unwrap();
+        assert!(!result.success);
+        assert!(result.rolled_back);
+        assert!(result.tweets.is_empty());
+        assert!(result.error.unwrap().contains("did not confirm"));
+
+        let confirmations = driver
+            .actions()
+            .iter()
+            .filter(|action| {
+                *action == "click button[data-testid=\"confirmationSheetConfirm\"]"
+            })
+            .count();
+        assert_eq!(confirmations, 2);
     }
 }
 
-mod search_tests {
+mod engage_tests {
     use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    const TWEET_URL: &str = "https://x.com/i/status/1793000000000000001";
+    const PROFILE_URL: &str = "https://x.com/rustlang";
+    const FOCAL: &str = "article[data-testid=\"tweet\"][tabindex=\"-1\"]";
+
+    fn tweet_driver(page: &str) -> Arc<MockDriver> {
+        Arc::new(MockDriver::new().with_page(TWEET_URL, &fixture(page)))
+    }
+
+    fn profile_driver(page: &str) -> Arc<MockDriver> {
+        Arc::new(MockDriver::new().with_page(PROFILE_URL, &fixture(page)))
+    }
+
+    fn tweet_input(tweet_url_or_id: &str) -> TwitterTweetActionInput {
+        TwitterTweetActionInput {
+            tweet_url_or_id: tweet_url_or_id.to_string(),
+        }
+    }
+
+    fn user_input(username: &str) -> TwitterUserActionInput {
+        TwitterUserActionInput {
+            username: username.to_string(),
+        }
+    }
+
+    fn clicks(driver: &MockDriver) -> Vec<String> {
+        driver
+            .actions()
+            .into_iter()
+            .filter(|action| action.starts_with("click "))
+            .collect()
+    }
 
     #[tokio::test]
-    async fn test_search_valid_query() {
-        let input = TwitterSearchInput {
-            query: "rust programming".to_string(),
-            count: 20,
-            filter: SearchFilter::Top,
-        };
+    async fn test_like() {
+        let driver = tweet_driver("twitter/tweet.html");
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
 
-        let result = twitter_search(input, &session, &rate_limiter).await;
-        assert!(result.is_ok());
-        let result = result.unwrap();
+        let result = twitter_like(
+            tweet_input("https://x.com/rustlang/status/1793000000000000001"),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await
+        .unwrap();
         assert!(result.success);
+        assert!(result.changed);
+        assert_eq!(result.action, "like");
+        assert_eq!(result.target, "1793000000000000001");
+
+        assert_eq!(
+            clicks(&driver),
+            vec![format!("click {} button[data-testid=\"like\"]", FOCAL)]
+        );
+        assert!(driver
+            .actions()
+            .contains(&format!("wait {} button[data-testid=\"unlike\"]", FOCAL)));
     }
 
     #[tokio::test]
-    async fn test_search_empty_query() {
-        let input = TwitterSearchInput {
-            query: "".to_string(),
-            count: 20,
-            filter: SearchFilter::Top,
-        };
+    async fn test_like_already_liked() {
+        let driver = tweet_driver("twitter/tweet_engaged.html");
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
 
-        let result = twitter_search(input, &session, &rate_limiter).await;
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            TwitterError::InvalidInput { .. }
-        ));
+        let result = twitter_like(
+            tweet_input("1793000000000000001"),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+        assert!(!result.changed);
+        assert!(clicks(&driver).is_empty());
     }
 
     #[tokio::test]
-    async fn test_search_zero_count() {
-        let input = TwitterSearchInput {
-            query: "test".to_string(),
-            count: 0,
-            filter: SearchFilter::Top,
-        };
+    async fn test_unlike() {
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
 
-        let result = twitter_search(input, &session, &rate_limiter).await;
-        assert!(result.is_err());
+        let driver = tweet_driver("twitter/tweet_engaged.html");
+        let input = tweet_input("1793000000000000001");
+        let result = twitter_unlike(input, &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(result.changed);
+        assert_eq!(
+            clicks(&driver),
+            vec![format!("click {} button[data-testid=\"unlike\"]", FOCAL)]
+        );
+
+        let driver = tweet_driver("twitter/tweet.html");
+        let input = tweet_input("1793000000000000001");
+        let result = twitter_unlike(input, &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(!result.changed);
     }
 
     #[tokio::test]
-    async fn test_search_count_too_high() {
-        let input = TwitterSearchInput {
-            query: "test".to_string(),
-            count: 101,
-            filter: SearchFilter::Top,
-        };
+    async fn test_retweet_confirms() {
+        let driver = tweet_driver("twitter/tweet.html");
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
 
-        let result = twitter_search(input, &session, &rate_limiter).await;
-        assert!(result.is_err());
+        let result = twitter_retweet(
+            tweet_input("1793000000000000001"),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await
+        .unwrap();
+        assert!(result.changed);
+        assert_eq!(
+            clicks(&driver),
+            vec![
+                format!("click {} button[data-testid=\"retweet\"]", FOCAL),
+                "click div[data-testid=\"retweetConfirm\"]".to_string(),
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn test_search_different_filters() {
+    async fn test_bookmark_idempotent() {
         let session = mock_session();
-        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
 
-        for filter in [
-            SearchFilter::Top,
-            SearchFilter::Latest,
-            SearchFilter::People,
-            SearchFilter::Photos,
-            SearchFilter::Videos,
-        ] {
-            let input = TwitterSearchInput {
-                query: "test".to_string(),
-                count: 10,
-                filter,
-            };
-            let result = twitter_search(input, &session, &rate_limiter).await;
-            assert!(result.is_ok());
+        let driver = tweet_driver("twitter/tweet.html");
+        let input = tweet_input("1793000000000000001");
+        let result = twitter_bookmark(input, &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(result.changed);
+
+        let driver = tweet_driver("twitter/tweet_engaged.html");
+        let input = tweet_input("1793000000000000001");
+        let result = twitter_bookmark(input, &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(!result.changed);
+        assert!(clicks(&driver).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tweet_action_missing_button() {
+        let driver = Arc::new(MockDriver::new().with_page(TWEET_URL, "<html></html>"));
+        let session = mock_session();
+
+        let result = twitter_like(
+            tweet_input("1793000000000000001"),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::Unknown { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_tweet_action_invalid_id() {
+        let driver = tweet_driver("twitter/tweet.html");
+        let session = mock_session();
+        let limiter = fast_limiter(10);
+
+        let result = twitter_like(tweet_input("not-a-tweet"), &session, &limiter, &driver).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+        assert!(driver.actions().is_empty());
+        assert_eq!(limiter.remaining_tokens(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_tweet_action_no_session() {
+        let driver = tweet_driver("twitter/tweet.html");
+        let session = invalid_session();
+
+        let result = twitter_retweet(
+            tweet_input("1793000000000000001"),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::NoSession)));
+    }
+
+    #[tokio::test]
+    async fn test_follow() {
+        let driver = profile_driver("twitter/profile_follow.html");
+        let session = mock_session();
+
+        let result = twitter_follow(user_input("@rustlang"), &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(result.changed);
+        assert_eq!(result.target, "rustlang");
+        assert_eq!(driver.actions()[0], format!("goto {}", PROFILE_URL));
+        assert_eq!(
+            clicks(&driver),
+            vec!["click button[data-testid$=\"-follow\"][aria-label$=\"@rustlang\" i]".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_follow_already_following() {
+        let driver = profile_driver("twitter/profile_following.html");
+        let session = mock_session();
+
+        let result = twitter_follow(user_input("rustlang"), &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(!result.changed);
+        assert!(clicks(&driver).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unfollow_confirms() {
+        let driver = profile_driver("twitter/profile_following.html");
+        let session = mock_session();
+
+        let result = twitter_unfollow(user_input("rustlang"), &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(result.changed);
+        assert_eq!(
+            clicks(&driver),
+            vec![
+                "click button[data-testid$=\"-unfollow\"][aria-label$=\"@rustlang\" i]".to_string(),
+                "click button[data-testid=\"confirmationSheetConfirm\"]".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_follow_ignores_suggested_accounts() {
+        let driver = profile_driver("twitter/profile_following_suggestions.html");
+        let session = mock_session();
+
+        let result = twitter_follow(user_input("rustlang"), &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(!result.changed);
+        assert!(clicks(&driver).is_empty());
+
+        let result = twitter_unfollow(user_input("rustlang"), &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(result.changed);
+        assert_eq!(
+            clicks(&driver)[0],
+            "click button[data-testid$=\"-unfollow\"][aria-label$=\"@rustlang\" i]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_follow_missing_account() {
+        let driver = Arc::new(MockDriver::new().with_page(
+            "https://x.com/no_such_user_42",
+            &fixture("twitter/profile_missing.html"),
+        ));
+        let session = mock_session();
+
+        let result = twitter_follow(
+            user_input("no_such_user_42"),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await;
+        match result.unwrap_err() {
+            TwitterError::UserNotFound { username } => assert_eq!(username, "no_such_user_42"),
+            e => panic!("Expected UserNotFound, got {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follow_suspended_account() {
+        // The profile header never renders on a suspended account
+        let driver = Arc::new(
+            MockDriver::new()
+                .with_page(
+                    "https://x.com/spam_account_1",
+                    &fixture("twitter/profile_suspended.html"),
+                )
+                .with_missing(super::page::PROFILE_SELECTOR),
+        );
+        let session = mock_session();
+
+        let result = twitter_follow(
+            user_input("spam_account_1"),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await;
+        match result.unwrap_err() {
+            TwitterError::UserSuspended { username } => assert_eq!(username, "spam_account_1"),
+            e => panic!("Expected UserSuspended, got {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follow_invalid_username() {
+        let driver = profile_driver("twitter/profile_follow.html");
+        let session = mock_session();
+
+        let result = twitter_follow(
+            user_input("not a username"),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+        assert!(driver.actions().is_empty());
+    }
+}
+
+mod profile_tests {
+    use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    async fn profile(username: &str, page: &str) -> Result<TwitterGetProfileResult, TwitterError> {
+        let handle = username.trim_start_matches('@');
+        let url = format!("https://x.com/{}", handle);
+        let driver = Arc::new(MockDriver::new().with_page(&url, &fixture(page)));
+        let input = TwitterGetProfileInput {
+            username: username.to_string(),
+        };
+        twitter_get_profile(input, &mock_session(), &fast_limiter(10), &driver).await
+    }
+
+    #[tokio::test]
+    async fn test_get_profile() {
+        let result = profile("rustlang", "twitter/profile.html").await.unwrap();
+        assert!(result.success);
+        let profile = result.profile.unwrap();
+        assert_eq!(profile.username, "rustlang");
+        assert_eq!(profile.display_name, "Rust Language");
+        assert_eq!(
+            profile.bio,
+            "Empowering everyone to build reliable and efficient software. 🦀"
+        );
+        assert_eq!(profile.joined.as_deref(), Some("March 2009"));
+        assert_eq!(profile.followers, Some(165_300));
+        assert_eq!(profile.following, Some(12));
+        assert!(profile.verified);
+        assert!(!profile.protected);
+
+        let pinned = profile.pinned_tweet.unwrap();
+        assert_eq!(pinned.id, "1794000000000000001");
+        assert_eq!(pinned.likes, 4200);
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_with_at_sign() {
+        let result = profile("@rustlang", "twitter/profile.html").await.unwrap();
+        assert_eq!(result.profile.unwrap().username, "rustlang");
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_protected() {
+        let result = profile("ferris_private", "twitter/profile_protected.html")
+            .await
+            .unwrap();
+        let profile = result.profile.unwrap();
+        assert!(profile.protected);
+        assert!(!profile.verified);
+        assert_eq!(profile.followers, Some(88));
+        assert_eq!(profile.following, Some(1024));
+        assert!(profile.pinned_tweet.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_missing() {
+        let result = profile("no_such_user_42", "twitter/profile_missing.html").await;
+        match result.unwrap_err() {
+            TwitterError::UserNotFound { username } => assert_eq!(username, "no_such_user_42"),
+            e => panic!("Expected UserNotFound, got {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_suspended() {
+        let result = profile("spam_account_1", "twitter/profile_suspended.html").await;
+        match result {
+            Err(TwitterError::UserSuspended { username }) => assert_eq!(username, "spam_account_1"),
+            e => panic!("Expected UserSuspended, got {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_invalid_username() {
+        let result = profile("bad-name!", "twitter/profile.html").await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_profile_no_session() {
+        let driver = Arc::new(MockDriver::new());
+        let input = TwitterGetProfileInput {
+            username: "rustlang".to_string(),
+        };
+
+        let result =
+            twitter_get_profile(input, &invalid_session(), &fast_limiter(10), &driver).await;
+        assert!(matches!(result, Err(TwitterError::NoSession)));
+        assert!(driver.actions().is_empty());
+    }
+}
+
+mod notifications_tests {
+    use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    const NOTIFICATIONS_URL: &str = "https://x.com/notifications";
+
+    async fn notifications(
+        page: &str,
+        count: usize,
+        since: Option<String>,
+    ) -> Result<TwitterGetNotificationsResult, TwitterError> {
+        let driver = Arc::new(MockDriver::new().with_page(NOTIFICATIONS_URL, &fixture(page)));
+        let input = TwitterGetNotificationsInput {
+            tab: NotificationTab::All,
+            count,
+            since,
+        };
+        let session = mock_session();
+        twitter_get_notifications(input, &session, &fast_limiter(10), &driver, &Progress::none())
+            .await
+    }
+
+    #[test]
+    fn test_parse_notifications() {
+        let parsed = parse_notifications(&fixture("twitter/notifications.html"));
+        let kinds: Vec<NotificationKind> = parsed.iter().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                NotificationKind::Reply,
+                NotificationKind::Like,
+                NotificationKind::Mention,
+                NotificationKind::Follow,
+                NotificationKind::Retweet,
+            ]
+        );
+
+        let reply = &parsed[0];
+        assert_eq!(reply.id, "1795000000000000101");
+        assert_eq!(reply.tweet_id.as_deref(), Some("1795000000000000101"));
+        assert_eq!(reply.actors, vec!["ferris_fan"]);
+        assert_eq!(reply.timestamp.as_deref(), Some("2024-08-01T12:00:00.000Z"));
+        assert_eq!(reply.text, "Great thread, thanks!");
+
+        let like = &parsed[1];
+        assert_eq!(like.actors, vec!["ferris_fan", "tokio_rs"]);
+        assert_eq!(like.tweet_id.as_deref(), Some("1792000000000000001"));
+        assert_eq!(like.text, "Ferris Fan and Tokio liked your post");
+        assert!(like.timestamp.is_none());
+
+        let follow = &parsed[3];
+        assert_eq!(follow.actors, vec!["rustlang"]);
+        assert!(follow.tweet_id.is_none());
+
+        let retweet = &parsed[4];
+        assert_eq!(retweet.timestamp.as_deref(), Some("2024-07-31T18:00:00.000Z"));
+        assert_eq!(retweet.tweet_id.as_deref(), Some("1792000000000000001"));
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications() {
+        let result = notifications("twitter/notifications.html", 20, None)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.notifications.len(), 5);
+        assert!(result.since.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_respects_count() {
+        let result = notifications("twitter/notifications.html", 2, None)
+            .await
+            .unwrap();
+        assert_eq!(result.notifications.len(), 2);
+        assert_eq!(result.notifications[0].kind, NotificationKind::Reply);
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_since() {
+        let first = notifications("twitter/notifications.html", 20, None)
+            .await
+            .unwrap();
+
+        // Only the mention that arrived after the first poll is returned
+        let second = notifications("twitter/notifications_new.html", 20, first.since.clone())
+            .await
+            .unwrap();
+        assert_eq!(second.notifications.len(), 1);
+        assert_eq!(second.notifications[0].id, "1795000000000000109");
+        assert_eq!(second.notifications[0].kind, NotificationKind::Mention);
+
+        // Nothing new: the cursor is handed back unchanged
+        let third = notifications("twitter/notifications_new.html", 20, second.since.clone())
+            .await
+            .unwrap();
+        assert!(third.notifications.is_empty());
+        assert_eq!(third.since, second.since);
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_since_more_than_count() {
+        let all = parse_notifications(&fixture("twitter/notifications.html"));
+        let ids: Vec<&str> = all.iter().map(|n| n.id.as_str()).collect();
+        // The previous poll only saw the oldest notification
+        let since = FeedCursor {
+            query: NOTIFICATIONS_URL.to_string(),
+            last_id: ids[4].to_string(),
+            scroll_y: 0,
+            seen: vec![ids[4].to_string()],
+            pending: vec![],
+        };
+
+        // Four arrived since: two per poll, none lost
+        let first = notifications("twitter/notifications.html", 2, Some(since.encode()))
+            .await
+            .unwrap();
+        let returned: Vec<&str> = first.notifications.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(returned, ids[..2]);
+
+        let second = notifications("twitter/notifications.html", 2, first.since)
+            .await
+            .unwrap();
+        let returned: Vec<&str> = second.notifications.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(returned, ids[2..4]);
+
+        let third = notifications("twitter/notifications.html", 2, second.since)
+            .await
+            .unwrap();
+        assert!(third.notifications.is_empty());
+
+        // Once caught up, only what is newer still is returned
+        let fourth = notifications("twitter/notifications_new.html", 2, third.since)
+            .await
+            .unwrap();
+        let returned: Vec<&str> = fourth.notifications.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(returned, ["1795000000000000109"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_since_other_tab() {
+        let first = notifications("twitter/notifications.html", 20, None)
+            .await
+            .unwrap();
+        let driver = Arc::new(MockDriver::new());
+        let input = TwitterGetNotificationsInput {
+            tab: NotificationTab::Mentions,
+            count: 20,
+            since: first.since,
+        };
+        let session = mock_session();
+
+        let result =
+            twitter_get_notifications(input, &session, &fast_limiter(10), &driver, &Progress::none())
+                .await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_mentions_tab() {
+        let driver = Arc::new(MockDriver::new().with_page(
+            "https://x.com/notifications/mentions",
+            &fixture("twitter/notifications.html"),
+        ));
+        let input = TwitterGetNotificationsInput {
+            tab: NotificationTab::Mentions,
+            count: 1,
+            since: None,
+        };
+        let session = mock_session();
+
+        let result =
+            twitter_get_notifications(input, &session, &fast_limiter(10), &driver, &Progress::none())
+                .await
+                .unwrap();
+        assert_eq!(result.notifications.len(), 1);
+        assert_eq!(driver.actions()[0], "goto https://x.com/notifications/mentions");
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_invalid_count() {
+        let result = notifications("twitter/notifications.html", 0, None).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+
+        let result =
+            notifications("twitter/notifications.html", MAX_RESULTS_PER_REQUEST + 1, None).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_no_session() {
+        let driver = Arc::new(MockDriver::new());
+        let input = TwitterGetNotificationsInput {
+            tab: NotificationTab::All,
+            count: 20,
+            since: None,
+        };
+
+        let result = twitter_get_notifications(
+            input,
+            &invalid_session(),
+            &fast_limiter(10),
+            &driver,
+            &Progress::none(),
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::NoSession)));
+    }
+}
+
+mod dm_tests {
+    use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    const CONVERSATION_URL: &str = "https://x.com/messages/1001-165262228";
+    const COMPOSE_URL: &str = "https://x.com/messages/compose";
+
+    fn send_input(conversation_id: Option<&str>, username: Option<&str>) -> TwitterSendDmInput {
+        TwitterSendDmInput {
+            conversation_id: conversation_id.map(str::to_string),
+            username: username.map(str::to_string),
+            text: "See you in Montreal!".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_conversations() {
+        let parsed = parse_conversations(&fixture("twitter/dm_conversations.html"));
+        assert_eq!(parsed.len(), 3);
+
+        let first = &parsed[0];
+        assert_eq!(first.conversation_id, "1001-165262228");
+        assert_eq!(first.display_name, "Ferris Crab");
+        assert_eq!(first.participants, vec!["ferris"]);
+        assert_eq!(first.last_message, "Are you coming to RustConf?");
+        assert_eq!(first.timestamp.as_deref(), Some("2024-05-20T10:15:00.000Z"));
+        assert!(first.unread);
+
+        let group = &parsed[1];
+        assert_eq!(group.conversation_id, "1795000000000000500");
+        assert_eq!(group.display_name, "Compiler team");
+        assert_eq!(group.participants, vec!["ralfj", "nikomatsakis"]);
+        assert!(!group.unread);
+    }
+
+    #[test]
+    fn test_parse_messages() {
+        let parsed = parse_messages(&fixture("twitter/dm_conversation.html"));
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].sender.as_deref(), Some("ferris"));
+        assert!(!parsed[0].outgoing);
+        assert_eq!(parsed[0].text, "Hi! Long time no see.");
+        assert!(parsed[1].outgoing);
+        assert!(parsed[1].sender.is_none());
+        assert_eq!(parsed[2].timestamp.as_deref(), Some("2024-05-20T10:15:00.000Z"));
+    }
+
+    #[test]
+    fn test_debug_redacts_message_bodies() {
+        let conversations = parse_conversations(&fixture("twitter/dm_conversations.html"));
+        let messages = parse_messages(&fixture("twitter/dm_conversation.html"));
+        let input = send_input(Some("1001-165262228"), None);
+
+        let debug = format!("{:?} {:?} {:?}", conversations, messages, input);
+        assert!(!debug.contains("RustConf"));
+        assert!(!debug.contains("Long time no see"));
+        assert!(!debug.contains("Montreal"));
+        assert!(debug.contains("1001-165262228"));
+    }
+
+    #[tokio::test]
+    async fn test_list_conversations() {
+        let driver = Arc::new(MockDriver::new().with_page(
+            "https://x.com/messages",
+            &fixture("twitter/dm_conversations.html"),
+        ));
+        let input = TwitterListConversationsInput { count: 2 };
+        let session = mock_session();
+
+        let result = twitter_list_conversations(input, &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.conversations.len(), 2);
+        assert_eq!(result.conversations[0].conversation_id, "1001-165262228");
+    }
+
+    #[tokio::test]
+    async fn test_read_dm_keeps_most_recent() {
+        let driver = Arc::new(
+            MockDriver::new().with_page(CONVERSATION_URL, &fixture("twitter/dm_conversation.html")),
+        );
+        let input = TwitterReadDmInput {
+            conversation_id: "1001-165262228".to_string(),
+            count: 2,
+        };
+        let session = mock_session();
+
+        let result = twitter_read_dm(input, &session, &fast_limiter(10), &driver)
+            .await
+            .unwrap();
+        assert_eq!(result.conversation_id, "1001-165262228");
+        let texts: Vec<&str> = result.messages.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["Hey Ferris, how are you?", "Are you coming to RustConf?"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_dm_invalid_conversation_id() {
+        let driver = Arc::new(MockDriver::new());
+        let session = mock_session();
+        for conversation_id in ["", "abc", "1-2-3", "12-", "../settings"] {
+            let input = TwitterReadDmInput {
+                conversation_id: conversation_id.to_string(),
+                count: 10,
+            };
+            let result = twitter_read_dm(input, &session, &fast_limiter(10), &driver).await;
+            assert!(
+                matches!(result, Err(TwitterError::InvalidInput { .. })),
+                "{}",
+                conversation_id
+            );
+        }
+        assert!(driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_send_dm_to_conversation() {
+        let driver = Arc::new(MockDriver::new().with_snapshots(
+            CONVERSATION_URL,
+            &[
+                &fixture("twitter/dm_conversation.html"),
+                &fixture("twitter/dm_conversation_sent.html"),
+            ],
+        ));
+        let session = mock_session();
+
+        let result = twitter_send_dm(
+            send_input(Some("1001-165262228"), None),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+        assert_eq!(result.conversation_id.as_deref(), Some("1001-165262228"));
+        assert!(driver
+            .actions()
+            .contains(&"click button[data-testid=\"dmComposerSendButton\"]".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_send_dm_to_username() {
+        let driver = Arc::new(MockDriver::new().with_snapshots(
+            COMPOSE_URL,
+            &[
+                &fixture("twitter/dm_compose.html"),
+                &fixture("twitter/dm_conversation.html"),
+                &fixture("twitter/dm_conversation_sent.html"),
+            ],
+        ));
+        let session = mock_session();
+
+        let result = twitter_send_dm(
+            send_input(None, Some("@Ferris")),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+        let actions = driver.actions();
+        assert!(actions.contains(&"type input[data-testid=\"searchPeople\"] Ferris".to_string()));
+        assert!(actions.contains(&"click button[data-testid=\"nextButton\"]".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_send_dm_unknown_username() {
+        let driver =
+            Arc::new(MockDriver::new().with_page(COMPOSE_URL, &fixture("twitter/dm_compose.html")));
+        let session = mock_session();
+
+        // "ferris_fan" is only the second suggestion for "ferris_f"
+        let result = twitter_send_dm(
+            send_input(None, Some("ferris_f")),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::UserNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_send_dm_unconfirmed() {
+        let driver = Arc::new(
+            MockDriver::new().with_page(CONVERSATION_URL, &fixture("twitter/dm_conversation.html")),
+        );
+        let session = mock_session();
+
+        let result = twitter_send_dm(
+            send_input(Some("1001-165262228"), None),
+            &session,
+            &fast_limiter(10),
+            &driver,
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::Unknown { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_send_dm_invalid_input() {
+        let driver = Arc::new(MockDriver::new());
+        let session = mock_session();
+
+        let mut inputs = vec![
+            send_input(None, None),
+            send_input(Some("1001-165262228"), Some("ferris")),
+            send_input(None, Some("not a username")),
+        ];
+        let mut empty = send_input(Some("1001-165262228"), None);
+        empty.text = "  ".to_string();
+        inputs.push(empty);
+        let mut too_long = send_input(Some("1001-165262228"), None);
+        too_long.text = "a".repeat(DM_MAX_LENGTH + 1);
+        inputs.push(too_long);
+
+        for input in inputs {
+            let result = twitter_send_dm(input, &session, &fast_limiter(10), &driver).await;
+            assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+        }
+        assert!(driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dm_no_session() {
+        let driver = Arc::new(MockDriver::new());
+
+        let result = twitter_send_dm(
+            send_input(Some("1001-165262228"), None),
+            &invalid_session(),
+            &fast_limiter(10),
+            &driver,
+        )
+        .await;
+        assert!(matches!(result, Err(TwitterError::NoSession)));
+
+        let input = TwitterListConversationsInput { count: 20 };
+        let result =
+            twitter_list_conversations(input, &invalid_session(), &fast_limiter(10), &driver).await;
+        assert!(matches!(result, Err(TwitterError::NoSession)));
+    }
+
+    #[test]
+    fn test_dm_rate_limiter_preset() {
+        let config = RateLimitConfig::for_dm();
+        assert!(config.max_requests < RateLimitConfig::for_follow().max_requests);
+        assert!(config.min_delay_ms >= RateLimitConfig::for_follow().min_delay_ms);
+    }
+}
+
+mod session_tests {
+    use super::*;
+    use crate::testing::MockDriver;
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+    use synmem_core::{Cookie, SessionManager, SessionManagerConfig};
+
+    const PASSWORD: &str = "correct horse battery staple";
+
+    fn cookie(name: &str, value: &str, domain: &str) -> Cookie {
+        Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: domain.to_string(),
+            path: "/".to_string(),
+            secure: true,
+            http_only: false,
+            same_site: Some("Lax".to_string()),
+            expires: None,
+        }
+    }
+
+    fn twitter_cookies() -> Vec<Cookie> {
+        vec![
+            cookie("auth_token", "secret_auth", ".x.com"),
+            cookie("ct0", "csrf_value", ".x.com"),
+            cookie("twid", "u%3D165262228", ".x.com"),
+            cookie("session", "other_site", "example.com"),
+        ]
+    }
+
+    /// A session manager whose store is empty, holding `cookies` in memory
+    fn session_manager(lifetime_days: i64, cookies: Vec<Cookie>) -> SessionManager {
+        let mut manager = SessionManager::new(SessionManagerConfig {
+            storage_path: std::env::temp_dir().join("synmem-twitter-session-tests"),
+            default_lifetime_days: lifetime_days,
+            auto_refresh: false,
+        });
+        manager
+            .create_session("twitter-main", cookies, PASSWORD)
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_from_cookies() {
+        let session = TwitterSession::from_cookies(&twitter_cookies()).unwrap();
+        assert_eq!(
+            session.cookies,
+            "auth_token=secret_auth; ct0=csrf_value; twid=u%3D165262228"
+        );
+        assert_eq!(session.csrf_token, "csrf_value");
+        assert_eq!(session.user_id.as_deref(), Some("165262228"));
+    }
+
+    #[test]
+    fn test_from_cookies_requires_login() {
+        let cookies = vec![cookie("ct0", "csrf_value", "x.com")];
+        assert!(matches!(
+            TwitterSession::from_cookies(&cookies),
+            Err(TwitterError::NoSession)
+        ));
+
+        // Cookies of other domains are not used
+        let cookies = vec![
+            cookie("auth_token", "secret_auth", "notx.com"),
+            cookie("ct0", "csrf_value", "x.com"),
+        ];
+        assert!(matches!(
+            TwitterSession::from_cookies(&cookies),
+            Err(TwitterError::NoSession)
+        ));
+    }
+
+    #[test]
+    fn test_from_cookies_expired_login() {
+        let mut cookies = twitter_cookies();
+        cookies[0].expires = Some(Utc::now() - Duration::hours(1));
+        assert!(matches!(
+            TwitterSession::from_cookies(&cookies),
+            Err(TwitterError::SessionExpired)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_from_profile_sets_cookies() {
+        let mut manager = session_manager(30, twitter_cookies());
+        let driver = Arc::new(MockDriver::new());
+
+        let session = TwitterSession::from_profile(&mut manager, "twitter-main", PASSWORD, &driver)
+            .await
+            .unwrap();
+        assert_eq!(session.csrf_token, "csrf_value");
+        // Only the x.com cookies reach the browser
+        assert_eq!(driver.actions(), vec!["set_cookies 3"]);
+        assert!(!format!("{:?}", session).contains("secret_auth"));
+    }
+
+    #[tokio::test]
+    async fn test_from_profile_expired() {
+        let mut manager = session_manager(-1, twitter_cookies());
+        let driver = Arc::new(MockDriver::new());
+
+        let result =
+            TwitterSession::from_profile(&mut manager, "twitter-main", PASSWORD, &driver).await;
+        assert!(matches!(result, Err(TwitterError::SessionExpired)));
+        assert!(driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_from_profile_missing() {
+        let mut manager = session_manager(30, twitter_cookies());
+        let driver = Arc::new(MockDriver::new());
+
+        let result =
+            TwitterSession::from_profile(&mut manager, "twitter-alt", PASSWORD, &driver).await;
+        assert!(matches!(result, Err(TwitterError::NoSession)));
+    }
+}
+
+mod interstitial_tests {
+    use super::parser::{detect_interstitial, Interstitial};
+    use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    const TWEET_URL: &str = "https://x.com/i/status/1790000000000000001";
+
+    /// Read a thread from a tweet page redirected to `landing`
+    async fn read_redirected(landing: &str, page: &str) -> TwitterError {
+        let driver = Arc::new(
+            MockDriver::new()
+                .with_redirect(TWEET_URL, landing)
+                .with_page(landing, &fixture(page)),
+        );
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: "1790000000000000001".to_string(),
+            max_tweets: 10,
+            download_media: false,
+        };
+        let session = mock_session();
+        twitter_read_thread(input, &session, &fast_limiter(10), &driver, &Progress::none())
+            .await
+            .unwrap_err()
+    }
+
+    #[test]
+    fn test_detect_interstitials() {
+        let cases = [
+            ("https://x.com/i/flow/login", "twitter/login_wall.html", Interstitial::LoginWall),
+            ("https://x.com/home", "twitter/login_wall.html", Interstitial::LoginWall),
+            ("https://x.com/account/access", "twitter/challenge.html", Interstitial::Challenge),
+            (
+                "https://x.com/account/access",
+                "twitter/account_locked.html",
+                Interstitial::AccountLocked,
+            ),
+            ("https://x.com/rustlang", "twitter/age_gate.html", Interstitial::AgeGate),
+            ("https://x.com/rustlang", "twitter/rate_limited.html", Interstitial::RateLimited),
+        ];
+        for (url, page, expected) in cases {
+            assert_eq!(detect_interstitial(url, &fixture(page)), Some(expected), "{}", page);
+        }
+
+        // An empty access page is still the locked account page
+        assert_eq!(
+            detect_interstitial("https://x.com/account/access?lang=en", ""),
+            Some(Interstitial::AccountLocked)
+        );
+
+        // The browser's own error page for a bare 429 response
+        assert_eq!(
+            detect_interstitial(TWEET_URL, "<body><div>HTTP ERROR 429</div></body>"),
+            Some(Interstitial::RateLimited)
+        );
+    }
+
+    #[test]
+    fn test_detect_interstitial_ignores_content() {
+        assert_eq!(
+            detect_interstitial(TWEET_URL, &fixture("twitter/tweet.html")),
+            None
+        );
+        assert_eq!(
+            detect_interstitial("https://x.com/rustlang", &fixture("twitter/profile.html")),
+            None
+        );
+
+        // Tweets may quote the wording of any interstitial
+        let quoting = r#"<article data-testid="tweet"><div data-testid="tweetText">
+            <span>My account has been locked after some unusual activity</span>
+        </div></article>"#;
+        assert_eq!(detect_interstitial(TWEET_URL, quoting), None);
+
+        // Paths that merely start like an interstitial's are content
+        assert_eq!(detect_interstitial("https://x.com/login_fan", ""), None);
+    }
+
+    #[tokio::test]
+    async fn test_login_wall() {
+        let error = read_redirected("https://x.com/i/flow/login", "twitter/login_wall.html").await;
+        assert!(matches!(error, TwitterError::LoginRequired));
+        assert!(error.needs_human());
+    }
+
+    #[tokio::test]
+    async fn test_challenge() {
+        let error = read_redirected("https://x.com/account/access", "twitter/challenge.html").await;
+        assert!(matches!(error, TwitterError::ChallengeRequired));
+    }
+
+    #[tokio::test]
+    async fn test_account_locked() {
+        let error =
+            read_redirected("https://x.com/account/access", "twitter/account_locked.html").await;
+        assert!(matches!(error, TwitterError::AccountLocked));
+    }
+
+    #[tokio::test]
+    async fn test_profile_login_wall() {
+        let driver = Arc::new(
+            MockDriver::new()
+                .with_redirect("https://x.com/rustlang", "https://x.com/i/flow/login")
+                .with_page("https://x.com/i/flow/login", &fixture("twitter/login_wall.html")),
+        );
+        let input = TwitterGetProfileInput {
+            username: "rustlang".to_string(),
+        };
+        let session = mock_session();
+
+        let result = twitter_get_profile(input, &session, &fast_limiter(10), &driver).await;
+        assert!(matches!(result, Err(TwitterError::LoginRequired)));
+    }
+
+    /// Driver showing the rate limit page instead of the tweet
+    fn rate_limited_driver() -> MockDriver {
+        MockDriver::new()
+            .with_page(TWEET_URL, &fixture("twitter/rate_limited.html"))
+            .with_missing(super::page::TWEET_SELECTOR)
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_page() {
+        let driver = Arc::new(rate_limited_driver());
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: "1790000000000000001".to_string(),
+            max_tweets: 10,
+            download_media: false,
+        };
+        let session = mock_session();
+        let error =
+            twitter_read_thread(input, &session, &fast_limiter(10), &driver, &Progress::none())
+                .await
+                .unwrap_err();
+        assert!(matches!(error, TwitterError::Throttled { wait_seconds: 900 }));
+        assert!(error.is_recoverable());
+        assert!(!error.needs_human());
+    }
+
+    #[tokio::test]
+    async fn test_throttled_tool_backs_off() {
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_tools(&mut registry, mock_session());
+        let context = crate::testing::test_context_with(rate_limited_driver());
+
+        let result = registry
+            .call(
+                "twitter_read_thread",
+                serde_json::json!({"tweet_url_or_id": "1790000000000000001"}),
+                &context,
+                &crate::tools::RequestContext::detached(),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error);
+
+        // Both the operation and the account stop until Twitter calms down
+        for key in ["123456789/twitter.read", "123456789/twitter.account"] {
+            let limiter = context.rate_limiters.get_or_create(key, RateLimitConfig::for_read());
+            assert_eq!(limiter.remaining_tokens(), 0, "{}", key);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_throttled_post_backs_off() {
+        let driver = MockDriver::new()
+            .with_page("https://x.com/compose/post", &fixture("twitter/rate_limited.html"))
+            .with_missing("div[data-testid=\"tweetTextarea_0\"]");
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_tools(&mut registry, mock_session());
+        let context = crate::testing::test_context_with(driver);
+
+        let result = registry
+            .call(
+                "twitter_post",
+                serde_json::json!({"text": "Hello?"}),
+                &context,
+                &crate::tools::RequestContext::detached(),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error);
+
+        for key in ["123456789/twitter.post", "123456789/twitter.account"] {
+            let limiter = context.rate_limiters.get_or_create(key, RateLimitConfig::for_post());
+            assert_eq!(limiter.remaining_tokens(), 0, "{}", key);
+        }
+    }
+}
+
+mod retry_tests {
+    use super::*;
+    use crate::config::{RetryPolicies, RetryPolicy};
+    use crate::testing::{fixture, test_context_with, MockDriver};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 1000,
+            deadline_seconds: 10,
+        }
+    }
+
+    fn network_error() -> TwitterError {
+        TwitterError::NetworkError {
+            message: "connection reset".to_string(),
+        }
+    }
+
+    /// Recoverable error without a wait of its own
+    fn transient_error() -> TwitterError {
+        TwitterError::RateLimited { wait_seconds: 0 }
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let calls = AtomicU32::new(0);
+        let outcome = retry(&fast_policy(5), || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(transient_error()),
+                _ => Ok("posted"),
+            }
+        })
+        .await;
+
+        assert_eq!(outcome.result.unwrap(), "posted");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        // The delay doubles after each failure
+        let backoffs: Vec<u64> = outcome.attempts.iter().map(|a| a.backoff_ms).collect();
+        assert_eq!(backoffs, vec![10, 20]);
+        assert_eq!(outcome.attempts[0].attempt, 1);
+        assert!(outcome.attempts[0].error.contains("Rate limit exceeded"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let outcome: RetryOutcome<()> = retry(&fast_policy(3), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(transient_error())
+        })
+        .await;
+
+        assert!(matches!(outcome.result, Err(TwitterError::RateLimited { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(outcome.attempts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unrecoverable_errors_are_not_retried() {
+        let calls = AtomicU32::new(0);
+        let outcome: RetryOutcome<()> = retry(&fast_policy(3), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TwitterError::ChallengeRequired)
+        })
+        .await;
+
+        assert!(matches!(outcome.result, Err(TwitterError::ChallengeRequired)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(outcome.attempts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_is_not_retried_early() {
+        let calls = AtomicU32::new(0);
+        let outcome: RetryOutcome<()> = retry(&fast_policy(3), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TwitterError::Throttled { wait_seconds: 900 })
+        })
+        .await;
+
+        // Waiting 900 seconds would pass the deadline, so it gives up at once
+        assert!(matches!(
+            outcome.result,
+            Err(TwitterError::Throttled { wait_seconds: 900 })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(outcome.attempts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backoff_waits_for_retry_after() {
+        let outcome: RetryOutcome<()> = retry(&fast_policy(2), || async {
+            Err(TwitterError::RateLimited { wait_seconds: 1 })
+        })
+        .await;
+
+        assert_eq!(outcome.attempts.len(), 1);
+        assert_eq!(outcome.attempts[0].backoff_ms, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_at_deadline() {
+        let policy = RetryPolicy {
+            initial_backoff_ms: 5000,
+            deadline_seconds: 1,
+            ..fast_policy(5)
+        };
+        let calls = AtomicU32::new(0);
+        let outcome: RetryOutcome<()> = retry(&policy, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(network_error())
+        })
+        .await;
+
+        // Waiting 5 seconds would pass the deadline, so it gives up at once
+        assert!(outcome.result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(outcome.attempts.is_empty());
+    }
+
+    /// Spend the budget of the limiter under `key` for the next second
+    async fn exhaust(context: &crate::tools::ToolContext<MockDriver>, key: &str) {
+        let config = RateLimitConfig {
+            max_requests: 1,
+            window_seconds: 1,
+            min_delay_ms: 0,
+            ..Default::default()
+        };
+        let limiter = context.rate_limiters.get_or_create(key, config);
+        limiter.acquire().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tool_result_lists_attempts() {
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_tools(&mut registry, mock_session());
+        let driver = MockDriver::new()
+            .with_page(
+                "https://x.com/i/status/1790000000000000001",
+                &fixture("twitter/rate_limited.html"),
+            )
+            .with_missing(super::page::TWEET_SELECTOR);
+        let policies =
+            RetryPolicies([("twitter_read_*".to_string(), fast_policy(2))].into_iter().collect());
+        let context = test_context_with(driver).with_retry_policies(policies);
+        exhaust(&context, "123456789/twitter.read").await;
+
+        let result = registry
+            .call(
+                "twitter_read_thread",
+                serde_json::json!({"tweet_url_or_id": "1790000000000000001"}),
+                &context,
+                &crate::tools::RequestContext::detached(),
+            )
+            .await
+            .unwrap();
+
+        // Refused by the limiter for a second, then throttled by Twitter
+        assert!(result.is_error);
+        assert_eq!(result.content.len(), 2);
+        let crate::protocol::Content::Text { text } = &result.content[0] else {
+            panic!("expected text content");
+        };
+        assert!(text.contains("rate limiting"));
+        let crate::protocol::Content::Text { text } = &result.content[1] else {
+            panic!("expected text content");
+        };
+        let history: serde_json::Value = serde_json::from_str(text).unwrap();
+        let attempts = history["attempts"].as_array().unwrap();
+        assert_eq!(attempts.len(), 1);
+        assert!(attempts[0]["error"].as_str().unwrap().contains("Rate limit exceeded"));
+        assert_eq!(attempts[0]["backoff_ms"], serde_json::json!(1000));
+    }
+
+    #[tokio::test]
+    async fn test_backing_off_call_lets_others_use_the_browser() {
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_tools(&mut registry, mock_session());
+        crate::tools::browser::register_tools(&mut registry);
+        let policy = RetryPolicy {
+            initial_backoff_ms: 1000,
+            ..fast_policy(2)
+        };
+        let policies =
+            RetryPolicies([("twitter_read_thread".to_string(), policy)].into_iter().collect());
+        let context = test_context_with(MockDriver::new()).with_retry_policies(policies);
+        exhaust(&context, "123456789/twitter.read").await;
+
+        let request = crate::tools::RequestContext::detached();
+        let thread = registry.call(
+            "twitter_read_thread",
+            serde_json::json!({"tweet_url_or_id": "1790000000000000001"}),
+            &context,
+            &request,
+        );
+        let navigate = async {
+            // Once the thread call is backing off
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            let started = std::time::Instant::now();
+            let result = registry
+                .call(
+                    "browser_navigate",
+                    serde_json::json!({"url": "https://example.com"}),
+                    &context,
+                    &crate::tools::RequestContext::detached(),
+                )
+                .await
+                .unwrap();
+            (result, started.elapsed())
+        };
+        let (thread, (navigate, waited)) = tokio::join!(thread, navigate);
+
+        assert!(!navigate.is_error);
+        assert!(waited < std::time::Duration::from_millis(500));
+        // The thread was read after the navigation, on its second attempt
+        assert_eq!(thread.unwrap().content.len(), 2);
+        assert_eq!(context.driver.actions()[0], "goto https://example.com");
+        assert!(context.browser_lease.try_lock().is_ok());
+    }
+
+    /// Post through the MCP tool with `policies` while the post limiter is
+    /// spent for a second, behind the rate limit page
+    async fn post_throttled(policies: &[(&str, RetryPolicy)]) -> crate::protocol::CallToolResult {
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_tools(&mut registry, mock_session());
+        let driver = MockDriver::new()
+            .with_page("https://x.com/compose/post", &fixture("twitter/rate_limited.html"))
+            .with_missing("div[data-testid=\"tweetTextarea_0\"]");
+        let policies = policies.iter().map(|(key, policy)| (key.to_string(), *policy));
+        let context =
+            test_context_with(driver).with_retry_policies(RetryPolicies(policies.collect()));
+        exhaust(&context, "123456789/twitter.post").await;
+
+        registry
+            .call(
+                "twitter_post",
+                serde_json::json!({"text": "Hello?"}),
+                &context,
+                &crate::tools::RequestContext::detached(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_patterns_do_not_retry_posts() {
+        let result = post_throttled(&[("twitter_*", fast_policy(2))]).await;
+        assert!(result.is_error);
+        // No attempt history: the post was tried once
+        assert_eq!(result.content.len(), 1);
+
+        // Naming the tool opts it in
+        let result = post_throttled(&[("twitter_post", fast_policy(2))]).await;
+        assert!(result.is_error);
+        assert_eq!(result.content.len(), 2);
+    }
+}
+
+mod archive_tests {
+    use super::super::archive::{
+        best_video_variants, original_image_url, syndication_token, SyndicationTweet,
+    };
+    use super::*;
+    use crate::testing::{test_context_with, MockDriver};
+    use serde_json::json;
+
+    /// A 1x1 PNG
+    const PNG_URL: &str = "data:image/png;base64,\
+        iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGA\
+        WjR9awAAAABJRU5ErkJggg==";
+
+    fn tweet_with_media(id: &str, urls: &[&str]) -> Tweet {
+        Tweet {
+            id: id.to_string(),
+            text: String::new(),
+            author: "rustlang".to_string(),
+            author_display_name: "Rust Language".to_string(),
+            timestamp: String::new(),
+            likes: 0,
+            retweets: 0,
+            replies: 0,
+            media: urls
+                .iter()
+                .map(|url| TweetMedia {
+                    media_type: "image".to_string(),
+                    url: url.to_string(),
+                    alt_text: None,
+                    local: None,
+                })
+                .collect(),
+            quoted_tweet: None,
+        }
+    }
+
+    #[test]
+    fn test_syndication_token() {
+        // Computed with the embed script's `(id / 1e15 * Math.PI).toString(36)`
+        assert_eq!(syndication_token("1791000000000000010").unwrap(), "4calbt19r8");
+        assert_eq!(syndication_token("1629307668568633344").unwrap(), "3y6mctgwzxo");
+        assert_eq!(syndication_token("20").unwrap(), "6dq1a2xwd93");
+        assert!(syndication_token("not-an-id").is_none());
+    }
+
+    #[test]
+    fn test_original_image_url() {
+        assert_eq!(
+            original_image_url("https://pbs.twimg.com/media/GOaaa.jpg?format=jpg&name=360x360"),
+            "https://pbs.twimg.com/media/GOaaa.jpg?format=jpg&name=orig"
+        );
+        assert_eq!(
+            original_image_url("https://pbs.twimg.com/media/GOaaa.jpg"),
+            "https://pbs.twimg.com/media/GOaaa.jpg?name=orig"
+        );
+        let thumbnail = "https://pbs.twimg.com/amplify_video_thumb/1/img/ccc.jpg?name=small";
+        assert_eq!(original_image_url(thumbnail), thumbnail);
+        assert_eq!(original_image_url(PNG_URL), PNG_URL);
+    }
+
+    #[test]
+    fn test_best_video_variants() {
+        const HIGH_BITRATE_URL: &str = "https://v.test/1280.mp4";
+        let details: SyndicationTweet = serde_json::from_value(json!({
+            "mediaDetails": [
+                {"type": "photo", "media_url_https": "https://pbs.twimg.com/media/a.jpg"},
+                {
+                    "type": "video",
+                    "video_info": {"variants": [
+                        {"content_type": "application/x-mpegURL", "url": "https://v.test/1"},
+                        {"bitrate": 832000, "content_type": "video/mp4", "url": "https://v.test/2"},
+                        {"bitrate": 2176000, "content_type": "video/mp4", "url": HIGH_BITRATE_URL}
+                    ]}
+                }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(best_video_variants(&details), [HIGH_BITRATE_URL]);
+    }
+
+    #[tokio::test]
+    async fn test_media_store_deduplicates_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = MediaStore::new(dir.path());
+
+        let first = store.put("https://a.example/1", b"\xff\xd8\xffjpeg", "").await.unwrap();
+        let second = store.put("https://b.example/2", b"\xff\xd8\xffjpeg", "").await.unwrap();
+
+        assert_eq!(first.path, second.path);
+        assert_eq!(first.mime_type, "image/jpeg");
+        assert_eq!(first.size, 7);
+        assert_eq!(first.sha256.len(), 64);
+        assert!(first.path.to_string_lossy().ends_with(".jpg"));
+        let objects = std::fs::read_dir(first.path.parent().unwrap()).unwrap().count();
+        assert_eq!(objects, 1);
+
+        let found = store.lookup("https://b.example/2").await.unwrap();
+        assert_eq!(found, second);
+        assert!(store.lookup("https://c.example/3").await.is_none());
+        std::fs::remove_file(&first.path).unwrap();
+        assert!(store.lookup("https://a.example/1").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_archive_media_shares_files_between_tweets() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = MediaStore::new(dir.path());
+        let mut quoting = tweet_with_media("1", &[PNG_URL]);
+        quoting.quoted_tweet = Some(Box::new(tweet_with_media("2", &[PNG_URL])));
+        let mut tweets = vec![
+            quoting,
+            tweet_with_media("3", &[PNG_URL, "data:image/png;base64,***"]),
+        ];
+
+        let archived = archive_media(&mut tweets, &store).await;
+
+        assert_eq!(archived, 3);
+        let local = tweets[0].media[0].local.clone().unwrap();
+        assert_eq!(local.mime_type, "image/png");
+        assert!(local.path.exists());
+        let quoted = tweets[0].quoted_tweet.as_ref().unwrap();
+        assert_eq!(quoted.media[0].local.as_ref(), Some(&local));
+        assert_eq!(tweets[1].media[0].local.as_ref(), Some(&local));
+        // A broken file is skipped without failing the others
+        assert!(tweets[1].media[1].local.is_none());
+    }
+
+    fn timeline_with_photo() -> MockDriver {
+        let html = format!(
+            r#"<article data-testid="tweet">
+                <a href="/rustlang/status/1791000000000000010"><time>May 5</time></a>
+                <div data-testid="tweetPhoto"><img alt="Ferris" src="{}"></div>
+            </article>"#,
+            PNG_URL
+        );
+        MockDriver::new().with_page("https://x.com/home", &html)
+    }
+
+    #[tokio::test]
+    async fn test_timeline_tool_downloads_media() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_tools(&mut registry, mock_session());
+        let context =
+            test_context_with(timeline_with_photo()).with_media_store(MediaStore::new(dir.path()));
+
+        let result = registry
+            .call(
+                "twitter_get_timeline",
+                json!({"download_media": true}),
+                &context,
+                &crate::tools::RequestContext::detached(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.is_error);
+        let crate::protocol::Content::Text { text } = &result.content[0] else {
+            panic!("expected text content");
+        };
+        let result: TwitterGetTimelineResult = serde_json::from_str(text).unwrap();
+        let local = result.tweets[0].media[0].local.as_ref().unwrap();
+        assert!(local.path.starts_with(dir.path()));
+        assert_eq!(local.source_url, PNG_URL);
+    }
+
+    #[tokio::test]
+    async fn test_download_media_requires_a_store() {
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_tools(&mut registry, mock_session());
+        let context = test_context_with(timeline_with_photo());
+
+        let result = registry
+            .call(
+                "twitter_get_timeline",
+                json!({"download_media": true}),
+                &context,
+                &crate::tools::RequestContext::detached(),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.is_error);
+    }
+}
+
+mod schedule_tests {
+    use super::*;
+    use crate::storage::{FileStorage, MemoryStorage};
+    use crate::testing::{fixture, test_context_with, MockDriver};
+    use crate::tools::twitter::file_lock::FileLock;
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    fn memory_queue() -> PostQueue {
+        PostQueue::new(Arc::new(MemoryStorage::new()), "123456789")
+    }
+
+    fn schedule_input(text: &str, publish_at: chrono::DateTime<Utc>) -> TwitterSchedulePostInput {
+        TwitterSchedulePostInput {
+            post: TwitterPostInput {
+                text: text.to_string(),
+                media_urls: vec![],
+                reply_to: None,
+            },
+            publish_at,
+        }
+    }
+
+    /// Schedule `text` and move it into the past, so that it is due
+    async fn schedule_due(queue: &PostQueue, text: &str) -> ScheduledPost {
+        let input = schedule_input(text, Utc::now() + Duration::hours(1));
+        let mut post = twitter_schedule_post(input, queue).await.unwrap().post.unwrap();
+        post.publish_at = Utc::now() - Duration::minutes(1);
+        queue.save(&post).await.unwrap();
+        post
+    }
+
+    #[tokio::test]
+    async fn test_schedule_and_list() {
+        let queue = memory_queue();
+        let later = Utc::now() + Duration::hours(2);
+        let sooner = Utc::now() + Duration::hours(1);
+        twitter_schedule_post(schedule_input("Second", later), &queue)
+            .await
+            .unwrap();
+        let result = twitter_schedule_post(schedule_input("First", sooner), &queue)
+            .await
+            .unwrap();
+        assert!(result.success);
+        let post = result.post.unwrap();
+        assert_eq!(post.status, ScheduledPostStatus::Pending);
+        assert_eq!(post.publish_at, sooner);
+
+        let listed = twitter_list_scheduled(TwitterListScheduledInput::default(), &queue)
+            .await
+            .unwrap();
+        let texts: Vec<&str> = listed.posts.iter().map(|p| p.post.text.as_str()).collect();
+        assert_eq!(texts, ["First", "Second"]);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_rejects_past_time() {
+        let queue = memory_queue();
+        let input = schedule_input("Too late", Utc::now() - Duration::minutes(1));
+
+        let result = twitter_schedule_post(input, &queue).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+        assert!(queue.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_schedule_validates_post() {
+        let queue = memory_queue();
+        let input = schedule_input("", Utc::now() + Duration::hours(1));
+
+        let result = twitter_schedule_post(input, &queue).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_reschedule_and_cancel() {
+        let queue = memory_queue();
+        let input = schedule_input("Hello", Utc::now() + Duration::hours(1));
+        let id = twitter_schedule_post(input, &queue).await.unwrap().post.unwrap().id;
+
+        let new_time = Utc::now() + Duration::days(1);
+        let input = TwitterReschedulePostInput {
+            id: id.clone(),
+            publish_at: new_time,
+        };
+        let result = twitter_reschedule_post(input, &queue).await.unwrap();
+        assert_eq!(result.post.unwrap().publish_at, new_time);
+        assert_eq!(queue.get(&id).await.unwrap().unwrap().publish_at, new_time);
+
+        let input = TwitterCancelScheduledInput { id: id.clone() };
+        let result = twitter_cancel_scheduled(input, &queue).await.unwrap();
+        assert_eq!(result.post.unwrap().id, id);
+        assert!(queue.list().await.unwrap().is_empty());
+
+        let input = TwitterCancelScheduledInput { id };
+        let result = twitter_cancel_scheduled(input, &queue).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_ids_are_rejected() {
+        let queue = memory_queue();
+        for id in ["", "../other/key", "missing"] {
+            let input = TwitterCancelScheduledInput { id: id.to_string() };
+            let result = twitter_cancel_scheduled(input, &queue).await;
+            assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_posts_and_records_tweet() {
+        let queue = memory_queue();
+        let due = schedule_due(&queue, "Good morning!").await;
+        let input = schedule_input("Later", Utc::now() + Duration::hours(1));
+        let later = twitter_schedule_post(input, &queue).await.unwrap().post.unwrap();
+        let driver = compose_driver();
+
+        let processed = publish_due(&queue, &mock_session(), &fast_limiter(10), &driver, &Arc::new(Mutex::new(())))
+            .await
+            .unwrap();
+        assert_eq!(processed, 1);
+        assert!(driver.actions().contains(&"goto https://x.com/compose/post".to_string()));
+
+        let published = queue.get(&due.id).await.unwrap().unwrap();
+        assert_eq!(published.status, ScheduledPostStatus::Published);
+        assert!(published.error.is_none());
+        let later = queue.get(&later.id).await.unwrap().unwrap();
+        assert_eq!(later.status, ScheduledPostStatus::Pending);
+
+        // Published posts can no longer be changed
+        let input = TwitterCancelScheduledInput { id: due.id };
+        let result = twitter_cancel_scheduled(input, &queue).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_defers_when_rate_limited() {
+        let queue = memory_queue();
+        let first = schedule_due(&queue, "First").await;
+        let second = schedule_due(&queue, "Second").await;
+
+        let processed = publish_due(&queue, &mock_session(), &fast_limiter(1), &compose_driver(), &Arc::new(Mutex::new(())))
+            .await
+            .unwrap();
+        assert_eq!(processed, 1);
+
+        let mut deferred = 0;
+        for post in [&first, &second] {
+            let post = queue.get(&post.id).await.unwrap().unwrap();
+            if post.status == ScheduledPostStatus::Pending {
+                assert!(post.not_before.unwrap() > Utc::now());
+                deferred += 1;
+            }
+        }
+        assert_eq!(deferred, 1);
+
+        // The deferred post is not retried before the limiter allows it
+        let processed = publish_due(&queue, &mock_session(), &fast_limiter(10), &compose_driver(), &Arc::new(Mutex::new(())))
+            .await
+            .unwrap();
+        assert_eq!(processed, 0);
+    }
+
+    /// Publish the due tweets of `queue` through a compose page X replaced
+    async fn publish_blocked(queue: &PostQueue, driver: MockDriver) -> usize {
+        let driver = Arc::new(driver.with_missing("div[data-testid=\"tweetTextarea_0\"]"));
+        publish_due(queue, &mock_session(), &fast_limiter(10), &driver, &Arc::new(Mutex::new(())))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_defers_when_throttled() {
+        let queue = memory_queue();
+        let due = schedule_due(&queue, "Good morning!").await;
+        let driver = MockDriver::new()
+            .with_page("https://x.com/compose/post", &fixture("twitter/rate_limited.html"));
+
+        assert_eq!(publish_blocked(&queue, driver).await, 0);
+
+        let post = queue.get(&due.id).await.unwrap().unwrap();
+        assert_eq!(post.status, ScheduledPostStatus::Pending);
+        assert!(post.not_before.unwrap() > Utc::now() + Duration::minutes(10));
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_defers_behind_login_wall() {
+        let queue = memory_queue();
+        let due = schedule_due(&queue, "Good morning!").await;
+        let login = "https://x.com/i/flow/login";
+        let driver = MockDriver::new()
+            .with_redirect("https://x.com/compose/post", login)
+            .with_page(login, &fixture("twitter/login_wall.html"));
+
+        assert_eq!(publish_blocked(&queue, driver).await, 0);
+
+        let post = queue.get(&due.id).await.unwrap().unwrap();
+        assert_eq!(post.status, ScheduledPostStatus::Pending);
+        assert!(post.not_before.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_without_session_keeps_posts() {
+        let queue = memory_queue();
+        let due = schedule_due(&queue, "Good morning!").await;
+        let rate_limiter = fast_limiter(10);
+        let driver = compose_driver();
+
+        let result = publish_due(&queue, &invalid_session(), &rate_limiter, &driver, &Arc::new(Mutex::new(()))).await;
+        assert!(matches!(result, Err(TwitterError::NoSession)));
+        assert!(driver.actions().is_empty());
+        assert_eq!(rate_limiter.remaining_tokens(), 10);
+
+        let post = queue.get(&due.id).await.unwrap().unwrap();
+        assert_eq!(post.status, ScheduledPostStatus::Pending);
+        assert!(post.not_before.is_none());
+    }
+
+    /// How long reads of [`SlowStorage`] take
+    const READ_DELAY_MS: u64 = 50;
+
+    /// Storage returning what it read only after a while, so that a change
+    /// made in between goes unseen
+    struct SlowStorage(MemoryStorage);
+
+    #[async_trait::async_trait]
+    impl synmem_core::ports::outbound::StoragePort for SlowStorage {
+        type Error = crate::storage::StorageError;
+
+        async fn store(&self, key: &str, value: &str) -> Result<(), Self::Error> {
+            self.0.store(key, value).await
+        }
+
+        async fn retrieve(&self, key: &str) -> Result<Option<String>, Self::Error> {
+            let value = self.0.retrieve(key).await;
+            tokio::time::sleep(std::time::Duration::from_millis(READ_DELAY_MS)).await;
+            value
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), Self::Error> {
+            self.0.delete(key).await
+        }
+
+        async fn list_keys(&self, prefix: Option<&str>) -> Result<Vec<String>, Self::Error> {
+            self.0.list_keys(prefix).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_while_publishing() {
+        let storage: Arc<crate::storage::Storage> = Arc::new(SlowStorage(MemoryStorage::new()));
+        let queue = PostQueue::new(storage.clone(), "123456789");
+        let due = schedule_due(&queue, "Good morning!").await;
+        let driver = compose_driver();
+
+        // The worker and the tools use their own queues of the account
+        let publisher = PostQueue::new(storage, "123456789");
+        let publishing = tokio::spawn({
+            let driver = driver.clone();
+            async move {
+                let rate_limiter = fast_limiter(10);
+                publish_due(&publisher, &mock_session(), &rate_limiter, &driver, &Arc::new(Mutex::new(()))).await
+            }
+        });
+        // Cancel while the worker reads the entry it is about to claim: it
+        // lists the queue, then reads the entry again under the lock
+        tokio::time::sleep(std::time::Duration::from_millis(READ_DELAY_MS * 3 / 2)).await;
+        let input = TwitterCancelScheduledInput { id: due.id.clone() };
+        let cancelled = twitter_cancel_scheduled(input, &queue).await;
+        publishing.await.unwrap().unwrap();
+
+        // The worker claimed the tweet first, so it went out and stays
+        assert!(matches!(cancelled, Err(TwitterError::InvalidInput { .. })));
+        assert!(!driver.actions().is_empty());
+        let post = queue.get(&due.id).await.unwrap().unwrap();
+        assert_eq!(post.status, ScheduledPostStatus::Published);
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_waits_for_the_browser() {
+        let queue = memory_queue();
+        schedule_due(&queue, "Good morning!").await;
+        let driver = compose_driver();
+        let browser_lease = Arc::new(Mutex::new(()));
+
+        // A tool call is driving the browser
+        let lease = browser_lease.clone().lock_owned().await;
+        let publishing = tokio::spawn({
+            let driver = driver.clone();
+            let browser_lease = browser_lease.clone();
+            async move {
+                let rate_limiter = fast_limiter(10);
+                publish_due(&queue, &mock_session(), &rate_limiter, &driver, &browser_lease).await
+            }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(driver.actions().is_empty());
+
+        drop(lease);
+        assert_eq!(publishing.await.unwrap().unwrap(), 1);
+        assert!(!driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_publish_due_waits_for_other_processes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let queue = memory_queue().with_lock_dir(dir.path());
+        schedule_due(&queue, "Good morning!").await;
+        let driver = compose_driver();
+
+        // Another server sharing the lock directory holds the queue
+        let lock_path = dir.path().join("twitter/scheduled/123456789/queue.lock");
+        let lock = FileLock::acquire(&lock_path, std::time::Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        let publishing = tokio::spawn({
+            let driver = driver.clone();
+            async move {
+                let rate_limiter = fast_limiter(10);
+                publish_due(&queue, &mock_session(), &rate_limiter, &driver, &Arc::new(Mutex::new(()))).await
+            }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(driver.actions().is_empty());
+
+        drop(lock);
+        assert_eq!(publishing.await.unwrap().unwrap(), 1);
+        assert!(!driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_posts_claimed_elsewhere_are_left_alone() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let queue = memory_queue().with_lock_dir(dir.path());
+        let mut claimed = schedule_due(&queue, "Claimed").await;
+        let mut crashed = schedule_due(&queue, "Crashed").await;
+        for post in [&mut claimed, &mut crashed] {
+            post.status = ScheduledPostStatus::Publishing;
+            queue.save(post).await.unwrap();
+        }
+
+        // Another server is still publishing the first entry
+        let claim_path = dir
+            .path()
+            .join(format!("twitter/scheduled/123456789/{}.lock", claimed.id));
+        let _claim = FileLock::acquire(&claim_path, std::time::Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        let driver = compose_driver();
+        let scheduler = tokio::spawn(run_scheduler(
+            queue.clone(),
+            Arc::new(mock_session()),
+            Arc::new(fast_limiter(10)),
+            driver.clone(),
+            Arc::new(Mutex::new(())),
+        ));
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        scheduler.abort();
+
+        let status = |post: Option<ScheduledPost>| post.unwrap().status;
+        assert_eq!(
+            status(queue.get(&claimed.id).await.unwrap()),
+            ScheduledPostStatus::Publishing
+        );
+        assert_eq!(
+            status(queue.get(&crashed.id).await.unwrap()),
+            ScheduledPostStatus::Failed
+        );
+        assert!(driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queue_persists_across_restarts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let input = schedule_input("Persisted", Utc::now() + Duration::hours(1));
+        let queue = PostQueue::new(Arc::new(FileStorage::new(dir.path())), "123456789");
+        let id = twitter_schedule_post(input, &queue).await.unwrap().post.unwrap().id;
+
+        let queue = PostQueue::new(Arc::new(FileStorage::new(dir.path())), "123456789");
+        let posts = queue.list().await.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, id);
+        assert_eq!(posts[0].post.text, "Persisted");
+
+        // Queues are kept per account
+        let other = PostQueue::new(Arc::new(FileStorage::new(dir.path())), "987654321");
+        assert!(other.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_schedule_tools() {
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_tools(&mut registry, mock_session());
+        let context = test_context_with(MockDriver::new());
+        let request = crate::tools::RequestContext::detached();
+        let publish_at = (Utc::now() + Duration::hours(1)).to_rfc3339();
+
+        let result = registry
+            .call(
+                "twitter_schedule_post",
+                json!({"text": "Scheduled", "publish_at": publish_at}),
+                &context,
+                &request,
+            )
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+        let crate::protocol::Content::Text { text } = &result.content[0] else {
+            panic!("expected text content");
+        };
+        let result: TwitterScheduledPostResult = serde_json::from_str(text).unwrap();
+        let id = result.post.unwrap().id;
+
+        let result = registry
+            .call("twitter_list_scheduled", json!({"status": "pending"}), &context, &request)
+            .await
+            .unwrap();
+        let crate::protocol::Content::Text { text } = &result.content[0] else {
+            panic!("expected text content");
+        };
+        let result: TwitterListScheduledResult = serde_json::from_str(text).unwrap();
+        assert_eq!(result.posts.len(), 1);
+        assert_eq!(result.posts[0].id, id);
+
+        let result = registry
+            .call("twitter_cancel_scheduled", json!({"id": id}), &context, &request)
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+
+        let input = json!({"id": id, "publish_at": publish_at});
+        let result = registry
+            .call("twitter_reschedule_post", input, &context, &request)
+            .await
+            .unwrap();
+        assert!(result.is_error);
+    }
+}
+
+mod media_tests {
+    use super::super::media::{decode_data_url, download, download_at_most, is_public};
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    /// Serve `app` on a free port of `ip`
+    async fn serve(ip: &str, app: axum::Router) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind((ip, 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        addr
+    }
+
+    /// Serve `chunks` of `size` bytes at `/media` on a local port, without
+    /// a `Content-Length`
+    async fn serve_chunked(chunks: usize, size: usize) -> String {
+        let app = axum::Router::new().route(
+            "/media",
+            axum::routing::get(move || async move {
+                let chunks = (0..chunks).map(move |_| Ok::<_, std::io::Error>(vec![0u8; size]));
+                axum::body::Body::from_stream(futures::stream::iter(chunks))
+            }),
+        );
+        format!("http://{}/media", serve("127.0.0.1", app).await)
+    }
+
+    fn any_address(_: IpAddr) -> bool {
+        true
+    }
+
+    #[tokio::test]
+    async fn test_download_stops_past_limit_without_content_length() {
+        let url = serve_chunked(3, 10).await;
+
+        let (bytes, _) = download_at_most(&url, 30, any_address).await.unwrap();
+        assert_eq!(bytes.len(), 30);
+
+        let result = download_at_most(&url, 25, any_address).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_download_reports_error_status() {
+        let app = axum::Router::new().route(
+            "/media",
+            axum::routing::get(|| async { axum::http::StatusCode::NOT_FOUND }),
+        );
+        let url = format!("http://{}/media", serve("127.0.0.1", app).await);
+
+        let result = download_at_most(&url, 30, any_address).await;
+        assert!(matches!(result, Err(TwitterError::NetworkError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_download_refuses_local_addresses() {
+        let url = serve_chunked(1, 10).await;
+        let result = download(&url).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+
+        // Host names are checked once resolved
+        let url = url.replace("127.0.0.1", "localhost");
+        let Err(TwitterError::NetworkError { message }) = download(&url).await else {
+            panic!("expected a network error");
+        };
+        assert!(message.contains("no public address"));
+    }
+
+    #[tokio::test]
+    async fn test_download_refuses_redirect_to_local_address() {
+        let target = serve_chunked(1, 10).await;
+        let app = axum::Router::new().route(
+            "/media",
+            axum::routing::get(move || async move { axum::response::Redirect::temporary(&target) }),
+        );
+        let url = format!("http://{}/media", serve("127.0.0.2", app).await);
+        let (bytes, _) = download_at_most(&url, 30, any_address).await.unwrap();
+        assert_eq!(bytes.len(), 10);
+
+        // Only the redirecting server counts as public here
+        let result = download_at_most(&url, 30, |ip| ip != Ipv4Addr::LOCALHOST).await;
+        let Err(TwitterError::NetworkError { message }) = result else {
+            panic!("expected a network error");
+        };
+        assert!(message.contains("not a public address"));
+    }
+
+    #[test]
+    fn test_is_public() {
+        for ip in ["1.1.1.1", "8.8.8.8", "2606:4700:4700::1111"] {
+            assert!(is_public(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fc00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_decode_base64_data_url() {
+        let (bytes, mime) = decode_data_url("data:image/PNG;base64,iVBORw0K\nGgo=").unwrap();
+        assert_eq!(bytes, b"\x89PNG\r\n\x1a\n");
+        assert_eq!(mime, "image/png");
+    }
+
+    #[test]
+    fn test_decode_percent_encoded_data_url() {
+        let (bytes, mime) = decode_data_url("data:text/plain;charset=utf-8,a%20b%2Cc").unwrap();
+        assert_eq!(bytes, b"a b,c");
+        assert_eq!(mime, "text/plain");
+    }
+
+    #[test]
+    fn test_decode_invalid_data_url() {
+        for url in [
+            "data:image/png;base64",
+            "data:image/png;base64,***",
+            "data:,%zz",
+            "https://example.com/a.png",
+        ] {
+            let result = decode_data_url(url);
+            assert!(matches!(result, Err(TwitterError::InvalidInput { .. })), "{}", url);
+        }
+    }
+}
+
+mod read_thread_tests {
+    use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    const THREAD_ID: &str = "1790000000000000001";
+
+    fn thread_driver(id: &str) -> Arc<MockDriver> {
+        Arc::new(MockDriver::new().with_page(
+            &format!("https://x.com/i/status/{}", id),
+            &fixture("twitter/thread.html"),
+        ))
+    }
+
+    async fn read(
+        input: TwitterReadThreadInput,
+        driver: &Arc<MockDriver>,
+    ) -> Result<TwitterReadThreadResult, TwitterError> {
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
+        twitter_read_thread(input, &session, &rate_limiter, driver, &Progress::none()).await
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_by_url() {
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: format!("https://twitter.com/rustlang/status/{}", THREAD_ID),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &thread_driver(THREAD_ID)).await;
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_by_id() {
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: THREAD_ID.to_string(),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &thread_driver(THREAD_ID)).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_x_url() {
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: format!("https://x.com/rustlang/status/{}?s=20", THREAD_ID),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &thread_driver(THREAD_ID)).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_invalid_url() {
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: "https://example.com/not-twitter".to_string(),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &thread_driver(THREAD_ID)).await;
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            TwitterError::InvalidInput { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_no_session() {
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: THREAD_ID.to_string(),
+            max_tweets: 50,
+            download_media: false,
+        };
+        let session = invalid_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
+        let driver = thread_driver(THREAD_ID);
+
+        let result =
+            twitter_read_thread(input, &session, &rate_limiter, &driver, &Progress::none()).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), TwitterError::NoSession));
+        assert!(driver.actions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_follows_author_chain() {
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: THREAD_ID.to_string(),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &thread_driver(THREAD_ID)).await.unwrap();
+        let ids: Vec<&str> = result.tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "1790000000000000001",
+                "1790000000000000002",
+                "1790000000000000003"
+            ]
+        );
+        assert_eq!(result.total_count, 3);
+        assert!(result.tweets.iter().all(|t| t.author == "rustlang"));
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_parses_tweet_fields() {
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: THREAD_ID.to_string(),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &thread_driver(THREAD_ID)).await.unwrap();
+        let first = &result.tweets[0];
+        assert_eq!(first.author_display_name, "Rust Language");
+        assert_eq!(first.timestamp, "2024-05-02T15:00:00.000Z");
+        assert_eq!((first.replies, first.retweets, first.likes), (2, 340, 1512));
+        assert!(first.text.starts_with("Rust 1.78 is out!"));
+
+        let second = &result.tweets[1];
+        assert_eq!(second.media.len(), 1);
+        assert_eq!(second.media[0].media_type, "image");
+        assert_eq!(
+            second.media[0].alt_text.as_deref(),
+            Some("Example of a custom diagnostic message")
+        );
+        assert_eq!(result.tweets[2].replies, 0);
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_includes_ancestors_of_middle_tweet() {
+        let id = "1790000000000000002";
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: id.to_string(),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &thread_driver(id)).await.unwrap();
+        assert_eq!(result.tweets.len(), 3);
+        assert_eq!(result.tweets[0].id, THREAD_ID);
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_respects_max_tweets() {
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: THREAD_ID.to_string(),
+            max_tweets: 2,
+            download_media: false,
+        };
+        let driver = thread_driver(THREAD_ID);
+
+        let result = read(input, &driver).await.unwrap();
+        assert_eq!(result.tweets.len(), 2);
+        assert!(!driver.actions().iter().any(|a| a.contains("scrollBy")));
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_scrolls_to_load_replies() {
+        let driver = Arc::new(MockDriver::new().with_snapshots(
+            &format!("https://x.com/i/status/{}", THREAD_ID),
+            &[
+                &fixture("twitter/thread_top.html"),
+                &fixture("twitter/thread_bottom.html"),
+            ],
+        ));
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: THREAD_ID.to_string(),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &driver).await.unwrap();
+        assert_eq!(result.tweets.len(), 3);
+        assert_eq!(result.tweets[2].id, "1790000000000000003");
+        let scrolls = driver
+            .actions()
+            .iter()
+            .filter(|a| a.contains("scrollBy"))
+            .count();
+        assert_eq!(scrolls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_read_thread_missing_tweet() {
+        let driver = Arc::new(MockDriver::new().with_page(
+            "https://x.com/i/status/42",
+            "<html><body><span>Hmm...this page doesn't exist.</span></body></html>",
+        ));
+        let input = TwitterReadThreadInput {
+            tweet_url_or_id: "42".to_string(),
+            max_tweets: 50,
+            download_media: false,
+        };
+
+        let result = read(input, &driver).await;
+        assert!(matches!(
+            result,
+            Err(TwitterError::TweetNotFound { tweet_id }) if tweet_id == "42"
+        ));
+    }
+}
+
+mod parser_tests {
+    use super::*;
+    use crate::testing::fixture;
+
+    fn timeline() -> Vec<Tweet> {
+        parse_tweets(&fixture("twitter/timeline.html"))
+    }
+
+    #[test]
+    fn test_parse_count() {
+        assert_eq!(parse_count("0"), Some(0));
+        assert_eq!(parse_count("987"), Some(987));
+        assert_eq!(parse_count("12,345"), Some(12_345));
+        assert_eq!(parse_count("1.2K"), Some(1_200));
+        assert_eq!(parse_count("12.4K"), Some(12_400));
+        assert_eq!(parse_count("3.4M"), Some(3_400_000));
+        assert_eq!(parse_count("1B"), Some(1_000_000_000));
+        assert_eq!(parse_count(""), None);
+        assert_eq!(parse_count("K"), None);
+        assert_eq!(parse_count("Reply"), None);
+    }
+
+    #[test]
+    fn test_parse_tweets_empty_page() {
+        assert!(parse_tweets("<html><body></body></html>").is_empty());
+    }
+
+    #[test]
+    fn test_parse_tweets_prefers_exact_counts() {
+        let tweets = parse_tweets(&fixture("twitter/thread.html"));
+
+        assert_eq!(tweets.len(), 4);
+        assert_eq!(tweets[0].id, "1790000000000000001");
+        assert_eq!(tweets[0].author, "rustlang");
+        assert_eq!(tweets[0].author_display_name, "Rust Language");
+        assert_eq!(tweets[0].timestamp, "2024-05-02T15:00:00.000Z");
+        // The button shows "1.5K" but its label has the exact count
+        assert_eq!(tweets[0].likes, 1512);
+        assert_eq!(tweets[0].retweets, 340);
+        assert_eq!(tweets[0].replies, 2);
+        assert_eq!(tweets[2].replies, 0);
+    }
+
+    #[test]
+    fn test_parse_tweets_abbreviated_counts() {
+        let tweets = timeline();
+
+        assert_eq!(tweets.len(), 3);
+        assert_eq!(tweets[0].likes, 12_400);
+        assert_eq!(tweets[0].retweets, 1_200);
+        assert_eq!(tweets[0].replies, 87);
+        assert_eq!(tweets[2].likes, 1_500_000);
+    }
+
+    #[test]
+    fn test_parse_tweets_toggled_actions() {
+        let tweets = timeline();
+
+        // Liked and reposted tweets render "unlike" / "unretweet" buttons
+        assert_eq!(tweets[1].likes, 2_048);
+        assert_eq!(tweets[2].retweets, 64);
+    }
+
+    #[test]
+    fn test_parse_tweets_repost_uses_original_author() {
+        let tweet = &timeline()[0];
+
+        assert_eq!(tweet.id, "1791000000000000010");
+        assert_eq!(tweet.author, "tokio_rs");
+        assert_eq!(tweet.author_display_name, "Tokio");
+        assert_eq!(tweet.timestamp, "2024-05-05T09:30:00.000Z");
+    }
+
+    #[test]
+    fn test_parse_tweets_keeps_emoji() {
+        let tweet = &timeline()[0];
+
+        assert_eq!(tweet.text, "Tokio 1.38 is out 🎉 with a new runtime metrics API.");
+    }
+
+    #[test]
+    fn test_parse_tweets_video() {
+        let tweet = &timeline()[0];
+
+        assert_eq!(tweet.media.len(), 1);
+        assert_eq!(tweet.media[0].media_type, "video");
+        assert_eq!(
+            tweet.media[0].url,
+            "https://pbs.twimg.com/ext_tw_video_thumb/1791000000000000011/pu/img/abc.jpg"
+        );
+        assert!(tweet.media[0].alt_text.is_none());
+    }
+
+    #[test]
+    fn test_parse_tweets_gif() {
+        let tweet = &timeline()[1];
+
+        assert_eq!(tweet.media.len(), 1);
+        assert_eq!(tweet.media[0].media_type, "gif");
+        assert_eq!(tweet.media[0].url, "https://video.twimg.com/tweet_video/GNxyz.mp4");
+        assert_eq!(tweet.media[0].alt_text.as_deref(), Some("Crab dancing"));
+    }
+
+    #[test]
+    fn test_parse_tweets_image_alt_text() {
+        let tweets = parse_tweets(&fixture("twitter/thread.html"));
+
+        assert_eq!(tweets[1].media.len(), 1);
+        assert_eq!(tweets[1].media[0].media_type, "image");
+        assert_eq!(
+            tweets[1].media[0].alt_text.as_deref(),
+            Some("Example of a custom diagnostic message")
+        );
+    }
+
+    #[test]
+    fn test_parse_tweets_quoted_tweet() {
+        let tweet = &timeline()[2];
+
+        assert_eq!(tweet.id, "1791000000000000030");
+        assert_eq!(tweet.author_display_name, "Rust Language");
+        assert_eq!(tweet.text, "Worth a read before the next edition:");
+        assert!(tweet.media.is_empty());
+
+        let quoted = tweet.quoted_tweet.as_ref().unwrap();
+        assert_eq!(quoted.id, "");
+        assert_eq!(quoted.author, "insiderust");
+        assert_eq!(quoted.author_display_name, "Inside Rust");
+        assert_eq!(quoted.timestamp, "2024-05-04T18:00:00.000Z");
+        assert_eq!(quoted.text, "Planning for the 2024 edition is underway.");
+        assert_eq!(quoted.media.len(), 1);
+        assert_eq!(quoted.media[0].alt_text.as_deref(), Some("Edition roadmap"));
+        assert!(quoted.quoted_tweet.is_none());
+    }
+
+    #[test]
+    fn test_quoted_tweet_serialization() {
+        let tweets = timeline();
+
+        let plain = serde_json::to_value(&tweets[0]).unwrap();
+        assert!(plain.get("quoted_tweet").is_none());
+
+        let quoting = serde_json::to_value(&tweets[2]).unwrap();
+        assert_eq!(quoting["quoted_tweet"]["author"], "insiderust");
+    }
+}
+
+mod feed_tests {
+    use super::*;
+
+    const QUERY: &str = "https://x.com/home";
+
+    fn cursor() -> FeedCursor {
+        FeedCursor {
+            query: QUERY.to_string(),
+            last_id: "1791000000000000020".to_string(),
+            scroll_y: 2400,
+            seen: vec![
+                "1791000000000000010".to_string(),
+                "1791000000000000020".to_string(),
+            ],
+            pending: vec![],
+        }
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let encoded = cursor().encode();
+
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(FeedCursor::decode(&encoded, QUERY).unwrap(), cursor());
+    }
+
+    #[test]
+    fn test_cursor_for_other_query() {
+        let result = FeedCursor::decode(&cursor().encode(), "https://x.com/rustlang");
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_cursor_malformed() {
+        for bad in ["", "%%%", "bm90IGpzb24"] {
+            let result = FeedCursor::decode(bad, QUERY);
+            assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+        }
+    }
+}
+
+mod search_tests {
+    use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    async fn search(
+        input: TwitterSearchInput,
+        session: &TwitterSession,
+        rate_limiter: &RateLimiter,
+    ) -> Result<TwitterSearchResult, TwitterError> {
+        let driver = Arc::new(MockDriver::new());
+        twitter_search(input, session, rate_limiter, &driver, &Progress::none()).await
+    }
+
+    #[tokio::test]
+    async fn test_search_valid_query() {
+        let input = TwitterSearchInput {
+            query: "rust programming".to_string(),
+            operators: SearchOperators::default(),
+            count: 20,
+            filter: SearchFilter::Top,
+            cursor: None,
+            download_media: false,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
+
+        let result = search(input, &session, &rate_limiter).await;
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn test_search_empty_query() {
+        let input = TwitterSearchInput {
+            query: "".to_string(),
+            operators: SearchOperators::default(),
+            count: 20,
+            filter: SearchFilter::Top,
+            cursor: None,
+            download_media: false,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
+
+        let result = search(input, &session, &rate_limiter).await;
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            TwitterError::InvalidInput { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_zero_count() {
+        let input = TwitterSearchInput {
+            query: "test".to_string(),
+            operators: SearchOperators::default(),
+            count: 0,
+            filter: SearchFilter::Top,
+            cursor: None,
+            download_media: false,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
+
+        let result = search(input, &session, &rate_limiter).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_count_too_high() {
+        let input = TwitterSearchInput {
+            query: "test".to_string(),
+            operators: SearchOperators::default(),
+            count: 101,
+            filter: SearchFilter::Top,
+            cursor: None,
+            download_media: false,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
+
+        let result = search(input, &session, &rate_limiter).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_different_filters() {
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
+
+        for filter in [
+            SearchFilter::Top,
+            SearchFilter::Latest,
+            SearchFilter::People,
+            SearchFilter::Photos,
+            SearchFilter::Videos,
+        ] {
+            let input = TwitterSearchInput {
+                query: "test".to_string(),
+                operators: SearchOperators::default(),
+                count: 10,
+                filter,
+                cursor: None,
+                download_media: false,
+            };
+            let result = search(input, &session, &rate_limiter).await;
+            assert!(result.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_no_session() {
+        let input = TwitterSearchInput {
+            query: "test".to_string(),
+            operators: SearchOperators::default(),
+            count: 20,
+            filter: SearchFilter::Top,
+            cursor: None,
+            download_media: false,
+        };
+        let session = invalid_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
+
+        let result = search(input, &session, &rate_limiter).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), TwitterError::NoSession));
+    }
+
+    #[tokio::test]
+    async fn test_search_deduplicates_across_scrolls() {
+        let driver = Arc::new(MockDriver::new().with_snapshots(
+            "https://x.com/search?q=rust%20release&f=live&src=typed_query",
+            &[
+                &fixture("twitter/thread_top.html"),
+                &fixture("twitter/thread_bottom.html"),
+            ],
+        ));
+        let input = TwitterSearchInput {
+            query: "rust release".to_string(),
+            operators: SearchOperators::default(),
+            count: 20,
+            filter: SearchFilter::Latest,
+            cursor: None,
+            download_media: false,
+        };
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
+
+        let result = twitter_search(input, &session, &rate_limiter, &driver, &Progress::none())
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = result.tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "1790000000000000001",
+                "1790000000000000002",
+                "1790000000000000003",
+                "1790000000000000050",
+            ]
+        );
+        assert!(result.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_build_search_query() {
+        let operators = SearchOperators {
+            from: Some("@rustlang".to_string()),
+            to: Some("ferris".to_string()),
+            mentions: vec!["rust_foundation".to_string()],
+            hashtags: vec!["#rust".to_string(), "release_notes".to_string()],
+            since: Some("2024-01-01".to_string()),
+            until: Some("2024-02-01".to_string()),
+            min_likes: Some(100),
+            min_retweets: Some(10),
+            lang: Some("EN".to_string()),
+            has_media: true,
+            exclude_replies: true,
+            exclude_retweets: true,
+        };
+        assert_eq!(
+            build_search_query(" async ", &operators).unwrap(),
+            "async from:rustlang to:ferris @rust_foundation #rust #release_notes \
+             since:2024-01-01 until:2024-02-01 min_faves:100 min_retweets:10 lang:en \
+             filter:media -filter:replies -filter:retweets"
+        );
+
+        // Operators alone make a query
+        let operators = SearchOperators {
+            from: Some("rustlang".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(build_search_query("", &operators).unwrap(), "from:rustlang");
+    }
+
+    #[test]
+    fn test_build_search_query_rejects_invalid_operators() {
+        let cases = [
+            SearchOperators::default(),
+            SearchOperators {
+                from: Some("not a user".to_string()),
+                ..Default::default()
+            },
+            SearchOperators {
+                mentions: vec!["".to_string()],
+                ..Default::default()
+            },
+            SearchOperators {
+                hashtags: vec!["2024".to_string()],
+                ..Default::default()
+            },
+            SearchOperators {
+                hashtags: vec!["rust-lang".to_string()],
+                ..Default::default()
+            },
+            SearchOperators {
+                since: Some("2024-13-01".to_string()),
+                ..Default::default()
+            },
+            SearchOperators {
+                since: Some("2024-02-01".to_string()),
+                until: Some("2024-02-01".to_string()),
+                ..Default::default()
+            },
+            SearchOperators {
+                lang: Some("eng".to_string()),
+                ..Default::default()
+            },
+        ];
+        for operators in cases {
+            let result = build_search_query("", &operators);
+            assert!(
+                matches!(result, Err(TwitterError::InvalidInput { .. })),
+                "{:?}",
+                operators
+            );
         }
     }
 
     #[tokio::test]
-    async fn test_search_no_session() {
+    async fn test_search_with_operators_only() {
+        let driver = Arc::new(MockDriver::new());
+        let input: TwitterSearchInput = serde_json::from_value(serde_json::json!({
+            "from": "rustlang",
+            "min_likes": 50,
+            "filter": "latest"
+        }))
+        .unwrap();
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
+
+        let result =
+            twitter_search(input, &session, &rate_limiter, &driver, &Progress::none()).await;
+        assert!(result.is_ok());
+        assert_eq!(
+            driver.actions()[0],
+            "goto https://x.com/search?q=from%3Arustlang%20min_faves%3A50&f=live&src=typed_query"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_rejects_malformed_cursor() {
         let input = TwitterSearchInput {
             query: "test".to_string(),
+            operators: SearchOperators::default(),
             count: 20,
             filter: SearchFilter::Top,
+            cursor: Some("not a cursor".to_string()),
+            download_media: false,
         };
-        let session = invalid_session();
+        let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_search());
 
-        let result = twitter_search(input, &session, &rate_limiter).await;
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), TwitterError::NoSession));
+        let result = search(input, &session, &rate_limiter).await;
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
     }
 }
 
 mod timeline_tests {
     use super::*;
+    use crate::testing::{fixture, MockDriver};
+    use std::sync::Arc;
+
+    const HOME_URL: &str = "https://x.com/home";
+
+    async fn timeline(
+        input: TwitterGetTimelineInput,
+        session: &TwitterSession,
+        rate_limiter: &RateLimiter,
+    ) -> Result<TwitterGetTimelineResult, TwitterError> {
+        let driver = Arc::new(MockDriver::new());
+        twitter_get_timeline(input, session, rate_limiter, &driver, &Progress::none()).await
+    }
 
     #[tokio::test]
     async fn test_get_home_timeline() {
@@ -332,11 +3559,12 @@ mod timeline_tests {
             username: None,
             count: 20,
             cursor: None,
+            download_media: false,
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
 
-        let result = twitter_get_timeline(input, &session, &rate_limiter).await;
+        let result = timeline(input, &session, &rate_limiter).await;
         assert!(result.is_ok());
         let result = result.unwrap();
         assert!(result.success);
@@ -349,11 +3577,12 @@ mod timeline_tests {
             username: None,
             count: 20,
             cursor: None,
+            download_media: false,
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
 
-        let result = twitter_get_timeline(input, &session, &rate_limiter).await;
+        let result = timeline(input, &session, &rate_limiter).await;
         assert!(result.is_ok());
     }
 
@@ -364,11 +3593,12 @@ mod timeline_tests {
             username: Some("testuser".to_string()),
             count: 20,
             cursor: None,
+            download_media: false,
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
 
-        let result = twitter_get_timeline(input, &session, &rate_limiter).await;
+        let result = timeline(input, &session, &rate_limiter).await;
         assert!(result.is_ok());
     }
 
@@ -379,11 +3609,12 @@ mod timeline_tests {
             username: None,
             count: 20,
             cursor: None,
+            download_media: false,
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
 
-        let result = twitter_get_timeline(input, &session, &rate_limiter).await;
+        let result = timeline(input, &session, &rate_limiter).await;
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -398,11 +3629,12 @@ mod timeline_tests {
             username: Some("invalid username with spaces".to_string()),
             count: 20,
             cursor: None,
+            download_media: false,
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
 
-        let result = twitter_get_timeline(input, &session, &rate_limiter).await;
+        let result = timeline(input, &session, &rate_limiter).await;
         assert!(result.is_err());
     }
 
@@ -413,11 +3645,12 @@ mod timeline_tests {
             username: None,
             count: 0,
             cursor: None,
+            download_media: false,
         };
         let session = mock_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
 
-        let result = twitter_get_timeline(input, &session, &rate_limiter).await;
+        let result = timeline(input, &session, &rate_limiter).await;
         assert!(result.is_err());
     }
 
@@ -428,18 +3661,238 @@ mod timeline_tests {
             username: None,
             count: 20,
             cursor: None,
+            download_media: false,
         };
         let session = invalid_session();
         let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
 
-        let result = twitter_get_timeline(input, &session, &rate_limiter).await;
+        let result = timeline(input, &session, &rate_limiter).await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), TwitterError::NoSession));
     }
+
+    fn home_input(count: usize, cursor: Option<String>) -> TwitterGetTimelineInput {
+        TwitterGetTimelineInput {
+            timeline_type: TimelineType::Home,
+            username: None,
+            count,
+            cursor,
+            download_media: false,
+        }
+    }
+
+    async fn home(
+        input: TwitterGetTimelineInput,
+        driver: &Arc<MockDriver>,
+    ) -> Result<TwitterGetTimelineResult, TwitterError> {
+        let session = mock_session();
+        let rate_limiter = RateLimiter::new(RateLimitConfig::for_read());
+        twitter_get_timeline(input, &session, &rate_limiter, driver, &Progress::none()).await
+    }
+
+    #[tokio::test]
+    async fn test_get_timeline_collects_tweets() {
+        let driver =
+            Arc::new(MockDriver::new().with_page(HOME_URL, &fixture("twitter/timeline.html")));
+
+        let result = home(home_input(20, None), &driver).await.unwrap();
+
+        let ids: Vec<_> = result.tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            ["1791000000000000010", "1791000000000000020", "1791000000000000030"]
+        );
+        assert!(result.next_cursor.is_none());
+        assert!(driver.actions().contains(&format!("goto {}", HOME_URL)));
+    }
+
+    #[tokio::test]
+    async fn test_get_timeline_returns_cursor_when_more_remain() {
+        let driver = Arc::new(
+            MockDriver::new()
+                .with_page(HOME_URL, &fixture("twitter/timeline.html"))
+                .with_js_result("1800"),
+        );
+
+        let result = home(home_input(2, None), &driver).await.unwrap();
+
+        assert_eq!(result.tweets.len(), 2);
+        let cursor = FeedCursor::decode(&result.next_cursor.unwrap(), HOME_URL).unwrap();
+        assert_eq!(cursor.last_id, "1791000000000000020");
+        assert_eq!(cursor.scroll_y, 1800);
+        assert_eq!(cursor.seen, ["1791000000000000010", "1791000000000000020"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_timeline_resumes_from_cursor() {
+        let driver = Arc::new(
+            MockDriver::new()
+                .with_page(HOME_URL, &fixture("twitter/timeline.html"))
+                .with_js_result("1800"),
+        );
+        let first = home(home_input(2, None), &driver).await.unwrap();
+
+        let second = home(home_input(2, first.next_cursor), &driver).await.unwrap();
+
+        assert_eq!(second.tweets.len(), 1);
+        assert_eq!(second.tweets[0].id, "1791000000000000030");
+        assert!(second.next_cursor.is_none());
+        assert!(driver
+            .actions()
+            .contains(&"js window.scrollTo(0, 1800)".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_timeline_skips_tweets_seen_in_earlier_pages() {
+        let cursor = FeedCursor {
+            query: HOME_URL.to_string(),
+            last_id: "1791000000000000099".to_string(),
+            scroll_y: 0,
+            seen: vec!["1791000000000000020".to_string()],
+            pending: vec![],
+        };
+        let driver =
+            Arc::new(MockDriver::new().with_page(HOME_URL, &fixture("twitter/timeline.html")));
+
+        let result = home(home_input(20, Some(cursor.encode())), &driver).await.unwrap();
+
+        let ids: Vec<_> = result.tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["1791000000000000010", "1791000000000000030"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_timeline_rejects_cursor_for_other_timeline() {
+        let cursor = FeedCursor {
+            query: "https://x.com/home?f=following".to_string(),
+            last_id: "1".to_string(),
+            scroll_y: 0,
+            seen: vec![],
+            pending: vec![],
+        };
+        let driver = Arc::new(MockDriver::new());
+
+        let result = home(home_input(20, Some(cursor.encode())), &driver).await;
+
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+        assert!(driver.actions().is_empty());
+    }
+
+    fn input_for(timeline_type: TimelineType) -> TwitterGetTimelineInput {
+        TwitterGetTimelineInput {
+            timeline_type,
+            username: None,
+            count: 20,
+            cursor: None,
+            download_media: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeline_urls() {
+        let username = || "rustlang".to_string();
+        let cases = [
+            (
+                TimelineType::List {
+                    id: "1234".to_string(),
+                },
+                "https://x.com/i/lists/1234",
+            ),
+            (TimelineType::Bookmarks, "https://x.com/i/bookmarks"),
+            (
+                TimelineType::Likes {
+                    username: username(),
+                },
+                "https://x.com/rustlang/likes",
+            ),
+            (
+                TimelineType::Replies {
+                    username: username(),
+                },
+                "https://x.com/rustlang/with_replies",
+            ),
+            (
+                TimelineType::Media {
+                    username: username(),
+                },
+                "https://x.com/rustlang/media",
+            ),
+        ];
+
+        for (timeline_type, url) in cases {
+            let driver = Arc::new(MockDriver::new());
+            home(input_for(timeline_type), &driver).await.unwrap();
+            assert!(driver.actions().contains(&format!("goto {}", url)), "{}", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_list_timeline_collects_tweets() {
+        let url = "https://x.com/i/lists/1234";
+        let driver = Arc::new(MockDriver::new().with_page(url, &fixture("twitter/timeline.html")));
+        let input = input_for(TimelineType::List {
+            id: "1234".to_string(),
+        });
+
+        let result = home(input, &driver).await.unwrap();
+
+        assert_eq!(result.tweets.len(), 3);
+        assert_eq!(result.tweets[0].author, "tokio_rs");
+    }
+
+    #[tokio::test]
+    async fn test_get_list_timeline_rejects_invalid_id() {
+        for id in ["", "rust-lists", "../home"] {
+            let driver = Arc::new(MockDriver::new());
+            let input = input_for(TimelineType::List { id: id.to_string() });
+
+            let result = home(input, &driver).await;
+
+            assert!(matches!(result, Err(TwitterError::InvalidInput { .. })), "{}", id);
+            assert!(driver.actions().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_likes_timeline_invalid_username() {
+        let driver = Arc::new(MockDriver::new());
+        let input = input_for(TimelineType::Likes {
+            username: "not a user".to_string(),
+        });
+
+        let result = home(input, &driver).await;
+
+        assert!(matches!(result, Err(TwitterError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_media_timeline_parses_grid() {
+        let url = "https://x.com/rustlang/media";
+        let driver =
+            Arc::new(MockDriver::new().with_page(url, &fixture("twitter/media_grid.html")));
+        let input = input_for(TimelineType::Media {
+            username: "rustlang".to_string(),
+        });
+
+        let result = home(input, &driver).await.unwrap();
+
+        let ids: Vec<_> = result.tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["1792000000000000010", "1792000000000000020"]);
+        let photos = &result.tweets[0];
+        assert_eq!(photos.author, "rustlang");
+        assert_eq!(photos.media.len(), 2);
+        assert_eq!(photos.media[0].media_type, "image");
+        assert_eq!(photos.media[0].alt_text.as_deref(), Some("Rust 1.79 release notes"));
+        assert_eq!(photos.media[1].alt_text, None);
+        let video = &result.tweets[1].media[0];
+        assert_eq!(video.media_type, "video");
+        assert!(video.url.contains("amplify_video_thumb"));
+    }
 }
 
 mod rate_limiter_tests {
     use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
 
     #[tokio::test]
     async fn test_rate_limiter_acquire() {
@@ -447,6 +3900,7 @@ mod rate_limiter_tests {
             max_requests: 5,
             window_seconds: 60,
             min_delay_ms: 0,
+            ..Default::default()
         });
 
         for _ in 0..5 {
@@ -461,6 +3915,7 @@ mod rate_limiter_tests {
             max_requests: 2,
             window_seconds: 60,
             min_delay_ms: 0,
+            ..Default::default()
         });
 
         // Exhaust the rate limit
@@ -476,12 +3931,31 @@ mod rate_limiter_tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_rate_limiter_check_capacity() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            max_requests: 3,
+            window_seconds: 60,
+            min_delay_ms: 0,
+            ..Default::default()
+        });
+
+        assert!(limiter.check_capacity(3).await.is_ok());
+        assert!(matches!(
+            limiter.check_capacity(4).await,
+            Err(TwitterError::RateLimited { .. })
+        ));
+        // Checking consumes nothing
+        assert_eq!(limiter.remaining_tokens(), 3);
+    }
+
     #[test]
     fn test_rate_limiter_remaining_tokens() {
         let limiter = RateLimiter::new(RateLimitConfig {
             max_requests: 10,
             window_seconds: 60,
             min_delay_ms: 0,
+            ..Default::default()
         });
 
         assert_eq!(limiter.remaining_tokens(), 10);
@@ -493,11 +3967,269 @@ mod rate_limiter_tests {
             max_requests: 10,
             window_seconds: 60,
             min_delay_ms: 0,
+            ..Default::default()
         });
 
         assert!(limiter.can_proceed());
     }
 
+    fn persistent_limiter(dir: &tempfile::TempDir, max_requests: u32) -> RateLimiter {
+        RateLimiter::persistent(
+            RateLimitConfig {
+                max_requests,
+                window_seconds: 60,
+                min_delay_ms: 0,
+                ..Default::default()
+            },
+            RateLimitStore::new(dir.path()),
+            "123456789/twitter.post",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_persistent_rate_limiter_survives_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let limiter = persistent_limiter(&dir, 2);
+        assert!(limiter.acquire().await.is_ok());
+        drop(limiter);
+
+        // A new limiter on the same store picks up the spent budget
+        let limiter = persistent_limiter(&dir, 2);
+        assert!(limiter.acquire().await.is_ok());
+        assert!(matches!(
+            limiter.acquire().await,
+            Err(TwitterError::RateLimited { .. })
+        ));
+        assert!(dir.path().join("123456789/twitter.post.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_persistent_rate_limiters_share_budget() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let first = persistent_limiter(&dir, 3);
+        let second = persistent_limiter(&dir, 3);
+
+        assert!(first.acquire().await.is_ok());
+        assert!(second.acquire().await.is_ok());
+        assert!(first.acquire().await.is_ok());
+        assert!(second.acquire().await.is_err());
+        assert!(second.check_capacity(1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_persistent_rate_limiter_ignores_corrupt_state() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("123456789")).unwrap();
+        std::fs::write(dir.path().join("123456789/twitter.post.json"), "garbage").unwrap();
+
+        let limiter = persistent_limiter(&dir, 2);
+        assert!(limiter.acquire().await.is_ok());
+        assert_eq!(limiter.remaining_tokens(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_lock_is_exclusive() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = RateLimitStore::new(dir.path());
+        let key = "123456789/twitter.post";
+
+        let held = store.lock(key).await.unwrap();
+        let waiting = tokio::time::timeout(Duration::from_millis(100), store.lock(key)).await;
+        assert!(waiting.is_err());
+
+        drop(held);
+        assert!(store.lock(key).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_lock_left_by_crash_is_free() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("123456789")).unwrap();
+        std::fs::write(dir.path().join("123456789/twitter.post.json.lock"), "").unwrap();
+
+        let limiter = persistent_limiter(&dir, 2);
+        assert!(limiter.acquire().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_shared_budget_caps_all_limiters() {
+        let budget = Arc::new(RateLimiter::new(RateLimitConfig {
+            max_requests: 2,
+            window_seconds: 60,
+            min_delay_ms: 0,
+            ..Default::default()
+        }));
+        let config = RateLimitConfig {
+            max_requests: 10,
+            window_seconds: 60,
+            min_delay_ms: 0,
+            ..Default::default()
+        };
+        let post = RateLimiter::new(config).with_shared_budget(budget.clone());
+        let like = RateLimiter::new(config).with_shared_budget(budget.clone());
+
+        assert!(post.acquire().await.is_ok());
+        assert!(like.acquire().await.is_ok());
+        assert!(matches!(
+            like.acquire().await,
+            Err(TwitterError::RateLimited { .. })
+        ));
+        // The refused request spent nothing of the operation's own budget
+        assert_eq!(like.remaining_tokens(), 9);
+        assert_eq!(budget.remaining_tokens(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_race_for_last_shared_token_spends_nothing_on_loss() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = RateLimitStore::new(dir.path());
+        let budget = Arc::new(limiter(1, RateLimitMode::SlidingWindow));
+        let post = RateLimiter::persistent(
+            RateLimitConfig {
+                max_requests: 10,
+                window_seconds: 60,
+                min_delay_ms: 0,
+                mode: RateLimitMode::SlidingWindow,
+                jitter_ms: 0,
+            },
+            store.clone(),
+            "123456789/twitter.post",
+        )
+        .with_shared_budget(budget.clone());
+        let like = limiter(10, RateLimitMode::SlidingWindow).with_shared_budget(budget.clone());
+
+        // The post passes the budget check, then waits for its own state
+        let held = store.lock("123456789/twitter.post").await.unwrap();
+        let posting = post.acquire();
+        tokio::pin!(posting);
+        tokio::select! {
+            _ = &mut posting => panic!("acquired while the state was locked"),
+            _ = tokio::time::sleep(Duration::from_millis(50)) => {}
+        }
+
+        // Meanwhile the like takes the last shared token
+        assert!(like.acquire().await.is_ok());
+        drop(held);
+        assert!(matches!(
+            posting.await,
+            Err(TwitterError::RateLimited { .. })
+        ));
+
+        // The post got its own token back
+        assert_eq!(post.remaining_tokens(), 10);
+    }
+
+    fn limiter(max_requests: u32, mode: RateLimitMode) -> RateLimiter {
+        RateLimiter::new(RateLimitConfig {
+            max_requests,
+            window_seconds: 1,
+            min_delay_ms: 0,
+            mode,
+            jitter_ms: 0,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_sliding_window_frees_each_request_a_window_later() {
+        let limiter = limiter(2, RateLimitMode::SlidingWindow);
+        assert!(limiter.acquire().await.is_ok());
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert!(limiter.acquire().await.is_ok());
+        assert!(matches!(
+            limiter.acquire().await,
+            Err(TwitterError::RateLimited { wait_seconds: 1 })
+        ));
+
+        // The first request has left the window, the second has not
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(limiter.acquire().await.is_ok());
+        assert!(limiter.acquire().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sliding_window_has_no_boundary_burst() {
+        let limiter = limiter(2, RateLimitMode::SlidingWindow);
+        assert!(limiter.acquire().await.is_ok());
+        assert!(limiter.acquire().await.is_ok());
+
+        // Late in the window, nothing has freed up yet
+        tokio::time::sleep(Duration::from_millis(900)).await;
+        assert!(limiter.acquire().await.is_err());
+
+        // Once the window has passed, both requests are free again
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(limiter.acquire().await.is_ok());
+        assert!(limiter.acquire().await.is_ok());
+        assert!(limiter.acquire().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_back_off_halves_budget_and_recovers() {
+        let limiter = limiter(4, RateLimitMode::FixedWindow);
+        assert!(limiter.acquire().await.is_ok());
+        limiter.back_off().await.unwrap();
+        assert_eq!(limiter.remaining_tokens(), 0);
+        assert!(limiter.acquire().await.is_err());
+
+        // The next window only has half the budget
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert!(limiter.acquire().await.is_ok());
+        assert_eq!(limiter.remaining_tokens(), 1);
+
+        // Two quiet windows later the full budget is back
+        tokio::time::sleep(Duration::from_millis(2000)).await;
+        assert!(limiter.acquire().await.is_ok());
+        assert_eq!(limiter.remaining_tokens(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_back_off_reaches_shared_budget() {
+        let budget = Arc::new(limiter(10, RateLimitMode::SlidingWindow));
+        let read = limiter(10, RateLimitMode::SlidingWindow).with_shared_budget(budget.clone());
+
+        read.back_off().await.unwrap();
+        assert_eq!(budget.remaining_tokens(), 0);
+        assert!(matches!(
+            budget.acquire().await,
+            Err(TwitterError::RateLimited { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_jitter_is_bounded() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            max_requests: 10,
+            window_seconds: 60,
+            min_delay_ms: 0,
+            mode: RateLimitMode::SlidingWindow,
+            jitter_ms: 50,
+        });
+        let started = std::time::Instant::now();
+        for _ in 0..5 {
+            assert!(limiter.acquire().await.is_ok());
+        }
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_persistent_state_without_backoff_fields() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        std::fs::create_dir_all(dir.path().join("123456789")).unwrap();
+        std::fs::write(
+            dir.path().join("123456789/twitter.post.json"),
+            format!(r#"{{"tokens":1,"window_start_ms":{},"last_request_ms":0}}"#, now),
+        )
+        .unwrap();
+
+        let limiter = persistent_limiter(&dir, 2);
+        assert!(limiter.acquire().await.is_ok());
+        assert!(limiter.acquire().await.is_err());
+    }
+
     #[test]
     fn test_rate_limit_configs() {
         let post_config = RateLimitConfig::for_post();
@@ -547,6 +4279,16 @@ mod error_tests {
         assert_eq!(TwitterError::NoSession.retry_after(), None);
     }
 
+    #[test]
+    fn test_error_needs_human() {
+        assert!(TwitterError::SessionExpired.needs_human());
+        assert!(TwitterError::ChallengeRequired.needs_human());
+        assert!(TwitterError::AgeRestricted.needs_human());
+        assert!(!TwitterError::ChallengeRequired.is_recoverable());
+        assert!(!TwitterError::RateLimited { wait_seconds: 60 }.needs_human());
+        assert!(!TwitterError::NoSession.needs_human());
+    }
+
     #[test]
     fn test_error_display() {
         let error = TwitterError::TweetTooLong {
@@ -558,11 +4300,18 @@ mod error_tests {
 
         let error = TwitterError::NoSession;
         assert!(error.to_string().contains("session"));
+
+        let error = TwitterError::UserSuspended {
+            username: "spam_account_1".to_string(),
+        };
+        assert!(error.to_string().contains("suspended"));
+        assert!(!error.is_recoverable());
     }
 }
 
 mod types_tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_tweet_max_length() {
@@ -581,6 +4330,26 @@ mod types_tests {
         assert!(matches!(timeline_type, TimelineType::Home));
     }
 
+    #[test]
+    fn test_timeline_type_serialization() {
+        let list = TimelineType::List {
+            id: "1234".to_string(),
+        };
+        assert_eq!(serde_json::to_value(&list).unwrap(), json!({"list": {"id": "1234"}}));
+        assert_eq!(serde_json::to_value(TimelineType::Bookmarks).unwrap(), json!("bookmarks"));
+
+        let media: TimelineType =
+            serde_json::from_value(json!({"media": {"username": "rustlang"}})).unwrap();
+        assert_eq!(
+            media,
+            TimelineType::Media {
+                username: "rustlang".to_string()
+            }
+        );
+        let home: TimelineType = serde_json::from_value(json!("home")).unwrap();
+        assert_eq!(home, TimelineType::Home);
+    }
+
     #[test]
     fn test_twitter_post_input_serialization() {
         let input = TwitterPostInput {
@@ -609,6 +4378,7 @@ mod types_tests {
             retweets: 50,
             replies: 25,
             media: vec![],
+            quoted_tweet: None,
         };
 
         let json = serde_json::to_string(&tweet).unwrap();
@@ -642,3 +4412,98 @@ mod types_tests {
         assert!(debug_output.contains("[REDACTED]"));
     }
 }
+
+mod schema_tests {
+    use super::*;
+    use crate::schema::{validate, InputSchema};
+    use serde_json::json;
+
+    #[test]
+    fn test_serialized_inputs_match_their_schemas() {
+        let post = TwitterPostInput {
+            text: "Hello".to_string(),
+            media_urls: vec!["https://example.com/a.png".to_string()],
+            reply_to: None,
+        };
+        let search = TwitterSearchInput {
+            query: "rust".to_string(),
+            operators: SearchOperators {
+                from: Some("rustlang".to_string()),
+                hashtags: vec!["rust".to_string()],
+                since: Some("2024-01-01".to_string()),
+                min_likes: Some(10),
+                exclude_replies: true,
+                ..Default::default()
+            },
+            count: 20,
+            filter: SearchFilter::Latest,
+            cursor: None,
+            download_media: false,
+        };
+        let timeline = TwitterGetTimelineInput {
+            timeline_type: TimelineType::User,
+            username: Some("rustlang".to_string()),
+            count: 20,
+            cursor: None,
+            download_media: false,
+        };
+        let thread = TwitterReadThreadInput {
+            tweet_url_or_id: "123".to_string(),
+            max_tweets: 10,
+            download_media: false,
+        };
+
+        let check = |schema: serde_json::Value, value: serde_json::Value| {
+            assert!(validate(&schema, &value).is_ok(), "{} failed", value);
+        };
+        check(TwitterPostInput::input_schema(), serde_json::to_value(&post).unwrap());
+        check(TwitterSearchInput::input_schema(), serde_json::to_value(&search).unwrap());
+        check(TwitterGetTimelineInput::input_schema(), serde_json::to_value(&timeline).unwrap());
+        check(TwitterReadThreadInput::input_schema(), serde_json::to_value(&thread).unwrap());
+    }
+
+    #[test]
+    fn test_enum_variants_in_schema() {
+        let schema = TwitterSearchInput::input_schema();
+        assert_eq!(
+            schema["properties"]["filter"]["enum"],
+            json!(["top", "latest", "people", "photos", "videos"])
+        );
+
+        let schema = TwitterGetTimelineInput::input_schema();
+        assert_eq!(
+            schema["properties"]["timeline_type"]["oneOf"][0]["enum"],
+            json!(["home", "user", "following", "bookmarks"])
+        );
+    }
+
+    #[test]
+    fn test_timeline_schema_accepts_parameterized_variants() {
+        let schema = TwitterGetTimelineInput::input_schema();
+        let valid = [
+            json!({"timeline_type": "bookmarks"}),
+            json!({"timeline_type": {"list": {"id": "1234"}}}),
+            json!({"timeline_type": {"replies": {"username": "rustlang"}}}),
+        ];
+        for input in valid {
+            assert!(validate(&schema, &input).is_ok(), "{}", input);
+        }
+
+        let invalid = [
+            json!({"timeline_type": "list"}),
+            json!({"timeline_type": {"list": {}}}),
+            json!({"timeline_type": {"likes": {"username": "rustlang"}, "media": {}}}),
+        ];
+        for input in invalid {
+            assert!(validate(&schema, &input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_post_schema_rejects_long_text() {
+        let schema = TwitterPostInput::input_schema();
+        let too_long = json!({ "text": "a".repeat(TWEET_MAX_LENGTH + 1) });
+        assert!(validate(&schema, &too_long).is_err());
+        assert!(validate(&schema, &json!({ "media_urls": [] })).is_err());
+    }
+}
@@ -1,11 +1,19 @@
 //! Twitter timeline tool
 //!
-//! Get home or user timeline.
+//! Get the home, following, user, list, bookmarks, likes, replies or media
+//! timeline.
 
+use std::sync::Arc;
+
+use synmem_core::ports::outbound::BrowserDriverPort;
+
+use super::feed::{self, FeedCursor, FeedLayout};
+use super::page::TWITTER_BASE_URL;
 use super::{
-    RateLimiter, RateLimitConfig, TimelineType, Tweet, TwitterError, TwitterGetTimelineInput,
-    TwitterGetTimelineResult, TwitterSession,
+    RateLimiter, RateLimitConfig, TimelineType, TwitterError, TwitterGetTimelineInput,
+    TwitterGetTimelineResult, TwitterSession, MAX_RESULTS_PER_REQUEST, USERNAME_MAX_LENGTH,
 };
+use crate::tools::Progress;
 
 /// Get Twitter timeline
 ///
@@ -13,9 +21,12 @@ use super::{
 /// * `input` - The timeline type and options
 /// * `session` - Valid Twitter session
 /// * `rate_limiter` - Rate limiter to prevent API abuse
+/// * `driver` - Browser driver used to load the timeline
+/// * `progress` - Receives the number of tweets collected out of `count`
 ///
 /// # Returns
-/// Result containing timeline tweets or an error
+/// Result containing timeline tweets or an error. Pass `next_cursor` back
+/// as `cursor` to continue after the last tweet returned.
 ///
 /// # Example
 /// ```ignore
@@ -24,19 +35,29 @@ use super::{
 ///     username: None,
 ///     count: 20,
 ///     cursor: None,
+///     download_media: false,
 /// };
-/// let result = twitter_get_timeline(input, &session, &rate_limiter).await?;
+/// let result =
+///     twitter_get_timeline(input, &session, &rate_limiter, &driver, &Progress::none()).await?;
 /// for tweet in result.tweets {
 ///     println!("{}: {}", tweet.author, tweet.text);
 /// }
 /// ```
-pub async fn twitter_get_timeline(
+pub async fn twitter_get_timeline<D: BrowserDriverPort>(
     input: TwitterGetTimelineInput,
     session: &TwitterSession,
     rate_limiter: &RateLimiter,
+    driver: &Arc<D>,
+    progress: &Progress,
 ) -> Result<TwitterGetTimelineResult, TwitterError> {
     // Validate input
     validate_timeline_input(&input)?;
+    let timeline_url = build_timeline_url(&input)?;
+    let cursor = input
+        .cursor
+        .as_deref()
+        .map(|cursor| FeedCursor::decode(cursor, &timeline_url))
+        .transpose()?;
 
     // Check rate limit
     rate_limiter.acquire().await?;
@@ -44,22 +65,15 @@ pub async fn twitter_get_timeline(
     // Validate session
     validate_session(session)?;
 
-    // Build the timeline URL based on type
-    // TODO: This URL will be used by the browser driver for actual navigation
-    #[allow(unused_variables)]
-    let timeline_url = build_timeline_url(&input)?;
-
-    // In a real implementation, this would:
-    // 1. Navigate to the timeline URL
-    // 2. Wait for tweets to load
-    // 3. Extract tweets from the DOM
-    // 4. Handle pagination with cursor
-
-    // For now, return a placeholder result
+    let layout = match input.timeline_type {
+        TimelineType::Media { .. } => FeedLayout::MediaGrid,
+        _ => FeedLayout::Tweets,
+    };
+    let page = feed::collect(driver, &timeline_url, layout, cursor, input.count, progress).await?;
     Ok(TwitterGetTimelineResult {
         success: true,
-        tweets: vec![],
-        next_cursor: None,
+        tweets: page.tweets,
+        next_cursor: page.next_cursor,
         error: None,
     })
 }
@@ -80,14 +94,20 @@ fn validate_timeline_input(input: &TwitterGetTimelineInput) -> Result<(), Twitte
         });
     }
 
-    if input.count > 100 {
+    if input.count > MAX_RESULTS_PER_REQUEST {
         return Err(TwitterError::InvalidInput {
-            message: "Count cannot exceed 100".to_string(),
+            message: format!("Count cannot exceed {}", MAX_RESULTS_PER_REQUEST),
         });
     }
 
     // Validate username format if provided
-    if let Some(username) = &input.username {
+    let username = match &input.timeline_type {
+        TimelineType::Likes { username }
+        | TimelineType::Replies { username }
+        | TimelineType::Media { username } => Some(username),
+        _ => input.username.as_ref(),
+    };
+    if let Some(username) = username {
         if !is_valid_username(username) {
             return Err(TwitterError::InvalidInput {
                 message: format!("Invalid username format: {}", username),
@@ -95,30 +115,45 @@ fn validate_timeline_input(input: &TwitterGetTimelineInput) -> Result<(), Twitte
         }
     }
 
+    if let TimelineType::List { id } = &input.timeline_type {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(TwitterError::InvalidInput {
+                message: format!("Invalid list ID: {}", id),
+            });
+        }
+    }
+
     Ok(())
 }
 
 /// Build the timeline URL for the given type
 fn build_timeline_url(input: &TwitterGetTimelineInput) -> Result<String, TwitterError> {
-    let url = match input.timeline_type {
-        TimelineType::Home => "https://twitter.com/home".to_string(),
-        TimelineType::Following => "https://twitter.com/home?f=following".to_string(),
+    let url = match &input.timeline_type {
+        TimelineType::Home => format!("{}/home", TWITTER_BASE_URL),
+        TimelineType::Following => format!("{}/home?f=following", TWITTER_BASE_URL),
         TimelineType::User => {
             let username = input.username.as_ref().ok_or(TwitterError::InvalidInput {
                 message: "Username is required for user timeline".to_string(),
             })?;
-            format!("https://twitter.com/{}", username)
+            format!("{}/{}", TWITTER_BASE_URL, username)
         }
+        TimelineType::List { id } => format!("{}/i/lists/{}", TWITTER_BASE_URL, id),
+        TimelineType::Bookmarks => format!("{}/i/bookmarks", TWITTER_BASE_URL),
+        TimelineType::Likes { username } => format!("{}/{}/likes", TWITTER_BASE_URL, username),
+        TimelineType::Replies { username } => {
+            format!("{}/{}/with_replies", TWITTER_BASE_URL, username)
+        }
+        TimelineType::Media { username } => format!("{}/{}/media", TWITTER_BASE_URL, username),
     };
     Ok(url)
 }
 
 /// Check if a username is valid
-fn is_valid_username(username: &str) -> bool {
+pub(super) fn is_valid_username(username: &str) -> bool {
     // Twitter usernames:
     // - 1-15 characters
     // - Alphanumeric and underscore only
-    if username.is_empty() || username.len() > 15 {
+    if username.is_empty() || username.len() > USERNAME_MAX_LENGTH {
         return false;
     }
 
@@ -139,14 +174,3 @@ fn validate_session(session: &TwitterSession) -> Result<(), TwitterError> {
 pub fn create_timeline_rate_limiter() -> RateLimiter {
     RateLimiter::new(RateLimitConfig::for_read())
 }
-
-/// Parse tweets from the timeline DOM
-///
-/// This is a placeholder for the actual implementation that would
-/// extract tweet data from the browser DOM.
-#[allow(dead_code)]
-fn parse_timeline_tweets(_html: &str, _max_count: usize) -> Vec<Tweet> {
-    // In a real implementation, this would parse the DOM
-    // and extract tweets from the timeline
-    vec![]
-}
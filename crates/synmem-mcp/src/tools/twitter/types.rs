@@ -1,10 +1,28 @@
 //! Twitter types and data structures
 
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Maximum length for a tweet
 pub const TWEET_MAX_LENGTH: usize = 280;
 
+/// Maximum number of media attachments on a tweet
+pub const MAX_MEDIA_PER_TWEET: usize = 4;
+
+/// Maximum number of tweets returned by a single search or timeline request
+pub const MAX_RESULTS_PER_REQUEST: usize = 100;
+
+/// Maximum number of tweets in a thread
+pub const MAX_THREAD_LENGTH: usize = 25;
+
+/// Maximum length of a direct message
+pub const DM_MAX_LENGTH: usize = 10_000;
+
+/// Maximum length for a Twitter username
+pub const USERNAME_MAX_LENGTH: usize = 15;
+
 /// Input parameters for posting a tweet
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TwitterPostInput {
@@ -31,6 +49,450 @@ pub struct TwitterPostResult {
     pub error: Option<String>,
 }
 
+/// Input parameters for scheduling a tweet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterSchedulePostInput {
+    /// The tweet to post
+    #[serde(flatten)]
+    pub post: TwitterPostInput,
+    /// When to post it
+    pub publish_at: DateTime<Utc>,
+}
+
+/// A tweet in the schedule queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledPost {
+    /// Queue entry ID
+    pub id: String,
+    /// The tweet to post
+    pub post: TwitterPostInput,
+    /// When to post it
+    pub publish_at: DateTime<Utc>,
+    /// When it was queued
+    pub created_at: DateTime<Utc>,
+    /// Where it is in the queue
+    #[serde(default)]
+    pub status: ScheduledPostStatus,
+    /// Earliest next attempt, after the rate limit deferred the post
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<DateTime<Utc>>,
+    /// ID of the tweet once published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tweet_id: Option<String>,
+    /// URL of the tweet once published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tweet_url: Option<String>,
+    /// Why publishing failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Status of a scheduled tweet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduledPostStatus {
+    /// Waiting for its publish time
+    #[default]
+    Pending,
+    /// Being posted
+    Publishing,
+    /// Posted
+    Published,
+    /// Could not be posted
+    Failed,
+}
+
+impl ScheduledPostStatus {
+    /// All statuses
+    pub const ALL: [ScheduledPostStatus; 4] = [
+        ScheduledPostStatus::Pending,
+        ScheduledPostStatus::Publishing,
+        ScheduledPostStatus::Published,
+        ScheduledPostStatus::Failed,
+    ];
+}
+
+/// Input parameters for listing scheduled tweets
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TwitterListScheduledInput {
+    /// Only list tweets with this status
+    #[serde(default)]
+    pub status: Option<ScheduledPostStatus>,
+}
+
+/// Result of listing scheduled tweets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterListScheduledResult {
+    /// Whether the listing was successful
+    pub success: bool,
+    /// The scheduled tweets, by publish time
+    pub posts: Vec<ScheduledPost>,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
+/// Input parameters for moving a scheduled tweet to another time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterReschedulePostInput {
+    /// Queue entry ID
+    pub id: String,
+    /// When to post it instead
+    pub publish_at: DateTime<Utc>,
+}
+
+/// Input parameters for cancelling a scheduled tweet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterCancelScheduledInput {
+    /// Queue entry ID
+    pub id: String,
+}
+
+/// Result of scheduling, rescheduling or cancelling a tweet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterScheduledPostResult {
+    /// Whether the operation was successful
+    pub success: bool,
+    /// The queue entry, as it is after the operation
+    pub post: Option<ScheduledPost>,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
+/// Input parameters for posting a thread
+///
+/// Provide either `segments` or `text`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterPostThreadInput {
+    /// The tweets of the thread, in order
+    #[serde(default)]
+    pub segments: Vec<String>,
+    /// Long text to split into numbered tweets on sentence boundaries
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Delete the tweets already posted if a later one fails
+    #[serde(default)]
+    pub rollback_on_failure: bool,
+}
+
+/// A tweet posted as part of a thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostedTweet {
+    /// The ID of the tweet
+    pub tweet_id: String,
+    /// URL of the tweet
+    pub tweet_url: String,
+}
+
+/// Result of posting a thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterPostThreadResult {
+    /// Whether every tweet was posted
+    pub success: bool,
+    /// The tweets that are published, in order
+    pub tweets: Vec<PostedTweet>,
+    /// Number of tweets the thread was meant to have
+    pub total: usize,
+    /// Whether tweets were deleted after a failure
+    pub rolled_back: bool,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
+/// Input parameters for an action on a tweet (like, retweet, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterTweetActionInput {
+    /// The URL or ID of the tweet
+    pub tweet_url_or_id: String,
+}
+
+/// Input parameters for an action on an account (follow, unfollow)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterUserActionInput {
+    /// Username of the account, with or without the leading @
+    pub username: String,
+}
+
+/// Result of an engagement action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterActionResult {
+    /// Whether the action is in effect
+    pub success: bool,
+    /// The action performed, e.g. "like"
+    pub action: String,
+    /// Tweet ID or username the action applies to
+    pub target: String,
+    /// Whether anything changed (false if the action was already applied)
+    pub changed: bool,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
+/// Input parameters for getting a profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterGetProfileInput {
+    /// Username of the account, with or without the leading @
+    pub username: String,
+}
+
+/// Public profile of an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterProfile {
+    /// Username (without the @)
+    pub username: String,
+    /// Display name
+    pub display_name: String,
+    /// Profile description
+    pub bio: String,
+    /// When the account was created, as shown (e.g. "March 2009")
+    pub joined: Option<String>,
+    /// Number of followers, if shown
+    pub followers: Option<u64>,
+    /// Number of accounts followed, if shown
+    pub following: Option<u64>,
+    /// Whether the account has a verified badge
+    pub verified: bool,
+    /// Whether the account's tweets are protected
+    pub protected: bool,
+    /// Tweet pinned to the top of the profile
+    pub pinned_tweet: Option<Box<Tweet>>,
+}
+
+/// Result of getting a profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterGetProfileResult {
+    /// Whether the retrieval was successful
+    pub success: bool,
+    /// The profile
+    pub profile: Option<TwitterProfile>,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
+/// Input parameters for getting notifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterGetNotificationsInput {
+    /// Notifications tab to read
+    #[serde(default)]
+    pub tab: NotificationTab,
+    /// Maximum number of notifications to retrieve
+    #[serde(default = "default_notification_count")]
+    pub count: usize,
+    /// `since` returned by a previous call; only newer notifications are
+    /// returned
+    pub since: Option<String>,
+}
+
+pub(super) fn default_notification_count() -> usize {
+    20
+}
+
+/// Notifications tab
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationTab {
+    /// Every notification (default)
+    #[default]
+    All,
+    /// Mentions and replies only
+    Mentions,
+}
+
+impl NotificationTab {
+    /// All tab variants
+    pub const ALL: [NotificationTab; 2] = [NotificationTab::All, NotificationTab::Mentions];
+}
+
+/// Type of notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationKind {
+    /// A tweet mentioning the account
+    Mention,
+    /// A reply to one of the account's tweets
+    Reply,
+    /// Likes on one of the account's tweets
+    Like,
+    /// New followers
+    Follow,
+    /// Retweets of one of the account's tweets
+    Retweet,
+}
+
+/// A single notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterNotification {
+    /// Identifier of the notification, stable across polls
+    pub id: String,
+    /// Type of notification
+    pub kind: NotificationKind,
+    /// When it happened, if shown
+    pub timestamp: Option<String>,
+    /// Usernames of the accounts that triggered it
+    pub actors: Vec<String>,
+    /// The mentioning or replying tweet, or the tweet that was liked or
+    /// retweeted
+    pub tweet_id: Option<String>,
+    /// Text of the tweet, or the notification message
+    pub text: String,
+}
+
+/// Result of getting notifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterGetNotificationsResult {
+    /// Whether the retrieval was successful
+    pub success: bool,
+    /// Notifications, newest first
+    pub notifications: Vec<TwitterNotification>,
+    /// Pass as `since` on the next call to only get newer notifications,
+    /// including any this call left out to stay within `count`
+    pub since: Option<String>,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
+/// Input parameters for listing DM conversations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterListConversationsInput {
+    /// Maximum number of conversations to retrieve
+    #[serde(default = "default_conversation_count")]
+    pub count: usize,
+}
+
+pub(super) fn default_conversation_count() -> usize {
+    20
+}
+
+/// A DM conversation
+///
+/// The Debug implementation redacts the message preview.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TwitterConversation {
+    /// Conversation ID, e.g. "123-456" for one-to-one conversations
+    pub conversation_id: String,
+    /// Name of the conversation or of the other participant
+    pub display_name: String,
+    /// Usernames of the other participants, if shown
+    pub participants: Vec<String>,
+    /// Start of the last message
+    pub last_message: String,
+    /// Time of the last message
+    pub timestamp: Option<String>,
+    /// Whether the conversation has unread messages
+    pub unread: bool,
+}
+
+impl std::fmt::Debug for TwitterConversation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwitterConversation")
+            .field("conversation_id", &self.conversation_id)
+            .field("display_name", &self.display_name)
+            .field("participants", &self.participants)
+            .field("last_message", &"[REDACTED]")
+            .field("timestamp", &self.timestamp)
+            .field("unread", &self.unread)
+            .finish()
+    }
+}
+
+/// Result of listing DM conversations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterListConversationsResult {
+    /// Whether the retrieval was successful
+    pub success: bool,
+    /// Conversations, most recent first
+    pub conversations: Vec<TwitterConversation>,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
+/// Input parameters for reading a DM conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterReadDmInput {
+    /// Conversation ID, as returned by `twitter_list_conversations`
+    pub conversation_id: String,
+    /// Maximum number of messages to retrieve (the most recent ones)
+    #[serde(default = "default_message_count")]
+    pub count: usize,
+}
+
+pub(super) fn default_message_count() -> usize {
+    50
+}
+
+/// A direct message
+///
+/// The Debug implementation redacts the message text.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TwitterDirectMessage {
+    /// Username of the sender, `None` for messages sent by the account
+    pub sender: Option<String>,
+    /// Whether the account sent the message
+    pub outgoing: bool,
+    /// Message text
+    pub text: String,
+    /// When the message was sent, if shown
+    pub timestamp: Option<String>,
+}
+
+impl std::fmt::Debug for TwitterDirectMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwitterDirectMessage")
+            .field("sender", &self.sender)
+            .field("outgoing", &self.outgoing)
+            .field("text", &"[REDACTED]")
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
+
+/// Result of reading a DM conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterReadDmResult {
+    /// Whether the read was successful
+    pub success: bool,
+    /// The conversation read
+    pub conversation_id: String,
+    /// Messages, oldest first
+    pub messages: Vec<TwitterDirectMessage>,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
+/// Input parameters for sending a direct message
+///
+/// Provide either `conversation_id` or `username`. The Debug
+/// implementation redacts the message text.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TwitterSendDmInput {
+    /// Conversation to send the message to
+    pub conversation_id: Option<String>,
+    /// Username to start or continue a one-to-one conversation with
+    pub username: Option<String>,
+    /// Message text
+    pub text: String,
+}
+
+impl std::fmt::Debug for TwitterSendDmInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwitterSendDmInput")
+            .field("conversation_id", &self.conversation_id)
+            .field("username", &self.username)
+            .field("text", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Result of sending a direct message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwitterSendDmResult {
+    /// Whether the message was sent
+    pub success: bool,
+    /// Conversation the message was sent to, if known
+    pub conversation_id: Option<String>,
+    /// Error message if failed
+    pub error: Option<String>,
+}
+
 /// Input parameters for reading a thread
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TwitterReadThreadInput {
@@ -39,9 +501,12 @@ pub struct TwitterReadThreadInput {
     /// Maximum number of tweets to retrieve
     #[serde(default = "default_max_tweets")]
     pub max_tweets: usize,
+    /// Download the media of the tweets into the local media store
+    #[serde(default)]
+    pub download_media: bool,
 }
 
-fn default_max_tweets() -> usize {
+pub(super) fn default_max_tweets() -> usize {
     100
 }
 
@@ -67,6 +532,9 @@ pub struct Tweet {
     /// Media attachments
     #[serde(default)]
     pub media: Vec<TweetMedia>,
+    /// Tweet quoted by this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quoted_tweet: Option<Box<Tweet>>,
 }
 
 /// Media attachment on a tweet
@@ -78,6 +546,25 @@ pub struct TweetMedia {
     pub url: String,
     /// Alt text if available
     pub alt_text: Option<String>,
+    /// Copy in the local media store, when downloaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<LocalMedia>,
+}
+
+/// Media file kept in the local media store
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalMedia {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+    /// MIME type, e.g. "image/jpeg"
+    pub mime_type: String,
+    /// SHA-256 of the content, hex encoded
+    pub sha256: String,
+    /// URL the file was downloaded from, which may be a better quality
+    /// version of `url`
+    pub source_url: String,
 }
 
 /// Result of reading a thread
@@ -94,22 +581,75 @@ pub struct TwitterReadThreadResult {
 }
 
 /// Input parameters for searching tweets
+///
+/// The free-text `query` and the `operators` are combined into one X
+/// search query; either may be left empty, not both.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TwitterSearchInput {
     /// Search query
+    #[serde(default)]
     pub query: String,
+    /// Advanced search operators
+    #[serde(flatten)]
+    pub operators: SearchOperators,
     /// Maximum number of results
     #[serde(default = "default_search_count")]
     pub count: usize,
     /// Filter by: latest, top, people, photos, videos
     #[serde(default)]
     pub filter: SearchFilter,
+    /// Cursor for pagination
+    pub cursor: Option<String>,
+    /// Download the media of the tweets into the local media store
+    #[serde(default)]
+    pub download_media: bool,
 }
 
-fn default_search_count() -> usize {
+pub(super) fn default_search_count() -> usize {
     20
 }
 
+/// Advanced search operators, compiled into X's search syntax
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchOperators {
+    /// Only tweets posted by this account (`from:`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Only tweets replying to this account (`to:`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Only tweets mentioning all of these accounts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<String>,
+    /// Only tweets with all of these hashtags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hashtags: Vec<String>,
+    /// Only tweets posted on or after this date, as `YYYY-MM-DD` (`since:`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Only tweets posted before this date, as `YYYY-MM-DD` (`until:`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+    /// Only tweets with at least this many likes (`min_faves:`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_likes: Option<u64>,
+    /// Only tweets with at least this many retweets (`min_retweets:`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_retweets: Option<u64>,
+    /// Only tweets in this language, as an ISO 639-1 code (`lang:`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// Only tweets with images or videos (`filter:media`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub has_media: bool,
+    /// Leave out replies (`-filter:replies`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclude_replies: bool,
+    /// Leave out retweets (`-filter:retweets`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclude_retweets: bool,
+}
+
 /// Search result filter type
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -127,6 +667,17 @@ pub enum SearchFilter {
     Videos,
 }
 
+impl SearchFilter {
+    /// All filter variants
+    pub const ALL: [SearchFilter; 5] = [
+        SearchFilter::Top,
+        SearchFilter::Latest,
+        SearchFilter::People,
+        SearchFilter::Photos,
+        SearchFilter::Videos,
+    ];
+}
+
 /// Result of searching tweets
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TwitterSearchResult {
@@ -153,14 +704,20 @@ pub struct TwitterGetTimelineInput {
     pub count: usize,
     /// Cursor for pagination
     pub cursor: Option<String>,
+    /// Download the media of the tweets into the local media store
+    #[serde(default)]
+    pub download_media: bool,
 }
 
-fn default_timeline_count() -> usize {
+pub(super) fn default_timeline_count() -> usize {
     20
 }
 
 /// Type of timeline to retrieve
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// Variants without parameters are plain strings ("home"); the others are
+/// objects keyed by the variant, e.g. `{"list": {"id": "1234"}}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TimelineType {
     /// Home timeline (for you)
@@ -170,6 +727,38 @@ pub enum TimelineType {
     User,
     /// Following timeline
     Following,
+    /// Tweets of a list
+    List {
+        /// Numeric list ID, as in `/i/lists/<id>`
+        id: String,
+    },
+    /// Bookmarks of the logged-in account
+    Bookmarks,
+    /// Tweets liked by a user (only visible for the logged-in account)
+    Likes {
+        /// Username without the @
+        username: String,
+    },
+    /// Tweets and replies of a user
+    Replies {
+        /// Username without the @
+        username: String,
+    },
+    /// Tweets with photos or videos of a user
+    Media {
+        /// Username without the @
+        username: String,
+    },
+}
+
+impl TimelineType {
+    /// Timeline variants without parameters
+    pub const SIMPLE: [TimelineType; 4] = [
+        TimelineType::Home,
+        TimelineType::User,
+        TimelineType::Following,
+        TimelineType::Bookmarks,
+    ];
 }
 
 /// Result of getting timeline
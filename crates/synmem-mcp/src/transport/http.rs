@@ -0,0 +1,445 @@
+//! Streamable HTTP transport
+//!
+//! A single [`MCP_PATH`] endpoint:
+//! - `POST` carries one JSON-RPC message and returns the JSON response
+//!   (`202 Accepted` for notifications)
+//! - `GET` opens a Server-Sent Events stream of server-to-client
+//!   notifications for the session
+//! - `DELETE` ends the session
+//!
+//! A session is created by a successful `initialize`; its ID is returned in
+//! the [`SESSION_HEADER`] and must be sent with every later request.
+//! Sessions left idle for [`SESSION_IDLE_TIMEOUT`] without a notification
+//! stream are dropped when a new one starts, and at most [`MAX_SESSIONS`]
+//! are kept: past that, new sessions are refused with `503 Service
+//! Unavailable` rather than dropping one with an open stream. When a bearer
+//! token is configured, every request must carry
+//! `Authorization: Bearer <token>`.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use axum::extract::{Request, State};
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE, WWW_AUTHENTICATE};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::Router;
+use futures::{stream, Stream, StreamExt};
+use serde_json::Value;
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use crate::connection::Connection;
+use crate::server::McpServer;
+
+/// Path of the MCP endpoint
+pub const MCP_PATH: &str = "/mcp";
+
+/// Header carrying the session ID
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// How long a session may go without requests before it can be dropped
+pub const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Most sessions kept at once; the least recently used without a
+/// notification stream makes room
+pub const MAX_SESSIONS: usize = 1024;
+
+/// State shared by the HTTP handlers
+struct HttpState<D: BrowserDriverPort> {
+    server: Arc<McpServer<D>>,
+    bearer_token: Option<String>,
+    sessions: RwLock<HashMap<String, HttpSession>>,
+    idle_timeout: Duration,
+    max_sessions: usize,
+    shutdown: watch::Receiver<bool>,
+}
+
+/// A client session
+struct HttpSession {
+    connection: Arc<Connection>,
+    /// When the client last sent a request
+    last_seen: Mutex<Instant>,
+}
+
+impl HttpSession {
+    fn last_seen(&self) -> Instant {
+        *self.last_seen.lock().unwrap()
+    }
+
+    /// Whether the client stopped using the session: no request for
+    /// `timeout` and no notification stream open
+    fn is_idle(&self, now: Instant, timeout: Duration) -> bool {
+        !self.connection.has_listeners() && now.duration_since(self.last_seen()) >= timeout
+    }
+}
+
+impl<D: BrowserDriverPort> HttpState<D> {
+    /// Look up the session named by the request headers
+    fn session(&self, headers: &HeaderMap) -> Result<Arc<Connection>, (StatusCode, &'static str)> {
+        let id = headers
+            .get(SESSION_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .ok_or((StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header"))?;
+
+        let sessions = self.sessions.read().unwrap();
+        let session = sessions
+            .get(id)
+            .ok_or((StatusCode::NOT_FOUND, "Unknown session"))?;
+        *session.last_seen.lock().unwrap() = Instant::now();
+        Ok(session.connection.clone())
+    }
+
+    /// Keep `connection` as a session, once its `initialize` succeeded
+    ///
+    /// Drops the idle sessions first, then the least recently used one
+    /// without a notification stream if there are still too many. Fails
+    /// when every session has a stream open.
+    fn add_session(&self, connection: Arc<Connection>) -> Result<(), (StatusCode, &'static str)> {
+        let mut sessions = self.sessions.write().unwrap();
+        let now = Instant::now();
+        let idle: Vec<String> = sessions
+            .iter()
+            .filter(|(_, session)| session.is_idle(now, self.idle_timeout))
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in idle {
+            sessions.remove(&id);
+            self.server.disconnect(&id);
+            info!(session = %id, "HTTP session expired");
+        }
+        if sessions.len() >= self.max_sessions {
+            let oldest = sessions
+                .iter()
+                .filter(|(_, session)| !session.connection.has_listeners())
+                .min_by_key(|(_, session)| session.last_seen())
+                .map(|(id, _)| id.clone());
+            let Some(id) = oldest else {
+                warn!("Too many HTTP sessions, all streaming; refused a new one");
+                return Err((StatusCode::SERVICE_UNAVAILABLE, "Too many sessions"));
+            };
+            sessions.remove(&id);
+            self.server.disconnect(&id);
+            warn!(session = %id, "Too many HTTP sessions, dropped the least recently used");
+        }
+
+        info!(session = %connection.id(), "HTTP session started");
+        let session = HttpSession {
+            connection: connection.clone(),
+            last_seen: Mutex::new(now),
+        };
+        sessions.insert(connection.id().to_string(), session);
+        Ok(())
+    }
+}
+
+/// Serve MCP over HTTP on `listener` until `shutdown` resolves
+///
+/// On shutdown the listener stops accepting connections, open SSE streams
+/// are closed and in-flight requests are allowed to finish.
+pub async fn serve_http<D, F>(
+    server: Arc<McpServer<D>>,
+    listener: TcpListener,
+    bearer_token: Option<String>,
+    shutdown: F,
+) -> std::io::Result<()>
+where
+    D: BrowserDriverPort + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    if bearer_token.is_none() {
+        warn!("HTTP transport running without bearer-token authentication");
+    }
+    info!(address = %listener.local_addr()?, "Serving MCP over HTTP");
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let state = Arc::new(HttpState {
+        server,
+        bearer_token,
+        sessions: RwLock::new(HashMap::new()),
+        idle_timeout: SESSION_IDLE_TIMEOUT,
+        max_sessions: MAX_SESSIONS,
+        shutdown: shutdown_rx,
+    });
+
+    let app = Router::new()
+        .route(
+            MCP_PATH,
+            post(handle_post::<D>)
+                .get(handle_get::<D>)
+                .delete(handle_delete::<D>),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            authorize::<D>,
+        ))
+        .with_state(state);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            info!("Shutting down HTTP transport");
+            let _ = shutdown_tx.send(true);
+        })
+        .await
+}
+
+/// Reject requests without the configured bearer token
+async fn authorize<D: BrowserDriverPort>(
+    State(state): State<Arc<HttpState<D>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(expected) = &state.bearer_token {
+        let provided = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+
+        let authorized = provided
+            .map(|token| constant_time_eq(token.as_bytes(), expected.as_bytes()))
+            .unwrap_or(false);
+        if !authorized {
+            debug!("Rejected request with missing or invalid bearer token");
+            return (
+                StatusCode::UNAUTHORIZED,
+                [(WWW_AUTHENTICATE, "Bearer")],
+                "Missing or invalid bearer token",
+            )
+                .into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Handle a JSON-RPC message
+async fn handle_post<D: BrowserDriverPort>(
+    State(state): State<Arc<HttpState<D>>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let initializing = is_initialize(&body);
+    let connection = if initializing {
+        state.server.connect(uuid::Uuid::new_v4().to_string())
+    } else {
+        match state.session(&headers) {
+            Ok(connection) => connection,
+            Err(error) => return error.into_response(),
+        }
+    };
+
+    let reply = state.server.handle_message(&body, &connection).await;
+    if initializing {
+        // A rejected handshake leaves no session behind
+        if !reply.as_deref().is_some_and(is_success) {
+            state.server.disconnect(connection.id());
+            return reply_response(reply);
+        }
+        if let Err(error) = state.add_session(connection.clone()) {
+            state.server.disconnect(connection.id());
+            return error.into_response();
+        }
+    }
+
+    let mut response = reply_response(reply);
+
+    if let Ok(id) = HeaderValue::from_str(connection.id()) {
+        response.headers_mut().insert(SESSION_HEADER, id);
+    }
+    response
+}
+
+/// HTTP response carrying a JSON-RPC reply, `202 Accepted` without one
+fn reply_response(reply: Option<String>) -> Response {
+    match reply {
+        Some(json) => ([(CONTENT_TYPE, "application/json")], json).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+/// Open the notification stream for a session
+async fn handle_get<D: BrowserDriverPort>(
+    State(state): State<Arc<HttpState<D>>>,
+    headers: HeaderMap,
+) -> Response {
+    let connection = match state.session(&headers) {
+        Ok(connection) => connection,
+        Err(error) => return error.into_response(),
+    };
+    debug!(session = %connection.id(), "Notification stream opened");
+
+    let mut shutdown = state.shutdown.clone();
+    let events = notification_events(&connection).take_until(async move {
+        let _ = shutdown.wait_for(|stopped| *stopped).await;
+    });
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// End a session
+async fn handle_delete<D: BrowserDriverPort>(
+    State(state): State<Arc<HttpState<D>>>,
+    headers: HeaderMap,
+) -> Response {
+    let connection = match state.session(&headers) {
+        Ok(connection) => connection,
+        Err(error) => return error.into_response(),
+    };
+
+    state.sessions.write().unwrap().remove(connection.id());
+    state.server.disconnect(connection.id());
+    info!(session = %connection.id(), "HTTP session ended");
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// SSE events for the notifications sent to `connection`
+///
+/// The stream ends once the session is removed and its last in-flight
+/// request completes.
+fn notification_events(
+    connection: &Connection,
+) -> impl Stream<Item = Result<Event, Infallible>> + Send + 'static {
+    stream::unfold(connection.subscribe(), |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(notification) => {
+                    let data = serde_json::to_string(&notification).unwrap_or_default();
+                    return Some((Ok(Event::default().event("message").data(data)), receiver));
+                }
+                Err(RecvError::Lagged(skipped)) => warn!(skipped, "Dropped notifications"),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+/// Whether the body is an `initialize` request
+fn is_initialize(body: &str) -> bool {
+    serde_json::from_str::<Value>(body)
+        .map(|v| v["method"] == "initialize")
+        .unwrap_or(false)
+}
+
+/// Whether a JSON-RPC response reports success
+fn is_success(response: &str) -> bool {
+    serde_json::from_str::<Value>(response)
+        .map(|v| v.get("result").is_some())
+        .unwrap_or(false)
+}
+
+/// Compare two byte strings without short-circuiting on the first mismatch
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{test_context, MockDriver};
+    use crate::tools::ToolRegistry;
+
+    fn test_state(idle_timeout: Duration, max_sessions: usize) -> HttpState<MockDriver> {
+        let server = McpServer::new(ToolRegistry::new(), test_context());
+        HttpState {
+            server: Arc::new(server),
+            bearer_token: None,
+            sessions: RwLock::new(HashMap::new()),
+            idle_timeout,
+            max_sessions,
+            shutdown: watch::channel(false).1,
+        }
+    }
+
+    /// Start a session and return its ID
+    fn start_session(state: &HttpState<MockDriver>) -> String {
+        let connection = state.server.connect(uuid::Uuid::new_v4().to_string());
+        state.add_session(connection.clone()).unwrap();
+        connection.id().to_string()
+    }
+
+    fn headers_for(id: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(SESSION_HEADER, HeaderValue::from_str(id).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_idle_sessions_expire() {
+        let state = test_state(Duration::ZERO, MAX_SESSIONS);
+        let idle = start_session(&state);
+        let streaming = start_session(&state);
+        let _stream = state.session(&headers_for(&streaming)).unwrap().subscribe();
+
+        let current = start_session(&state);
+        assert!(state.session(&headers_for(&idle)).is_err());
+        assert!(state.session(&headers_for(&streaming)).is_ok());
+        assert!(state.session(&headers_for(&current)).is_ok());
+    }
+
+    #[test]
+    fn test_sessions_are_capped() {
+        let state = test_state(SESSION_IDLE_TIMEOUT, 2);
+        let first = start_session(&state);
+        let second = start_session(&state);
+        // Using the first session makes the second the least recently used
+        std::thread::sleep(Duration::from_millis(5));
+        state.session(&headers_for(&first)).unwrap();
+
+        let third = start_session(&state);
+        assert_eq!(state.sessions.read().unwrap().len(), 2);
+        assert!(state.session(&headers_for(&second)).is_err());
+        assert!(state.session(&headers_for(&first)).is_ok());
+        assert!(state.session(&headers_for(&third)).is_ok());
+    }
+
+    #[test]
+    fn test_streaming_sessions_are_not_evicted() {
+        let state = test_state(SESSION_IDLE_TIMEOUT, 2);
+        let first = start_session(&state);
+        let _first_stream = state.session(&headers_for(&first)).unwrap().subscribe();
+        let second = start_session(&state);
+
+        // The first session is older but streaming, so the second makes room
+        let third = start_session(&state);
+        assert!(state.session(&headers_for(&first)).is_ok());
+        assert!(state.session(&headers_for(&second)).is_err());
+        let _third_stream = state.session(&headers_for(&third)).unwrap().subscribe();
+
+        // With every session streaming, new ones are refused
+        let connection = state.server.connect(uuid::Uuid::new_v4().to_string());
+        let error = state.add_session(connection).unwrap_err();
+        assert_eq!(error.0, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(state.sessions.read().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_is_initialize() {
+        assert!(is_initialize(
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#
+        ));
+        assert!(!is_initialize(
+            r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#
+        ));
+        assert!(!is_initialize("{not json"));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"token2"));
+    }
+}
@@ -0,0 +1,7 @@
+//! Transports carrying MCP messages between clients and the server
+
+mod http;
+mod stdio;
+
+pub use http::{serve_http, MCP_PATH, SESSION_HEADER};
+pub use stdio::serve_stdio;
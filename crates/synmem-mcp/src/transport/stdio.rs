@@ -0,0 +1,130 @@
+//! Stdio transport
+//!
+//! Newline-delimited JSON-RPC messages on stdin/stdout. Logging must go to
+//! stderr so it never corrupts the protocol stream.
+//!
+//! Requests are handled concurrently, so responses may be written out of
+//! order; notifications for the connection are interleaved as they arrive.
+
+use std::sync::Arc;
+use synmem_core::ports::outbound::BrowserDriverPort;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+use crate::server::McpServer;
+
+/// Connection ID used for the single stdio client
+const STDIO_CONNECTION_ID: &str = "stdio";
+
+/// Serve MCP over the process stdin/stdout until stdin is closed
+pub async fn serve_stdio<D: BrowserDriverPort + 'static>(
+    server: Arc<McpServer<D>>,
+) -> std::io::Result<()> {
+    serve(server, tokio::io::stdin(), tokio::io::stdout()).await
+}
+
+/// Serve MCP over an arbitrary reader/writer pair
+///
+/// Returns once the reader is closed and every in-flight request has been
+/// answered.
+async fn serve<D, R, W>(server: Arc<McpServer<D>>, reader: R, mut writer: W) -> std::io::Result<()>
+where
+    D: BrowserDriverPort + 'static,
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    info!("Serving MCP over stdio");
+    let connection = server.connect(STDIO_CONNECTION_ID);
+    let mut notifications = connection.subscribe();
+    let (responses_tx, mut responses) = mpsc::unbounded_channel::<String>();
+    let mut requests = JoinSet::new();
+    let mut lines = BufReader::new(reader).lines();
+    let mut input_open = true;
+
+    loop {
+        tokio::select! {
+            line = lines.next_line(), if input_open => match line? {
+                Some(line) if line.trim().is_empty() => {}
+                Some(line) => {
+                    let server = server.clone();
+                    let connection = connection.clone();
+                    let responses_tx = responses_tx.clone();
+                    requests.spawn(async move {
+                        if let Some(response) = server.handle_message(&line, &connection).await {
+                            let _ = responses_tx.send(response);
+                        }
+                    });
+                }
+                None => {
+                    info!("Stdin closed, shutting down");
+                    input_open = false;
+                }
+            },
+            Some(response) = responses.recv() => write_line(&mut writer, &response).await?,
+            notification = notifications.recv() => match notification {
+                Ok(notification) => {
+                    let line = serde_json::to_string(&notification)?;
+                    write_line(&mut writer, &line).await?;
+                }
+                Err(RecvError::Lagged(skipped)) => warn!(skipped, "Dropped notifications"),
+                Err(RecvError::Closed) => {}
+            },
+            Some(_) = requests.join_next(), if !requests.is_empty() => {}
+        }
+
+        if !input_open && requests.is_empty() {
+            break;
+        }
+    }
+
+    while let Ok(response) = responses.try_recv() {
+        write_line(&mut writer, &response).await?;
+    }
+    Ok(())
+}
+
+/// Write a single message followed by a newline
+async fn write_line<W: AsyncWrite + Unpin>(writer: &mut W, line: &str) -> std::io::Result<()> {
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ToolsConfig;
+    use crate::testing::test_context;
+    use crate::tools::default_registry;
+    use crate::tools::twitter::TwitterSession;
+
+    #[tokio::test]
+    async fn test_serve_round_trip() {
+        let session = TwitterSession {
+            cookies: String::new(),
+            csrf_token: String::new(),
+            bearer_token: String::new(),
+            user_id: None,
+        };
+        let registry = default_registry(&ToolsConfig::default(), session);
+        let server = Arc::new(McpServer::new(registry, test_context()));
+        let input = concat!(
+            "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}\n",
+            "{\"jsonrpc\":\"2.0\",\"method\":\"notifications/initialized\"}\n",
+            "\n",
+            "{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"tools/list\"}\n",
+        );
+        let mut output = Vec::new();
+
+        serve(server, input.as_bytes(), &mut output).await.unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().any(|l| l.contains("\"id\":1")));
+        assert!(lines.iter().any(|l| l.contains("twitter_post")));
+    }
+}
@@ -0,0 +1,338 @@
+//! Integration tests for the HTTP transport, driven by a local HTTP client
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use synmem_core::domain::entities::{BrowserState, SimpleCookie};
+use synmem_core::ports::outbound::BrowserDriverPort;
+use synmem_core::SessionManager;
+use synmem_mcp::config::ToolsConfig;
+use synmem_mcp::tools::{default_registry, ToolContext};
+use synmem_mcp::transport::{serve_http, MCP_PATH, SESSION_HEADER};
+use synmem_mcp::twitter::TwitterSession;
+use synmem_mcp::McpServer;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+const TOKEN: &str = "test-token";
+
+/// Driver that accepts every command and does nothing
+struct NoopDriver;
+
+#[derive(Debug, thiserror::Error)]
+#[error("noop driver error")]
+struct NoopError;
+
+#[async_trait]
+impl BrowserDriverPort for NoopDriver {
+    type Error = NoopError;
+
+    async fn goto(&self, _url: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn back(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn forward(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn refresh(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn current_url(&self) -> Result<String, Self::Error> {
+        Ok("about:blank".to_string())
+    }
+    async fn click(&self, _selector: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn type_text(&self, _selector: &str, _text: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn select(&self, _selector: &str, _value: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn upload_files(&self, _selector: &str, _paths: &[PathBuf]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn wait_for_element(&self, _selector: &str, _timeout_ms: u64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn screenshot(&self) -> Result<Vec<u8>, Self::Error> {
+        Ok(vec![])
+    }
+    async fn get_html(&self) -> Result<String, Self::Error> {
+        Ok(String::new())
+    }
+    async fn evaluate_js(&self, _script: &str) -> Result<String, Self::Error> {
+        Ok(String::new())
+    }
+    async fn get_cookies(&self) -> Result<Vec<SimpleCookie>, Self::Error> {
+        Ok(vec![])
+    }
+    async fn set_cookies(&self, _cookies: &[SimpleCookie]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn save_session(&self) -> Result<BrowserState, Self::Error> {
+        Ok(BrowserState::new("noop"))
+    }
+    async fn load_session(&self, _state: &BrowserState) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn clear_session(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn close(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A server listening on a random local port
+struct TestServer {
+    url: String,
+    client: Client,
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: JoinHandle<std::io::Result<()>>,
+}
+
+impl TestServer {
+    async fn start() -> Self {
+        let session = TwitterSession {
+            cookies: String::new(),
+            csrf_token: String::new(),
+            bearer_token: String::new(),
+            user_id: None,
+        };
+        let context = ToolContext::new(Arc::new(NoopDriver), SessionManager::default());
+        let registry = default_registry(&ToolsConfig::default(), session);
+        let server = Arc::new(McpServer::new(registry, context));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}{}", listener.local_addr().unwrap(), MCP_PATH);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let handle = tokio::spawn(serve_http(
+            server,
+            listener,
+            Some(TOKEN.to_string()),
+            async move {
+                let _ = shutdown_rx.await;
+            },
+        ));
+
+        Self {
+            url,
+            client: Client::new(),
+            shutdown: Some(shutdown_tx),
+            handle,
+        }
+    }
+
+    async fn post(&self, session: Option<&str>, body: Value) -> reqwest::Response {
+        let mut request = self.client.post(&self.url).bearer_auth(TOKEN).json(&body);
+        if let Some(session) = session {
+            request = request.header(SESSION_HEADER, session);
+        }
+        request.send().await.unwrap()
+    }
+
+    /// Initialize a session and return its ID
+    async fn initialize(&self) -> String {
+        let response = self
+            .post(
+                None,
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "initialize",
+                    "params": {
+                        "protocolVersion": "2024-11-05",
+                        "capabilities": {},
+                        "clientInfo": {"name": "http-test", "version": "1.0"}
+                    }
+                }),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        response.headers()[SESSION_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    async fn stop(mut self) {
+        self.shutdown.take().unwrap().send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), self.handle)
+            .await
+            .expect("server did not shut down")
+            .unwrap()
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_requests_require_bearer_token() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .client
+        .post(&server.url)
+        .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()["www-authenticate"], "Bearer");
+
+    let response = server
+        .client
+        .get(&server.url)
+        .bearer_auth("wrong-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn test_session_lifecycle() {
+    let server = TestServer::start().await;
+    let session = server.initialize().await;
+
+    let response = server
+        .post(
+            Some(&session),
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    let response = server
+        .post(
+            Some(&session),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    let tools = body["result"]["tools"].as_array().unwrap();
+    assert!(tools.iter().any(|t| t["name"] == "twitter_post"));
+
+    let response = server
+        .client
+        .delete(&server.url)
+        .bearer_auth(TOKEN)
+        .header(SESSION_HEADER, &session)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = server
+        .post(
+            Some(&session),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "ping"}),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn test_requests_outside_a_session_are_rejected() {
+    let server = TestServer::start().await;
+
+    let response = server
+        .post(None, json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post(
+            Some("not-a-session"),
+            json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn test_rejected_initialize_leaves_no_session() {
+    let server = TestServer::start().await;
+
+    // No protocol version
+    let response = server
+        .post(
+            None,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(SESSION_HEADER).is_none());
+    let body: Value = response.json().await.unwrap();
+    assert!(body["error"].is_object());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn test_sessions_are_independent() {
+    let server = TestServer::start().await;
+    let first = server.initialize().await;
+    let second = server.initialize().await;
+    assert_ne!(first, second);
+
+    let response = server
+        .post(
+            Some(&second),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "ping"}),
+        )
+        .await;
+    assert_eq!(response.headers()[SESSION_HEADER], second.as_str());
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["result"], json!({}));
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn test_shutdown_closes_notification_stream() {
+    let server = TestServer::start().await;
+    let session = server.initialize().await;
+
+    let response = server
+        .client
+        .get(&server.url)
+        .bearer_auth(TOKEN)
+        .header(SESSION_HEADER, &session)
+        .header("accept", "text/event-stream")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    let mut events = response.bytes_stream();
+    server.stop().await;
+
+    let end = tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(chunk) = events.next().await {
+            if chunk.is_err() {
+                break;
+            }
+        }
+    })
+    .await;
+    assert!(
+        end.is_ok(),
+        "notification stream was not closed on shutdown"
+    );
+}